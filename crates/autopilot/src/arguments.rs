@@ -7,7 +7,11 @@ use {
         http_client,
         price_estimation,
     },
-    std::{net::SocketAddr, num::NonZeroUsize, time::Duration},
+    std::{
+        net::SocketAddr,
+        num::{NonZeroU64, NonZeroUsize},
+        time::Duration,
+    },
     url::Url,
 };
 
@@ -187,6 +191,75 @@ pub struct Arguments {
     /// Driver base URLs.
     #[clap(long, env, use_value_delimiter = true)]
     pub drivers: Vec<Url>,
+
+    /// The maximum size in bytes of a driver response body. Larger responses
+    /// are rejected.
+    #[clap(long, env, default_value = "10000000")]
+    pub driver_response_size_limit: usize,
+
+    /// The maximum time in seconds to wait for a driver response.
+    #[clap(
+        long,
+        env,
+        default_value = "60",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub driver_response_time_limit: Duration,
+
+    /// How many times a driver request is retried after failing to connect
+    /// to the driver. Requests are never retried once a connection was
+    /// established.
+    #[clap(long, env, default_value = "1")]
+    pub driver_send_retries: usize,
+
+    /// The maximum number of idle connections kept open per driver. Uses
+    /// reqwest's default if unset.
+    #[clap(long, env)]
+    pub driver_pool_max_idle_per_host: Option<usize>,
+
+    /// The time in seconds after which idle driver connections are closed.
+    /// Uses reqwest's default if unset.
+    #[clap(long, env, value_parser = shared::arguments::duration_from_seconds)]
+    pub driver_pool_idle_timeout: Option<Duration>,
+
+    /// Which driver endpoint to use for executing the winning solution.
+    #[clap(long, env, default_value = "execute", value_enum)]
+    pub driver_execute_endpoint: crate::driver_api::ExecuteEndpoint,
+
+    /// The maximum time in seconds to wait for the settlement transaction of
+    /// the winning solution to be mined.
+    #[clap(
+        long,
+        env,
+        default_value = "60",
+        value_parser = shared::arguments::duration_from_seconds,
+    )]
+    pub settlement_max_wait_time: Duration,
+
+    /// The settlement transaction is polled for this many times per network
+    /// block interval. Must be positive.
+    #[clap(long, env, default_value = "2", value_parser = parse_poll_divisor)]
+    pub settlement_poll_divisor: f32,
+
+    /// Which orders an auction needs to contain for it to be sent to the
+    /// drivers.
+    #[clap(long, env, default_value = "non-liquidity", value_enum)]
+    pub solve_gate: crate::run_loop::SolveGate,
+
+    /// Run the solver competition without executing the winning solution.
+    #[clap(long, env)]
+    pub dry_run: bool,
+
+    /// The minimum score the winning solution needs to have for it to be
+    /// executed. Solutions with lower scores don't cover their costs.
+    #[clap(long, env, default_value = "0")]
+    pub min_score: f64,
+
+    /// Express the solve deadline as this many blocks after the auction's
+    /// block, using the network block interval to convert it to a timestamp.
+    /// Must be at least 1. By default a fixed wall clock time limit is used.
+    #[clap(long, env)]
+    pub solve_deadline_blocks: Option<NonZeroU64>,
 }
 
 impl std::fmt::Display for Arguments {
@@ -265,6 +338,59 @@ impl std::fmt::Display for Arguments {
         )?;
         writeln!(f, "enable_colocation: {:?}", self.enable_colocation,)?;
         display_list(f, "drivers", self.drivers.iter())?;
+        writeln!(
+            f,
+            "driver_response_size_limit: {}",
+            self.driver_response_size_limit
+        )?;
+        writeln!(
+            f,
+            "driver_response_time_limit: {:?}",
+            self.driver_response_time_limit
+        )?;
+        writeln!(f, "driver_send_retries: {}", self.driver_send_retries)?;
+        display_option(
+            f,
+            "driver_pool_max_idle_per_host",
+            &self.driver_pool_max_idle_per_host,
+        )?;
+        display_option(
+            f,
+            "driver_pool_idle_timeout",
+            &self
+                .driver_pool_idle_timeout
+                .map(|duration| duration.as_secs_f32()),
+        )?;
+        writeln!(
+            f,
+            "driver_execute_endpoint: {:?}",
+            self.driver_execute_endpoint
+        )?;
+        writeln!(
+            f,
+            "settlement_max_wait_time: {:?}",
+            self.settlement_max_wait_time
+        )?;
+        writeln!(
+            f,
+            "settlement_poll_divisor: {}",
+            self.settlement_poll_divisor
+        )?;
+        writeln!(f, "solve_gate: {:?}", self.solve_gate)?;
+        writeln!(f, "dry_run: {}", self.dry_run)?;
+        writeln!(f, "min_score: {}", self.min_score)?;
+        display_option(f, "solve_deadline_blocks", &self.solve_deadline_blocks)?;
         Ok(())
     }
 }
+
+/// Parses the settlement poll divisor, which the block interval is divided
+/// by and therefore needs to be positive.
+fn parse_poll_divisor(s: &str) -> anyhow::Result<f32> {
+    let divisor = s.parse::<f32>()?;
+    anyhow::ensure!(
+        divisor.is_finite() && divisor > 0.,
+        "poll divisor must be positive"
+    );
+    Ok(divisor)
+}
@@ -1,5 +1,5 @@
 use {
-    crate::driver_model::{execute, solve},
+    crate::driver_model::{execute, reveal, solve},
     anyhow::{anyhow, Context, Result},
     reqwest::Client,
     shared::http_client::response_body_with_size_limit,
@@ -7,37 +7,113 @@ use {
     url::Url,
 };
 
-const RESPONSE_SIZE_LIMIT: usize = 10_000_000;
-const RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(60);
+pub const DEFAULT_RESPONSE_SIZE_LIMIT: usize = 10_000_000;
+pub const DEFAULT_RESPONSE_TIME_LIMIT: Duration = Duration::from_secs(60);
+pub const DEFAULT_SEND_RETRIES: usize = 1;
+const SEND_RETRY_BACKOFF: Duration = Duration::from_millis(100);
 
 pub struct Driver {
     url: Url,
     client: Client,
+    response_size_limit: usize,
+    send_retries: usize,
+    execute_endpoint: ExecuteEndpoint,
+}
+
+/// The driver endpoint used to execute a solution.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum ExecuteEndpoint {
+    /// `POST /execute` with an [`execute::Request`] body.
+    #[default]
+    Execute,
+    /// Legacy `POST /settle/{solution_id}` without a body.
+    Settle,
 }
 
 impl Driver {
-    pub fn new(url: Url) -> Self {
+    /// Creates a driver client that rejects responses larger than
+    /// `response_size_limit` bytes or taking longer than
+    /// `response_time_limit` to arrive. The connection pool uses reqwest's
+    /// defaults unless `pool_max_idle_per_host` or `pool_idle_timeout` are
+    /// specified.
+    pub fn new(
+        url: Url,
+        response_size_limit: usize,
+        response_time_limit: Duration,
+        pool_max_idle_per_host: Option<usize>,
+        pool_idle_timeout: Option<Duration>,
+    ) -> Self {
+        let mut client = Client::builder().timeout(response_time_limit);
+        if let Some(max_idle) = pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(timeout) = pool_idle_timeout {
+            client = client.pool_idle_timeout(timeout);
+        }
         Self {
             url,
-            client: Client::builder()
-                .timeout(RESPONSE_TIME_LIMIT)
-                .build()
-                .unwrap(),
+            client: client.build().unwrap(),
+            response_size_limit,
+            send_retries: DEFAULT_SEND_RETRIES,
+            execute_endpoint: Default::default(),
         }
     }
 
+    /// Sets which endpoint is used to execute solutions.
+    pub fn with_execute_endpoint(mut self, execute_endpoint: ExecuteEndpoint) -> Self {
+        self.execute_endpoint = execute_endpoint;
+        self
+    }
+
+    /// Sets how many times a request is retried after failing to connect to
+    /// the driver. Requests are never retried once a connection was
+    /// established, so that a settlement can't get executed twice.
+    pub fn with_send_retries(mut self, send_retries: usize) -> Self {
+        self.send_retries = send_retries;
+        self
+    }
+
+    /// Creates a driver client with the default HTTP limits.
+    pub fn with_default_limits(url: Url) -> Self {
+        Self::new(
+            url,
+            DEFAULT_RESPONSE_SIZE_LIMIT,
+            DEFAULT_RESPONSE_TIME_LIMIT,
+            None,
+            None,
+        )
+    }
+
     pub async fn solve(&self, request: &solve::Request) -> Result<solve::Response> {
-        self.request_response(&["solve"], Some(request)).await
+        let body = self.request(&["solve"], Some(request)).await?;
+        parse_body(&body).map_err(|err| with_key_diagnostics(err, &body, solve::Response::KEYS))
+    }
+
+    /// Reveals the calldata of a previously computed solution without
+    /// executing it.
+    pub async fn reveal(&self, solution_id: &str) -> Result<reveal::Response> {
+        self.request_response(&["reveal", solution_id], Option::<&()>::None)
+            .await
     }
 
+    /// Executes a solution. Returns [`None`] when using the legacy `settle`
+    /// endpoint, which does not describe the executed solution.
     pub async fn execute(
         &self,
         solution_id: &str,
-        _request: &execute::Request,
-    ) -> Result<execute::Response> {
-        // TODO: should be execute
-        self.request_response(&["settle", solution_id], Option::<&()>::None)
-            .await
+        request: &execute::Request,
+    ) -> Result<Option<execute::Response>> {
+        match self.execute_endpoint {
+            ExecuteEndpoint::Execute => self
+                .request_response(&["execute"], Some(request))
+                .await
+                .map(Some),
+            ExecuteEndpoint::Settle => {
+                self.request(&["settle", solution_id], Option::<&()>::None)
+                    .await?;
+                Ok(None)
+            }
+        }
     }
 
     async fn request_response<Response>(
@@ -48,6 +124,16 @@ impl Driver {
     where
         Response: serde::de::DeserializeOwned,
     {
+        let body = self.request(path, request).await?;
+        parse_body(&body)
+    }
+
+    /// Sends a request and returns the body of the successful response.
+    async fn request(
+        &self,
+        path: &[&str],
+        request: Option<&impl serde::Serialize>,
+    ) -> Result<Vec<u8>> {
         let mut url = self.url.clone();
         let mut segments = url.path_segments_mut().unwrap();
         for path in path {
@@ -57,6 +143,7 @@ impl Driver {
         let request = if let Some(request) = request {
             tracing::trace!(
                 path=&url.path(),
+                size=serde_json::to_vec(request).unwrap().len(),
                 body=%serde_json::to_string_pretty(request).unwrap(),
                 "request",
             );
@@ -65,17 +152,293 @@ impl Driver {
             tracing::trace!(path=%url.path(), "request");
             self.client.post(url)
         };
-        let mut response = request.send().await.context("send")?;
+        let mut attempt = 0;
+        let mut response = loop {
+            let request = request
+                .try_clone()
+                .expect("request bodies are not streamed");
+            match request.send().await {
+                Ok(response) => break response,
+                Err(err) if attempt < self.send_retries && is_connection_error(&err) => {
+                    attempt += 1;
+                    tracing::debug!(?err, attempt, "retrying driver request");
+                    tokio::time::sleep(SEND_RETRY_BACKOFF * attempt as u32).await;
+                }
+                Err(err) => return Err(err).context("send"),
+            }
+        };
         let status = response.status().as_u16();
-        let body = response_body_with_size_limit(&mut response, RESPONSE_SIZE_LIMIT)
+        let body = response_body_with_size_limit(&mut response, self.response_size_limit)
             .await
-            .context("body")?;
+            .with_context(|| format!("body (limit {} bytes)", self.response_size_limit))?;
         let text = String::from_utf8_lossy(&body);
-        tracing::trace!(body=%text, "response");
+        tracing::trace!(size = body.len(), body=%text, "response");
         if status != 200 {
             let body = std::str::from_utf8(&body).context("body text")?;
             return Err(anyhow!("bad status {}, body {:?}", status, body));
         }
-        serde_json::from_slice(&body).with_context(|| format!("body json: {text}"))
+        Ok(body)
+    }
+}
+
+fn parse_body<Response>(body: &[u8]) -> Result<Response>
+where
+    Response: serde::de::DeserializeOwned,
+{
+    serde_json::from_slice(body)
+        .with_context(|| format!("body json: {}", String::from_utf8_lossy(body)))
+}
+
+/// Adds the expected top-level JSON keys that are missing from a response body
+/// that failed to deserialize to the error, and logs which keys were present.
+fn with_key_diagnostics(err: anyhow::Error, body: &[u8], expected: &[&str]) -> anyhow::Error {
+    let object = match serde_json::from_slice::<serde_json::Map<String, serde_json::Value>>(body) {
+        Ok(object) => object,
+        Err(_) => return err,
+    };
+    let present = object.keys().map(String::as_str).collect::<Vec<_>>();
+    let missing = expected
+        .iter()
+        .filter(|key| !object.contains_key(**key))
+        .collect::<Vec<_>>();
+    tracing::debug!(?present, ?expected, "malformed driver response");
+    err.context(format!(
+        "present keys {present:?}, missing keys {missing:?}"
+    ))
+}
+
+/// Whether the request failed before a connection to the driver was
+/// established, meaning that the driver can't have received the request. Any
+/// other error (including timeouts and connections closed without a response)
+/// might happen after the driver already started handling the request.
+fn is_connection_error(err: &reqwest::Error) -> bool {
+    err.is_connect() && !err.is_timeout()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use {
+        super::*,
+        std::sync::{Arc, Mutex},
+        tokio::{
+            io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+            net::TcpListener,
+        },
+    };
+
+    /// A request received by the mock driver.
+    #[derive(Debug)]
+    pub(crate) struct Request {
+        pub(crate) path: String,
+        pub(crate) body: String,
+    }
+
+    /// Starts a minimal HTTP server acting as a driver. Each accepted
+    /// connection is answered with the next `(status, body)` of `responses`,
+    /// or closed without a response for [`None`]. Returns the server URL and
+    /// the requests it received.
+    pub(crate) async fn mock_driver(
+        responses: Vec<Option<(u16, String)>>,
+    ) -> (Url, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        (url, serve(listener, responses))
+    }
+
+    /// Answers the connections accepted by `listener` as described in
+    /// [`mock_driver`].
+    fn serve(
+        listener: TcpListener,
+        responses: Vec<Option<(u16, String)>>,
+    ) -> Arc<Mutex<Vec<Request>>> {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let requests_ = requests.clone();
+        tokio::task::spawn(async move {
+            for response in responses {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut stream = BufReader::new(stream);
+                let mut request_line = String::new();
+                stream.read_line(&mut request_line).await.unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_owned();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    stream.read_line(&mut line).await.unwrap();
+                    let line = line.trim_end().to_lowercase();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(length) = line.strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                stream.read_exact(&mut body).await.unwrap();
+                requests_.lock().unwrap().push(Request {
+                    path,
+                    body: String::from_utf8(body).unwrap(),
+                });
+                let (status, body) = match response {
+                    Some(response) => response,
+                    None => continue,
+                };
+                let response = format!(
+                    "HTTP/1.1 {status} OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len(),
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+        requests
+    }
+
+    #[tokio::test]
+    async fn rejects_response_over_size_limit() {
+        let body = r#"{"id":"1","score":"1.0"}"#;
+        let (url, _) = mock_driver(vec![
+            Some((200, body.to_owned())),
+            Some((200, body.to_owned())),
+        ])
+        .await;
+
+        let driver = Driver::new(url.clone(), body.len(), Duration::from_secs(1), None, None);
+        let response = driver.solve(&Default::default()).await.unwrap();
+        assert_eq!(response.id, "1");
+
+        let driver = Driver::new(url, body.len() - 1, Duration::from_secs(1), None, None);
+        let err = driver.solve(&Default::default()).await.unwrap_err();
+        let err = format!("{err:?}");
+        assert!(err.contains("size limit exceeded"));
+        assert!(err.contains(&format!("limit {} bytes", body.len() - 1)));
+        assert!(err.contains(&format!("observed at least {} bytes", body.len())));
+    }
+
+    #[tokio::test]
+    async fn custom_connection_pool() {
+        let body = r#"{"id":"1","score":"1.0"}"#;
+        let (url, _) = mock_driver(vec![Some((200, body.to_owned()))]).await;
+
+        let driver = Driver::new(
+            url,
+            DEFAULT_RESPONSE_SIZE_LIMIT,
+            DEFAULT_RESPONSE_TIME_LIMIT,
+            Some(4),
+            Some(Duration::from_secs(30)),
+        );
+        let response = driver.solve(&Default::default()).await.unwrap();
+        assert_eq!(response.id, "1");
+    }
+
+    #[tokio::test]
+    async fn retries_after_connection_error() {
+        // Reserve a port that nothing listens on yet, so that the first
+        // attempt fails to connect, and only start the driver afterwards.
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let url = format!("http://{addr}/").parse().unwrap();
+        let driver = Driver::with_default_limits(url);
+        let request = Default::default();
+        let solve = driver.solve(&request);
+        let start_driver = async {
+            tokio::time::sleep(SEND_RETRY_BACKOFF / 2).await;
+            let body = r#"{"id":"1","score":"1.0"}"#;
+            serve(
+                TcpListener::bind(addr).await.unwrap(),
+                vec![Some((200, body.to_owned()))],
+            )
+        };
+        let (response, requests) = tokio::join!(solve, start_driver);
+        assert_eq!(response.unwrap().id, "1");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_after_connection_was_established() {
+        let (url, requests) = mock_driver(vec![
+            None,
+            Some((200, r#"{"id":"1","score":"1.0"}"#.to_owned())),
+        ])
+        .await;
+
+        let driver = Driver::with_default_limits(url).with_send_retries(3);
+        driver.solve(&Default::default()).await.unwrap_err();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_bad_status() {
+        let (url, requests) = mock_driver(vec![
+            Some((500, "error".to_owned())),
+            Some((200, r#"{"id":"1","score":"1.0"}"#.to_owned())),
+        ])
+        .await;
+
+        let driver = Driver::with_default_limits(url).with_send_retries(3);
+        driver.solve(&Default::default()).await.unwrap_err();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_posts_request_body() {
+        let response = serde_json::json!({
+            "account": "0x0000000000000000000000000000000000000001",
+            "nonce": 0,
+            "clearingPrices": {},
+            "trades": [],
+            "internalizedInteractions": [],
+            "calldata": "0x01",
+            "signature": "",
+        });
+        let (url, requests) = mock_driver(vec![Some((200, response.to_string()))]).await;
+
+        let driver = Driver::with_default_limits(url);
+        let request = execute::Request {
+            auction_id: 42,
+            transaction_identifier: vec![0xca, 0xfe],
+        };
+        let response = driver.execute("1", &request).await.unwrap().unwrap();
+        assert_eq!(response.calldata, [1]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].path, "/execute");
+        let body: serde_json::Value = serde_json::from_str(&requests[0].body).unwrap();
+        assert_eq!(body["auctionId"], 42);
+        assert_eq!(body["transactionIdentifier"], "0xcafe");
+    }
+
+    #[tokio::test]
+    async fn execute_with_settle_endpoint() {
+        let (url, requests) = mock_driver(vec![Some((200, String::new()))]).await;
+
+        let driver =
+            Driver::with_default_limits(url).with_execute_endpoint(ExecuteEndpoint::Settle);
+        let response = driver.execute("1", &Default::default()).await.unwrap();
+        assert!(response.is_none());
+        assert_eq!(requests.lock().unwrap()[0].path, "/settle/1");
+    }
+
+    #[tokio::test]
+    async fn solve_error_names_missing_keys() {
+        let (url, _) = mock_driver(vec![Some((200, r#"{"id":"1"}"#.to_owned()))]).await;
+
+        let driver = Driver::with_default_limits(url);
+        let err = driver.solve(&Default::default()).await.unwrap_err();
+        assert!(err.to_string().contains(r#"missing keys ["score"]"#));
+    }
+
+    #[tokio::test]
+    async fn reveal_returns_calldata() {
+        let (url, requests) =
+            mock_driver(vec![Some((200, r#"{"calldata":"0x0102"}"#.to_owned()))]).await;
+
+        let driver = Driver::with_default_limits(url);
+        let response = driver.reveal("7").await.unwrap();
+        assert_eq!(response.calldata, [1, 2]);
+        assert_eq!(requests.lock().unwrap()[0].path, "/reveal/7");
     }
 }
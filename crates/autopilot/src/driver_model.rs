@@ -112,11 +112,61 @@ pub mod solve {
         Liquidity,
     }
 
+    #[serde_as]
     #[derive(Clone, Debug, Default, Deserialize)]
-    #[serde(deny_unknown_fields)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
     pub struct Response {
         pub id: String,
+        #[serde_as(as = "DisplayFromStr")]
         pub score: f64,
+        /// The score minus the cost of the gas used by the solution. Drivers
+        /// that don't report it are ranked by their `score`.
+        #[serde_as(as = "Option<DisplayFromStr>")]
+        #[serde(default)]
+        pub net_score: Option<f64>,
+    }
+
+    impl Response {
+        /// The top-level JSON keys of a solve response.
+        pub const KEYS: &'static [&'static str] = &["id", "score"];
+
+        /// The score used for ranking solutions, which is the net score when
+        /// it is reported.
+        pub fn ranking_score(&self) -> f64 {
+            self.net_score.unwrap_or(self.score)
+        }
+    }
+}
+
+pub mod reveal {
+    use {
+        derivative::Derivative,
+        model::bytes_hex,
+        serde::{Deserialize, Serialize},
+    };
+
+    #[derive(Clone, Derivative, Default, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    #[derivative(Debug)]
+    pub struct Response {
+        /// The calldata of the settlement transaction the driver promises to
+        /// execute.
+        #[serde(with = "bytes_hex")]
+        #[derivative(Debug(format_with = "shared::debug_bytes"))]
+        pub calldata: Vec<u8>,
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn response_round_trip() {
+            let json = serde_json::json!({ "calldata": "0x0102ff" });
+            let response: Response = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(response.calldata, [0x01, 0x02, 0xff]);
+            assert_eq!(serde_json::to_value(&response).unwrap(), json);
+        }
     }
 }
 
@@ -102,6 +102,9 @@ impl LivenessChecking for Liveness {
 
 /// Assumes tracing and metrics registry have already been set up.
 pub async fn main(args: arguments::Arguments) {
+    args.order_quoting
+        .validate()
+        .expect("invalid order quoting arguments");
     let db = Postgres::new(args.db_url.as_str()).await.unwrap();
     tokio::task::spawn(
         crate::database::database_metrics(db.clone())
@@ -198,11 +201,26 @@ pub async fn main(args: arguments::Arguments) {
             &http_factory,
             &web3,
             args.shared.gas_estimators.as_slice(),
+            args.shared.gas_estimation_mode,
             args.shared.blocknative_api_key.clone(),
         )
         .await
         .expect("failed to create gas price estimator"),
     );
+    let native_gas_price_estimator = match args.shared.native_price_gas_estimator {
+        Some(estimator) => Some(Arc::new(
+            shared::gas_price_estimation::create_priority_estimator(
+                &http_factory,
+                &web3,
+                &[estimator],
+                Default::default(),
+                args.shared.blocknative_api_key.clone(),
+            )
+            .await
+            .expect("failed to create native price gas estimator"),
+        )),
+        None => None,
+    };
 
     let baseline_sources = args.shared.baseline_sources.clone().unwrap_or_else(|| {
         shared::sources::defaults_for_chain(chain_id)
@@ -228,7 +246,7 @@ pub async fn main(args: arguments::Arguments) {
 
     let base_tokens = Arc::new(BaseTokens::new(
         native_token.address(),
-        &args.shared.base_tokens,
+        &args.shared.effective_base_tokens(),
     ));
     let mut allowed_tokens = args.allowed_tokens.clone();
     allowed_tokens.extend(base_tokens.tokens().iter().copied());
@@ -399,6 +417,7 @@ pub async fn main(args: arguments::Arguments) {
             uniswap_v3_pools: uniswap_v3_pool_fetcher.clone().map(|a| a as _),
             tokens: token_info_fetcher.clone(),
             gas_price: gas_price_estimator.clone(),
+            native_gas_price: native_gas_price_estimator.map(|a| a as _),
             zeroex: zeroex_api.clone(),
             oneinch: one_inch_api.ok().map(|a| a as _),
         },
@@ -409,12 +428,14 @@ pub async fn main(args: arguments::Arguments) {
         .price_estimator(
             &args.order_quoting.price_estimators,
             &args.order_quoting.price_estimation_drivers,
+            args.order_quoting.max_price_estimation_drivers,
         )
         .unwrap();
     let native_price_estimator = price_estimator_factory
         .native_price_estimator(
             &args.native_price_estimators,
             &args.order_quoting.price_estimation_drivers,
+            args.order_quoting.max_price_estimation_drivers,
         )
         .unwrap();
 
@@ -474,6 +495,7 @@ pub async fn main(args: arguments::Arguments) {
             &http_factory,
             &web3,
             args.shared.gas_estimators.as_slice(),
+            args.shared.gas_estimation_mode,
             args.shared.blocknative_api_key.clone(),
         )
         .await
@@ -655,11 +677,27 @@ pub async fn main(args: arguments::Arguments) {
             drivers: args
                 .drivers
                 .into_iter()
-                .map(driver_api::Driver::new)
+                .map(|url| {
+                    driver_api::Driver::new(
+                        url,
+                        args.driver_response_size_limit,
+                        args.driver_response_time_limit,
+                        args.driver_pool_max_idle_per_host,
+                        args.driver_pool_idle_timeout,
+                    )
+                    .with_send_retries(args.driver_send_retries)
+                    .with_execute_endpoint(args.driver_execute_endpoint)
+                })
                 .collect(),
             current_block: current_block_stream,
             web3,
             network_block_interval: network_time_between_blocks,
+            settlement_max_wait_time: args.settlement_max_wait_time,
+            settlement_poll_divisor: args.settlement_poll_divisor,
+            solve_gate: args.solve_gate,
+            dry_run: args.dry_run,
+            min_score: args.min_score,
+            solve_deadline_blocks: args.solve_deadline_blocks,
         };
         run.run_forever().await;
         unreachable!("run loop exited");
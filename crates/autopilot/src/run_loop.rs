@@ -4,24 +4,32 @@ use {
         driver_api::Driver,
         driver_model::{
             execute,
+            reveal,
             solve::{self, Class},
         },
         solvable_orders::SolvableOrdersCache,
     },
     anyhow::{anyhow, Context, Result},
-    chrono::Utc,
+    chrono::{DateTime, NaiveDateTime, Utc},
     model::{
         auction::{Auction, AuctionId},
-        order::{LimitOrderClass, OrderClass},
+        order::{LimitOrderClass, Order, OrderClass, OrderUid},
     },
     primitive_types::H256,
     rand::seq::SliceRandom,
     shared::{
-        current_block::CurrentBlockStream,
+        current_block::{timestamp_of_block_in_seconds, CurrentBlockStream},
         ethrpc::Web3,
         event_handling::MAX_REORG_BLOCK_COUNT,
     },
-    std::{collections::HashSet, sync::Arc, time::Duration},
+    std::{
+        collections::HashMap,
+        future::Future,
+        num::NonZeroU64,
+        ops::Range,
+        sync::Arc,
+        time::Duration,
+    },
     tracing::Instrument,
     web3::types::Transaction,
 };
@@ -35,6 +43,22 @@ pub struct RunLoop {
     pub current_block: CurrentBlockStream,
     pub web3: Web3,
     pub network_block_interval: Duration,
+    /// The maximum time to wait for the settlement transaction to be mined.
+    pub settlement_max_wait_time: Duration,
+    /// The number of times the settlement transaction is polled for per
+    /// network block interval.
+    pub settlement_poll_divisor: f32,
+    /// Which orders an auction needs to contain for it to be solved.
+    pub solve_gate: SolveGate,
+    /// Run the competition and log the winner without executing its
+    /// solution.
+    pub dry_run: bool,
+    /// The minimum ranking score the winning solution needs for it to be
+    /// executed.
+    pub min_score: f64,
+    /// Pins the solve deadline to this many blocks after the auction's block
+    /// instead of [`SOLVE_TIME_LIMIT`].
+    pub solve_deadline_blocks: Option<NonZeroU64>,
 }
 
 impl RunLoop {
@@ -67,25 +91,24 @@ impl RunLoop {
 
     async fn single_run_(&self, id: AuctionId, auction: &Auction) {
         tracing::info!("solving");
-        let mut solutions = self.solve(auction, id).await;
-
-        // Shuffle so that sorting randomly splits ties.
-        solutions.shuffle(&mut rand::thread_rng());
-        solutions.sort_unstable_by(|left, right| left.1.score.total_cmp(&right.1.score));
+        let solutions = self.solve(auction, id).await;
+        tracing::info!(
+            solutions = solutions.len(),
+            drivers = self.drivers.len(),
+            "received solutions"
+        );
+        let metrics = Metrics::get();
+        metrics.solutions.set(solutions.len() as i64);
+        metrics.drivers.set(self.drivers.len() as i64);
 
-        // TODO: Keep going with other solutions until some deadline.
-        if let Some((index, solution)) = solutions.pop() {
-            tracing::info!("executing with solver {}", index);
-            match self
-                .execute(auction, id, &self.drivers[index], &solution)
-                .await
-            {
-                Ok(()) => (),
-                Err(err) => {
-                    tracing::error!(?err, "solver {index} failed to execute");
-                }
-            }
-        }
+        execute_winner(
+            &self.drivers,
+            solutions,
+            self.dry_run,
+            self.min_score,
+            |driver, solution| async move { self.execute(auction, id, driver, &solution).await },
+        )
+        .await;
 
         // TODO:
         // - Think about what per auction information needs to be permanently
@@ -96,83 +119,46 @@ impl RunLoop {
 
     /// Returns the successful /solve responses and the index of the solver.
     async fn solve(&self, auction: &Auction, id: AuctionId) -> Vec<(usize, solve::Response)> {
-        if auction
-            .orders
-            .iter()
-            .all(|order| match order.metadata.class {
-                OrderClass::Market => false,
-                OrderClass::Liquidity => true,
-                OrderClass::Limit(_) => false,
-            })
-        {
+        // Orders that are expired at the time of the auction's block can't be
+        // settled in any later block.
+        let block_time = timestamp_of_block_in_seconds(&self.web3, auction.block.into()).await;
+        let block_time = match block_time {
+            Ok(block_time) => block_time,
+            Err(err) => {
+                tracing::warn!(
+                    ?err,
+                    block = auction.block,
+                    "failed to get auction block time"
+                );
+                return Default::default();
+            }
+        };
+        let now = Utc::now();
+        let deadline = solve_deadline(
+            self.solve_deadline_blocks,
+            self.network_block_interval,
+            block_time,
+            now,
+        );
+        let time_limit = match (deadline - now).to_std() {
+            Ok(time_limit) if !time_limit.is_zero() => time_limit,
+            _ => {
+                tracing::warn!(%deadline, "solve deadline already passed");
+                return Default::default();
+            }
+        };
+        let request = &solve_request(id, auction, block_time, deadline);
+        if let Some(reason) = self.solve_gate.skip_reason(&request.orders) {
+            tracing::debug!(reason, "skipping auction");
             return Default::default();
         }
+        tracing::debug!(
+            auction_id = request.id,
+            orders = ?request_orders(request),
+            "sending solve request"
+        );
 
-        let request = &solve::Request {
-            id,
-            orders: auction
-                .orders
-                .iter()
-                .map(|order| {
-                    let (class, surplus_fee) = match order.metadata.class {
-                        OrderClass::Market => (Class::Market, None),
-                        OrderClass::Liquidity => (Class::Liquidity, None),
-                        OrderClass::Limit(LimitOrderClass { surplus_fee, .. }) => {
-                            (Class::Limit, surplus_fee)
-                        }
-                    };
-                    solve::Order {
-                        uid: order.metadata.uid,
-                        sell_token: order.data.sell_token,
-                        buy_token: order.data.buy_token,
-                        sell_amount: order.data.sell_amount,
-                        buy_amount: order.data.buy_amount,
-                        solver_fee: order.metadata.full_fee_amount,
-                        user_fee: order.data.fee_amount,
-                        valid_to: order.data.valid_to,
-                        kind: order.data.kind,
-                        receiver: order.data.receiver,
-                        owner: order.metadata.owner,
-                        partially_fillable: order.data.partially_fillable,
-                        executed: Default::default(),
-                        pre_interactions: Default::default(),
-                        sell_token_balance: order.data.sell_token_balance,
-                        buy_token_balance: order.data.buy_token_balance,
-                        class,
-                        surplus_fee,
-                        app_data: order.data.app_data,
-                        reward: Default::default(),
-                        signature: order.signature.clone(),
-                    }
-                })
-                .collect(),
-            prices: auction.prices.clone(),
-            deadline: Utc::now() + chrono::Duration::from_std(SOLVE_TIME_LIMIT).unwrap(),
-        };
-        let futures = self
-            .drivers
-            .iter()
-            .enumerate()
-            .map(|(index, driver)| async move {
-                let result =
-                    match tokio::time::timeout(SOLVE_TIME_LIMIT, driver.solve(request)).await {
-                        Ok(inner) => inner,
-                        Err(_) => Err(anyhow!("timeout")),
-                    };
-                (index, result)
-            })
-            .collect::<Vec<_>>();
-        let results = futures::future::join_all(futures).await;
-        results
-            .into_iter()
-            .filter_map(|(index, result)| match result {
-                Ok(result) => Some((index, result)),
-                Err(err) => {
-                    tracing::warn!(?err, "driver solve error");
-                    None
-                }
-            })
-            .collect()
+        solve_with_drivers(&self.drivers, request, time_limit).await
     }
 
     /// Execute the solver's solution. Returns Ok when the corresponding
@@ -188,10 +174,7 @@ impl RunLoop {
             auction_id: id,
             transaction_identifier: id.to_be_bytes().into(),
         };
-        let _response = driver
-            .execute(&solution.id, &request)
-            .await
-            .context("execute")?;
+        let revealed = reveal_and_execute(driver, &solution.id, &request).await?;
         // TODO: React to deadline expiring.
         let transaction = self
             .wait_for_settlement_transaction(&request.transaction_identifier)
@@ -199,25 +182,38 @@ impl RunLoop {
             .context("wait for settlement transaction")?;
         if let Some(tx) = transaction {
             tracing::debug!("settled in tx {:?}", tx.hash);
+            if let Some(revealed) = &revealed {
+                Self::verify_revealed_calldata(revealed, &tx);
+            }
         }
         Ok(())
     }
 
+    /// Warns if the settlement transaction does not execute the calldata that
+    /// was revealed by the driver.
+    fn verify_revealed_calldata(revealed: &reveal::Response, tx: &Transaction) {
+        if !tx.input.0.starts_with(&revealed.calldata) {
+            tracing::warn!(
+                ?tx.hash,
+                "settlement transaction calldata differs from revealed calldata"
+            );
+        }
+    }
+
     /// Tries to find a `settle` contract call with calldata ending in `tag`.
     ///
     /// Returns None if no transaction was found within the deadline.
     pub async fn wait_for_settlement_transaction(&self, tag: &[u8]) -> Result<Option<Transaction>> {
-        const MAX_WAIT_TIME: Duration = Duration::from_secs(60);
         // Start earlier than current block because there might be a delay when
         // receiving the Solver's /execute response during which it already
         // started broadcasting the tx.
-        let start_offset = MAX_REORG_BLOCK_COUNT;
-        let max_wait_time_blocks =
-            (MAX_WAIT_TIME.as_secs_f32() / self.network_block_interval.as_secs_f32()).ceil() as u64;
         let current = self.current_block.borrow().number;
-        let start = current.saturating_sub(start_offset);
-        let deadline = current.saturating_add(max_wait_time_blocks);
-        tracing::debug!(%current, %start, %deadline, ?tag, "waiting for tag");
+        let blocks = settlement_blocks(
+            current,
+            self.settlement_max_wait_time,
+            self.network_block_interval,
+        );
+        tracing::debug!(%current, ?blocks, ?tag, "waiting for tag");
 
         // Use the existing event indexing infrastructure to find the transaction. We
         // query all settlement events in the block range to get tx hashes and
@@ -227,41 +223,818 @@ impl RunLoop {
         // moving the starting block up while taking reorgs into account. With
         // the current range of 30 blocks this isn't necessary.
         //
-        // We do keep track of hashes we have already seen to reduce load from the node.
+        // We do keep track of transactions we have already fetched to reduce load from
+        // the node.
+        let database = &self.database;
+        poll_settlement_transaction(
+            move |blocks| database.recent_settlement_tx_hashes(blocks),
+            &self.web3,
+            &self.current_block,
+            blocks,
+            self.network_block_interval
+                .div_f32(self.settlement_poll_divisor),
+            tag,
+        )
+        .await
+    }
+}
 
-        let mut seen_transactions: HashSet<H256> = Default::default();
-        loop {
-            // This could be a while loop. It isn't, because some care must be taken to not
-            // accidentally keep the borrow alive, which would block senders. Technically
-            // this is fine with while conditions but this is clearer.
-            if self.current_block.borrow().number <= deadline {
-                break;
-            }
-            let mut hashes = self
-                .database
-                .recent_settlement_tx_hashes(start..deadline + 1)
-                .await?;
-            hashes.retain(|hash| !seen_transactions.contains(hash));
-            for hash in hashes {
-                let tx: Option<Transaction> = self
-                    .web3
+/// Reveals the solution and then executes it. Drivers that don't support
+/// revealing solutions (like ones only serving `/settle`) still get to execute
+/// them, in which case [`None`] is returned and the settlement calldata can't
+/// be verified.
+async fn reveal_and_execute(
+    driver: &Driver,
+    solution_id: &str,
+    request: &execute::Request,
+) -> Result<Option<reveal::Response>> {
+    let revealed = match driver.reveal(solution_id).await {
+        Ok(revealed) => {
+            tracing::debug!(?revealed, "revealed solution");
+            Some(revealed)
+        }
+        Err(err) => {
+            tracing::warn!(?err, "failed to reveal solution");
+            None
+        }
+    };
+    driver
+        .execute(solution_id, request)
+        .await
+        .context("execute")?;
+    Ok(revealed)
+}
+
+/// The blocks to search for the settlement transaction in when the current
+/// block is `current`. The range ends at the last block that is mined within
+/// `max_wait_time`.
+fn settlement_blocks(
+    current: u64,
+    max_wait_time: Duration,
+    block_interval: Duration,
+) -> Range<u64> {
+    let start = current.saturating_sub(MAX_REORG_BLOCK_COUNT);
+    let deadline = current.saturating_add(blocks_within(max_wait_time, block_interval));
+    start..deadline + 1
+}
+
+/// Polls for a settlement transaction in the specified block range whose
+/// calldata ends in `tag`, until the current block is past the range.
+///
+/// `recent_hashes` returns the hashes of the settlement transactions in a
+/// block range.
+async fn poll_settlement_transaction<F>(
+    recent_hashes: impl Fn(Range<u64>) -> F,
+    web3: &Web3,
+    current_block: &CurrentBlockStream,
+    blocks: Range<u64>,
+    poll_interval: Duration,
+    tag: &[u8],
+) -> Result<Option<Transaction>>
+where
+    F: Future<Output = Result<Vec<H256>>>,
+{
+    let mut transactions = TransactionCache::default();
+    loop {
+        // This could be a while loop. It isn't, because some care must be taken to not
+        // accidentally keep the borrow alive, which would block senders. Technically
+        // this is fine with while conditions but this is clearer.
+        if current_block.borrow().number >= blocks.end {
+            break;
+        }
+        let hashes = recent_hashes(blocks.clone()).await?;
+        if let Some(tx) = transactions.find(web3, &hashes, tag).await? {
+            return Ok(Some(tx));
+        }
+        // It would be more correct to wait until just after the last event update run,
+        // but that is hard to synchronize.
+        tokio::time::sleep(poll_interval).await;
+    }
+    Ok(None)
+}
+
+/// Which orders an auction needs to contain for it to be sent to the drivers.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum SolveGate {
+    /// Require at least one market or limit order.
+    #[default]
+    NonLiquidity,
+    /// Require at least one limit order.
+    Limit,
+}
+
+impl SolveGate {
+    /// Returns why an auction with the specified orders should not be solved,
+    /// or [`None`] if it should be.
+    fn skip_reason(self, orders: &[solve::Order]) -> Option<&'static str> {
+        match self {
+            Self::NonLiquidity => orders
+                .iter()
+                .all(|order| matches!(order.class, Class::Liquidity))
+                .then_some("no market or limit orders"),
+            Self::Limit => orders
+                .iter()
+                .all(|order| !matches!(order.class, Class::Limit))
+                .then_some("no limit orders"),
+        }
+    }
+}
+
+/// Transactions fetched from the node while waiting for a settlement
+/// transaction. Each transaction is only fetched once.
+#[derive(Default)]
+struct TransactionCache(HashMap<H256, Transaction>);
+
+impl TransactionCache {
+    /// Returns the first of the specified transactions with calldata ending in
+    /// `tag`. Transactions that are not yet cached are fetched from the node.
+    async fn find(
+        &mut self,
+        web3: &Web3,
+        hashes: &[H256],
+        tag: &[u8],
+    ) -> Result<Option<Transaction>> {
+        for hash in hashes {
+            if !self.0.contains_key(hash) {
+                let tx: Option<Transaction> = web3
                     .eth()
-                    .transaction(web3::types::TransactionId::Hash(hash))
+                    .transaction(web3::types::TransactionId::Hash(*hash))
                     .await
                     .with_context(|| format!("web3 transaction {hash:?}"))?;
-                let tx: Transaction = match tx {
-                    Some(tx) => tx,
+                match tx {
+                    Some(tx) => self.0.insert(*hash, tx),
+                    // The node doesn't know the transaction yet, try again next time.
                     None => continue,
                 };
-                if tx.input.0.ends_with(tag) {
-                    return Ok(Some(tx));
-                }
-                seen_transactions.insert(hash);
             }
-            // It would be more correct to wait until just after the last event update run,
-            // but that is hard to synchronize.
-            tokio::time::sleep(self.network_block_interval.div_f32(2.)).await;
+            let tx = &self.0[hash];
+            if tx.input.0.ends_with(tag) {
+                return Ok(Some(tx.clone()));
+            }
         }
         Ok(None)
     }
 }
+
+/// Has the driver of the winning solution execute it. Nothing is executed if
+/// there is no winner, see [`solution_to_execute`].
+async fn execute_winner<'a, F>(
+    drivers: &'a [Driver],
+    solutions: Vec<(usize, solve::Response)>,
+    dry_run: bool,
+    min_score: f64,
+    execute: impl FnOnce(&'a Driver, solve::Response) -> F,
+) where
+    F: Future<Output = Result<()>>,
+{
+    // TODO: Keep going with other solutions until some deadline.
+    if let Some((index, solution)) = solution_to_execute(solutions, dry_run, min_score) {
+        tracing::info!("executing with solver {}", index);
+        match execute(&drivers[index], solution).await {
+            Ok(()) => (),
+            Err(err) => {
+                tracing::error!(?err, "solver {index} failed to execute");
+            }
+        }
+    }
+}
+
+/// Picks the solution with the highest ranking score, splitting ties randomly.
+/// [`None`] is returned if the winner's score is below `min_score`. In dry-run
+/// mode the winner is only logged and [`None`] is returned so that it does not
+/// get executed.
+fn solution_to_execute(
+    mut solutions: Vec<(usize, solve::Response)>,
+    dry_run: bool,
+    min_score: f64,
+) -> Option<(usize, solve::Response)> {
+    // Shuffle so that sorting randomly splits ties.
+    solutions.shuffle(&mut rand::thread_rng());
+    solutions
+        .sort_unstable_by(|left, right| left.1.ranking_score().total_cmp(&right.1.ranking_score()));
+    let (index, solution) = solutions.pop()?;
+    if solution.ranking_score() < min_score {
+        tracing::info!(
+            solver = index,
+            score = solution.ranking_score(),
+            min_score,
+            "no economically viable solution"
+        );
+        return None;
+    }
+    if dry_run {
+        tracing::info!(
+            solver = index,
+            solution = %solution.id,
+            score = solution.ranking_score(),
+            "dry run, not executing winning solution"
+        );
+        return None;
+    }
+    Some((index, solution))
+}
+
+/// The number of blocks that are mined within the specified duration, rounded
+/// up.
+fn blocks_within(duration: Duration, block_interval: Duration) -> u64 {
+    (duration.as_secs_f32() / block_interval.as_secs_f32()).ceil() as u64
+}
+
+/// The deadline for solving an auction. This is [`SOLVE_TIME_LIMIT`] from now
+/// unless the deadline is pinned to a number of blocks, in which case it is
+/// the time those blocks are expected to be mined after the auction's block.
+fn solve_deadline(
+    deadline_blocks: Option<NonZeroU64>,
+    block_interval: Duration,
+    block_time: u32,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    match deadline_blocks {
+        Some(blocks) => {
+            let block_time =
+                DateTime::from_utc(NaiveDateTime::from_timestamp(block_time.into(), 0), Utc);
+            let time_limit = block_interval.mul_f64(blocks.get() as f64);
+            block_time + chrono::Duration::from_std(time_limit).unwrap()
+        }
+        None => now + chrono::Duration::from_std(SOLVE_TIME_LIMIT).unwrap(),
+    }
+}
+
+/// Builds the /solve request for the auction. Orders that are already expired
+/// at the specified timestamp of the auction's block are skipped.
+fn solve_request(
+    id: AuctionId,
+    auction: &Auction,
+    block_time: u32,
+    deadline: chrono::DateTime<Utc>,
+) -> solve::Request {
+    let orders = auction
+        .orders
+        .iter()
+        .filter(|order| order.data.valid_to > block_time)
+        .map(solve_order)
+        .collect::<Vec<_>>();
+    let expired = auction.orders.len() - orders.len();
+    if expired > 0 {
+        tracing::debug!(expired, "skipping expired orders");
+    }
+
+    solve::Request {
+        id,
+        orders,
+        prices: auction.prices.clone(),
+        deadline,
+    }
+}
+
+/// The uids and classes of the orders in a /solve request, for correlating
+/// failed settlements with the orders of the auction.
+fn request_orders(request: &solve::Request) -> Vec<(OrderUid, &Class)> {
+    request
+        .orders
+        .iter()
+        .map(|order| (order.uid, &order.class))
+        .collect()
+}
+
+/// Converts an auction order into the order sent to the drivers.
+fn solve_order(order: &Order) -> solve::Order {
+    let (class, surplus_fee) = match order.metadata.class {
+        OrderClass::Market => (Class::Market, None),
+        OrderClass::Liquidity => (Class::Liquidity, None),
+        OrderClass::Limit(LimitOrderClass { surplus_fee, .. }) => (Class::Limit, surplus_fee),
+    };
+    // Limit orders are priced against their dynamically computed surplus fee,
+    // so use it as the solver fee when it is available.
+    let solver_fee = match class {
+        Class::Limit => surplus_fee.unwrap_or(order.metadata.full_fee_amount),
+        Class::Market | Class::Liquidity => order.metadata.full_fee_amount,
+    };
+    solve::Order {
+        uid: order.metadata.uid,
+        sell_token: order.data.sell_token,
+        buy_token: order.data.buy_token,
+        sell_amount: order.data.sell_amount,
+        buy_amount: order.data.buy_amount,
+        solver_fee,
+        user_fee: order.data.fee_amount,
+        valid_to: order.data.valid_to,
+        kind: order.data.kind,
+        receiver: order.data.receiver,
+        owner: order.metadata.owner,
+        partially_fillable: order.data.partially_fillable,
+        executed: Default::default(),
+        pre_interactions: Default::default(),
+        sell_token_balance: order.data.sell_token_balance,
+        buy_token_balance: order.data.buy_token_balance,
+        class,
+        surplus_fee,
+        app_data: order.data.app_data,
+        reward: Default::default(),
+        signature: order.signature.clone(),
+    }
+}
+
+/// Sends the /solve request to all drivers concurrently. Returns the successful
+/// responses and the index of the solver.
+async fn solve_with_drivers(
+    drivers: &[Driver],
+    request: &solve::Request,
+    time_limit: Duration,
+) -> Vec<(usize, solve::Response)> {
+    let futures = drivers
+        .iter()
+        .enumerate()
+        .map(|(index, driver)| async move {
+            let result = match tokio::time::timeout(time_limit, driver.solve(request)).await {
+                Ok(inner) => inner,
+                Err(_) => Err(anyhow!("timeout")),
+            };
+            (index, result)
+        })
+        .collect::<Vec<_>>();
+    let results = futures::future::join_all(futures).await;
+    results
+        .into_iter()
+        .filter_map(|(index, result)| match result {
+            // Invalid scores would otherwise win or poison the ordering of
+            // solutions.
+            Ok(result) if !result.ranking_score().is_finite() || result.ranking_score() < 0. => {
+                tracing::warn!(
+                    driver = index,
+                    score = result.ranking_score(),
+                    "driver invalid score"
+                );
+                None
+            }
+            Ok(result) => Some((index, result)),
+            Err(err) => {
+                tracing::warn!(?err, "driver solve error");
+                None
+            }
+        })
+        .collect()
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+#[metric(subsystem = "run_loop")]
+struct Metrics {
+    /// Number of solutions received for the most recent auction.
+    solutions: prometheus::IntGauge,
+
+    /// Number of drivers that were asked to solve the most recent auction.
+    drivers: prometheus::IntGauge,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Metrics::instance(global_metrics::get_metric_storage_registry()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::driver_api::tests::mock_driver,
+        ethcontract::transport::DynTransport,
+        model::order::{OrderData, OrderMetadata},
+        serde_json::json,
+        shared::{current_block::BlockInfo, ethrpc::mock::MockTransport},
+    };
+
+    #[tokio::test]
+    async fn transactions_are_fetched_once() {
+        let tag = [0x42];
+        let tx = |byte: u8, input: Vec<u8>| Transaction {
+            hash: H256::repeat_byte(byte),
+            input: input.into(),
+            ..Default::default()
+        };
+        let transactions = [tx(1, vec![1]), tx(2, vec![2])];
+        let settlement = tx(3, vec![3, 0x42]);
+
+        let transport = MockTransport::new();
+        for tx in transactions.iter().chain([&settlement]) {
+            let response = serde_json::to_value(tx).unwrap();
+            transport
+                .mock()
+                .expect_execute()
+                .withf({
+                    let hash = json!(tx.hash);
+                    move |method, params| {
+                        method == "eth_getTransactionByHash" && params == &[hash.clone()]
+                    }
+                })
+                .times(1)
+                .returning(move |_, _| Ok(response.clone()));
+        }
+        let web3 = Web3::new(DynTransport::new(transport));
+
+        let mut cache = TransactionCache::default();
+        let hashes = transactions.iter().map(|tx| tx.hash).collect::<Vec<_>>();
+        for _ in 0..3 {
+            assert_eq!(cache.find(&web3, &hashes, &tag).await.unwrap(), None);
+        }
+        let hashes = [hashes, vec![settlement.hash]].concat();
+        for _ in 0..2 {
+            assert_eq!(
+                cache.find(&web3, &hashes, &tag).await.unwrap(),
+                Some(settlement.clone())
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_polls_for_settlement_transaction() {
+        let db = Postgres::new("postgresql://").await.unwrap();
+        database::clear_DANGER(&db.0).await.unwrap();
+        let settlement = Transaction {
+            hash: H256::repeat_byte(1),
+            input: vec![1, 0x42].into(),
+            ..Default::default()
+        };
+        let mut ex = db.0.begin().await.unwrap();
+        database::events::append(
+            &mut ex,
+            &[(
+                database::events::EventIndex {
+                    block_number: 11,
+                    log_index: 0,
+                },
+                database::events::Event::Settlement(database::events::Settlement {
+                    solver: Default::default(),
+                    transaction_hash: database::byte_array::ByteArray(settlement.hash.0),
+                }),
+            )],
+        )
+        .await
+        .unwrap();
+        ex.commit().await.unwrap();
+
+        let transport = MockTransport::new();
+        let response = serde_json::to_value(&settlement).unwrap();
+        transport
+            .mock()
+            .expect_execute()
+            .returning(move |_, _| Ok(response.clone()));
+        let web3 = Web3::new(DynTransport::new(transport));
+        let (sender, current_block) = tokio::sync::watch::channel(BlockInfo {
+            number: 10,
+            ..Default::default()
+        });
+        let (db, web3, current_block) = (&db, &web3, &current_block);
+        let poll = move |tag: &'static [u8]| {
+            poll_settlement_transaction(
+                |blocks| db.recent_settlement_tx_hashes(blocks),
+                web3,
+                current_block,
+                5..13,
+                Duration::from_millis(10),
+                tag,
+            )
+        };
+
+        assert_eq!(poll(&[0x42]).await.unwrap(), Some(settlement.clone()));
+
+        // Without a matching transaction, polling continues until the current
+        // block is past the range.
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            sender
+                .send(BlockInfo {
+                    number: 13,
+                    ..Default::default()
+                })
+                .unwrap();
+        });
+        assert_eq!(poll(&[0x43]).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn polling_stops_at_max_wait_time() {
+        // 36s at 12s per block allows the settlement to be mined in one of the
+        // next 3 blocks.
+        let blocks = settlement_blocks(100, Duration::from_secs(36), Duration::from_secs(12));
+        assert_eq!(blocks, 36..104);
+
+        let web3 = Web3::new(DynTransport::new(MockTransport::new()));
+        let (sender, current_block) = tokio::sync::watch::channel(BlockInfo {
+            number: 100,
+            ..Default::default()
+        });
+        let polled = std::sync::Mutex::new(Vec::new());
+        let recent_hashes = |_: Range<u64>| {
+            // Every poll happens one block after the previous one.
+            let number = current_block.borrow().number;
+            polled.lock().unwrap().push(number);
+            sender
+                .send(BlockInfo {
+                    number: number + 1,
+                    ..Default::default()
+                })
+                .unwrap();
+            futures::future::ready(Ok(Vec::new()))
+        };
+
+        let tx = poll_settlement_transaction(
+            recent_hashes,
+            &web3,
+            &current_block,
+            blocks,
+            Duration::ZERO,
+            &[0x42],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(tx, None);
+        assert_eq!(polled.into_inner().unwrap(), [100, 101, 102, 103]);
+    }
+
+    #[tokio::test]
+    async fn executes_without_reveal_route() {
+        let (url, requests) =
+            mock_driver(vec![Some((404, String::new())), Some((200, String::new()))]).await;
+        let driver = Driver::with_default_limits(url)
+            .with_execute_endpoint(crate::driver_api::ExecuteEndpoint::Settle);
+
+        let revealed = reveal_and_execute(&driver, "1", &Default::default())
+            .await
+            .unwrap();
+
+        assert!(revealed.is_none());
+        let paths = requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|request| request.path.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["/reveal/1", "/settle/1"]);
+    }
+
+    #[tokio::test]
+    async fn dry_run_does_not_execute() {
+        async fn execute(driver: &Driver, solution: solve::Response) -> Result<()> {
+            reveal_and_execute(driver, &solution.id, &Default::default())
+                .await
+                .map(|_| ())
+        }
+        let solution = |id: &str, score| solve::Response {
+            id: id.to_owned(),
+            score,
+            net_score: None,
+        };
+        let solutions = || vec![(0, solution("1", 1.)), (0, solution("2", 2.))];
+        // The mock driver answers any request, so executing a solution in
+        // dry-run mode shows up as a received request.
+        let (url, requests) =
+            mock_driver(vec![Some((200, String::new())), Some((200, String::new()))]).await;
+        let drivers = [Driver::with_default_limits(url)
+            .with_execute_endpoint(crate::driver_api::ExecuteEndpoint::Settle)];
+        let paths = || {
+            requests
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|request| request.path.clone())
+                .collect::<Vec<_>>()
+        };
+
+        execute_winner(&drivers, solutions(), true, 0., execute).await;
+        assert!(paths().is_empty(), "dry run executed a solution");
+
+        execute_winner(&drivers, solutions(), false, 0., execute).await;
+        assert_eq!(paths(), ["/reveal/2", "/settle/2"]);
+    }
+
+    #[test]
+    fn solutions_are_ranked_by_net_score() {
+        let solution = |id: &str, net_score| solve::Response {
+            id: id.to_owned(),
+            score: 10.,
+            net_score,
+        };
+
+        // Equal gross scores, but the first solution uses less gas.
+        let solutions = vec![(0, solution("1", Some(8.))), (1, solution("2", Some(5.)))];
+        let (index, winner) = solution_to_execute(solutions, false, 0.).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(winner.id, "1");
+
+        // Solutions without a net score are ranked by their score.
+        let solutions = vec![(0, solution("1", Some(8.))), (1, solution("2", None))];
+        let (index, _) = solution_to_execute(solutions, false, 0.).unwrap();
+        assert_eq!(index, 1);
+    }
+
+    #[test]
+    fn solutions_below_min_score_are_not_executed() {
+        let solutions = || {
+            vec![(
+                0,
+                solve::Response {
+                    id: "1".to_owned(),
+                    score: 1.,
+                    net_score: None,
+                },
+            )]
+        };
+
+        assert!(solution_to_execute(solutions(), false, 2.).is_none());
+        assert!(solution_to_execute(solutions(), false, 1.).is_some());
+        assert!(solution_to_execute(solutions(), false, 0.).is_some());
+    }
+
+    #[test]
+    fn solve_gate_skip_reasons() {
+        let order = |class| {
+            solve_order(&Order {
+                metadata: OrderMetadata {
+                    class,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        };
+        let liquidity = || order(OrderClass::Liquidity);
+        let market = || order(OrderClass::Market);
+        let limit = || order(OrderClass::Limit(Default::default()));
+
+        let all_liquidity = [liquidity(), liquidity()];
+        assert!(SolveGate::NonLiquidity
+            .skip_reason(&all_liquidity)
+            .is_some());
+        assert!(SolveGate::Limit.skip_reason(&all_liquidity).is_some());
+
+        let mixed = [liquidity(), market()];
+        assert!(SolveGate::NonLiquidity.skip_reason(&mixed).is_none());
+        assert!(SolveGate::Limit.skip_reason(&mixed).is_some());
+
+        let mixed = [liquidity(), market(), limit()];
+        assert!(SolveGate::NonLiquidity.skip_reason(&mixed).is_none());
+        assert!(SolveGate::Limit.skip_reason(&mixed).is_none());
+    }
+
+    #[test]
+    fn settlement_wait_time_in_blocks() {
+        let block_interval = Duration::from_secs(12);
+        assert_eq!(blocks_within(Duration::from_secs(60), block_interval), 5);
+        assert_eq!(blocks_within(Duration::from_secs(61), block_interval), 6);
+        assert_eq!(blocks_within(Duration::from_secs(300), block_interval), 25);
+        assert_eq!(blocks_within(Duration::ZERO, block_interval), 0);
+    }
+
+    #[test]
+    fn expired_orders_are_not_sent_to_drivers() {
+        let now = 1_000;
+        let order = |uid: u8, valid_to: u32| Order {
+            data: OrderData {
+                valid_to,
+                ..Default::default()
+            },
+            metadata: OrderMetadata {
+                uid: OrderUid([uid; 56]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let auction = Auction {
+            orders: vec![
+                order(1, now - 1),
+                order(2, now),
+                order(3, now + 1),
+                order(4, u32::MAX),
+            ],
+            ..Default::default()
+        };
+
+        let request = solve_request(0, &auction, now, Utc::now());
+
+        assert_eq!(
+            request
+                .orders
+                .iter()
+                .map(|order| order.uid)
+                .collect::<Vec<_>>(),
+            [OrderUid([3; 56]), OrderUid([4; 56])],
+        );
+    }
+
+    #[test]
+    fn solve_request_orders_are_logged() {
+        let order = |uid: u8, class| Order {
+            data: OrderData {
+                valid_to: u32::MAX,
+                ..Default::default()
+            },
+            metadata: OrderMetadata {
+                uid: OrderUid([uid; 56]),
+                class,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let auction = Auction {
+            orders: vec![
+                order(1, OrderClass::Market),
+                order(2, OrderClass::Limit(Default::default())),
+            ],
+            ..Default::default()
+        };
+
+        let request = solve_request(7, &auction, 0, Utc::now());
+        let logged = format!("{:?}", request_orders(&request));
+
+        assert_eq!(
+            logged,
+            format!(
+                "[({}, Market), ({}, Limit)]",
+                OrderUid([1; 56]),
+                OrderUid([2; 56])
+            )
+        );
+    }
+
+    #[test]
+    fn block_based_solve_deadline() {
+        let block_interval = Duration::from_secs(12);
+        let block_time = 1_000;
+        let now = DateTime::from_utc(NaiveDateTime::from_timestamp(1_005, 0), Utc);
+        let deadline = |blocks| solve_deadline(blocks, block_interval, block_time, now).timestamp();
+
+        // Pinned deadlines are relative to the auction's block, not the
+        // current time.
+        assert_eq!(deadline(NonZeroU64::new(1)), 1_012);
+        assert_eq!(deadline(NonZeroU64::new(3)), 1_036);
+        assert_eq!(deadline(None), 1_005 + SOLVE_TIME_LIMIT.as_secs() as i64);
+    }
+
+    #[test]
+    fn limit_orders_use_surplus_fee_as_solver_fee() {
+        let order = |class| Order {
+            data: OrderData {
+                fee_amount: 1.into(),
+                ..Default::default()
+            },
+            metadata: OrderMetadata {
+                full_fee_amount: 2.into(),
+                class,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let limit = solve_order(&order(OrderClass::Limit(LimitOrderClass {
+            surplus_fee: Some(3.into()),
+            ..Default::default()
+        })));
+        assert_eq!(limit.solver_fee, 3.into());
+        assert_eq!(limit.surplus_fee, Some(3.into()));
+        assert_eq!(limit.user_fee, 1.into());
+
+        let market = solve_order(&order(OrderClass::Market));
+        assert_eq!(market.solver_fee, 2.into());
+        assert_eq!(market.surplus_fee, None);
+    }
+
+    #[tokio::test]
+    async fn solutions_count_matches_successful_drivers() {
+        let response = Some((200, r#"{"id":"1","score":"1.0"}"#.to_owned()));
+        let (first, _) = mock_driver(vec![response.clone()]).await;
+        let (failing, _) = mock_driver(vec![Some((500, String::new()))]).await;
+        let (second, _) = mock_driver(vec![response]).await;
+        let drivers = [first, failing, second].map(Driver::with_default_limits);
+
+        let solutions = solve_with_drivers(&drivers, &Default::default(), SOLVE_TIME_LIMIT).await;
+
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(
+            solutions
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            [0, 2]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_scores_are_discarded() {
+        let response = |score: &str| Some((200, format!(r#"{{"id":"1","score":"{score}"}}"#)));
+        let (nan, _) = mock_driver(vec![response("NaN")]).await;
+        let (negative, _) = mock_driver(vec![response("-1.0")]).await;
+        let (valid, _) = mock_driver(vec![response("0.0")]).await;
+        let drivers = [nan, negative, valid].map(Driver::with_default_limits);
+
+        let solutions = solve_with_drivers(&drivers, &Default::default(), SOLVE_TIME_LIMIT).await;
+
+        assert_eq!(
+            solutions
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            [2]
+        );
+    }
+}
@@ -24,6 +24,18 @@ LIMIT 1
     sqlx::query_as(QUERY).fetch_optional(ex).await
 }
 
+/// Returns the number of orders in the most recent auction without loading
+/// the whole auction.
+pub async fn most_recent_order_count(ex: &mut PgConnection) -> Result<Option<i64>, sqlx::Error> {
+    const QUERY: &str = r#"
+SELECT jsonb_array_length(json->'orders')
+FROM auctions
+ORDER BY id DESC
+LIMIT 1
+    ;"#;
+    sqlx::query_scalar(QUERY).fetch_optional(ex).await
+}
+
 pub async fn delete_all_auctions(ex: &mut PgConnection) -> Result<(), sqlx::Error> {
     const QUERY: &str = "TRUNCATE auctions;";
     sqlx::query(QUERY).execute(ex).await.map(|_| ())
@@ -56,6 +68,8 @@ mod tests {
         delete_all_auctions(&mut db).await.unwrap();
         let result = load_most_recent(&mut db).await.unwrap();
         assert!(result.is_none());
+        let count = most_recent_order_count(&mut db).await.unwrap();
+        assert!(count.is_none());
 
         // id still increases after deletion
         let value = JsonValue::Number(3.into());
@@ -65,4 +79,22 @@ mod tests {
         assert_eq!(value, value_);
         assert_eq!(id_, id);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_most_recent_order_count() {
+        let mut db = PgConnection::connect("postgresql://").await.unwrap();
+        let mut db = db.begin().await.unwrap();
+        crate::clear_DANGER_(&mut db).await.unwrap();
+
+        let auction = |orders: usize| {
+            [("orders", JsonValue::Array(vec![JsonValue::Null; orders]))]
+                .into_iter()
+                .collect::<JsonValue>()
+        };
+        save(&mut db, &auction(2)).await.unwrap();
+        save(&mut db, &auction(3)).await.unwrap();
+        let count = most_recent_order_count(&mut db).await.unwrap();
+        assert_eq!(count, Some(3));
+    }
 }
@@ -120,14 +120,20 @@ pub async fn refundable_orders(
 SELECT eo.uid, eo.valid_to from orders o
 INNER JOIN ethflow_orders eo on eo.uid = o.uid 
 INNER JOIN order_quotes oq on o.uid = oq.order_uid
-LEFT JOIN trades t on o.uid = t.order_uid
 LEFT JOIN onchain_order_invalidations o_inv on o.uid = o_inv.uid
 LEFT JOIN ethflow_refunds o_ref on o.uid = o_ref.order_uid
 WHERE 
 o_ref.tx_hash is null
 AND o_inv.uid is null
-AND o.partially_fillable = false
-AND t.order_uid is null
+AND (
+    -- fill-or-kill orders are only refundable if they were never traded
+    NOT EXISTS (SELECT 1 FROM trades t WHERE t.order_uid = o.uid)
+    -- partially fillable orders are refundable as long as they are not completely filled
+    OR (
+        o.partially_fillable
+        AND (SELECT COALESCE(SUM(t.sell_amount - t.fee_amount), 0) FROM trades t WHERE t.order_uid = o.uid) < o.sell_amount
+    )
+)
 AND eo.valid_to < $1
 AND o.sell_amount = oq.sell_amount
 AND (1.0 - o.buy_amount / oq.buy_amount) >= $3
@@ -359,7 +365,37 @@ mod tests {
         let mut order_parts = create_standard_ethflow_order_parts(order_uid_4);
         order_parts.order.partially_fillable = true;
         insert_order_parts_in_db(&mut db, &order_parts).await;
-        // no refundable orders as order is partially fillable
+        // partially fillable orders are refundable until they are completely filled
+        let orders = refundable_orders(&mut db, 5, 1, 0.001).await.unwrap();
+        assert_eq!(orders, vec![order_parts.eth_order.clone()]);
+        let partial_fill = Trade {
+            order_uid: order_uid_4,
+            sell_amount_including_fee: BigDecimal::from(50u32),
+            ..Default::default()
+        };
+        insert_trade(
+            &mut db,
+            &EventIndex {
+                block_number: 2,
+                log_index: 0,
+            },
+            &partial_fill,
+        )
+        .await
+        .unwrap();
+        let orders = refundable_orders(&mut db, 5, 1, 0.001).await.unwrap();
+        assert_eq!(orders, vec![order_parts.eth_order.clone()]);
+        insert_trade(
+            &mut db,
+            &EventIndex {
+                block_number: 2,
+                log_index: 1,
+            },
+            &partial_fill,
+        )
+        .await
+        .unwrap();
+        // no refundable orders as the order is completely filled
         let orders = refundable_orders(&mut db, 5, 1, 0.001).await.unwrap();
         assert_eq!(orders, Vec::new());
 
@@ -567,6 +567,30 @@ pub fn user_orders<'a>(
         .fetch(ex)
 }
 
+/// All orders with the specified app data ordered by creation date descending
+/// (newest orders first).
+pub fn app_data_orders<'a>(
+    ex: &'a mut PgConnection,
+    app_data: &'a AppId,
+    offset: i64,
+    limit: Option<i64>,
+) -> BoxStream<'a, Result<FullOrder, sqlx::Error>> {
+    #[rustfmt::skip]
+    const QUERY: &str = const_format::concatcp!(
+"SELECT ", ORDERS_SELECT,
+" FROM ", ORDERS_FROM,
+" WHERE o.app_data = $1 ",
+" ORDER BY creation_timestamp DESC ",
+" LIMIT $2 ",
+" OFFSET $3 ",
+    );
+    sqlx::query_as(QUERY)
+        .bind(app_data)
+        .bind(limit)
+        .bind(offset)
+        .fetch(ex)
+}
+
 /// The base solvable orders query used in specialized queries. Parametrized by valid_to.
 ///
 /// Does not take limit order surplus fee into account.
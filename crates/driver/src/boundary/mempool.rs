@@ -82,6 +82,7 @@ impl Mempool {
                 }),
                 &boundary::web3(&eth),
                 &[shared::gas_price_estimation::GasEstimatorType::Native],
+                Default::default(),
                 None,
             )
             .await?,
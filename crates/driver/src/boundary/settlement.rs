@@ -166,7 +166,7 @@ impl Settlement {
         eth: &Ethereum,
         auction: &competition::Auction,
         gas: eth::Gas,
-    ) -> Result<competition::solution::Score> {
+    ) -> Result<competition::solution::Scores> {
         let prices = ExternalPrices::try_from_auction_prices(
             eth.contracts().weth().address(),
             auction
@@ -186,7 +186,10 @@ impl Settlement {
             &gas_price,
             &gas.into(),
         );
-        Ok(inputs.objective_value().into())
+        Ok(competition::solution::Scores {
+            score: inputs.objective_value().into(),
+            net: (&inputs.surplus_given + &inputs.solver_fees - &inputs.settlement_cost).into(),
+        })
     }
 }
 
@@ -6,14 +6,19 @@ use {
         },
         infra::time,
     },
-    std::{num::ParseIntError, str::FromStr},
+    rand::Rng,
+    std::{
+        collections::{HashMap, HashSet},
+        num::ParseIntError,
+        str::FromStr,
+    },
     thiserror::Error,
 };
 
 /// An auction is a set of orders that can be solved. The solvers calculate
 /// [`super::solution::Solution`]s by picking subsets of these orders and
 /// solving them.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Auction {
     // TODO Make this non-optional and simplify things
     /// [`None`] if the auction is used for quoting, [`Some`] if the auction is
@@ -25,7 +30,122 @@ pub struct Auction {
     pub deadline: Deadline,
 }
 
-#[derive(Debug)]
+impl Auction {
+    /// Verifies that every token traded by the orders in this auction has a
+    /// price. Without prices, solutions for these orders can't be scored.
+    pub fn validate_prices(&self) -> Result<(), MissingPrice> {
+        let priced = self
+            .tokens
+            .iter()
+            .filter(|token| token.price.is_some())
+            .map(|token| token.address)
+            .collect::<HashSet<_>>();
+        match self
+            .orders
+            .iter()
+            .flat_map(|order| [order.sell.token, order.buy.token])
+            .find(|token| !priced.contains(token))
+        {
+            Some(token) => Err(MissingPrice(token)),
+            None => Ok(()),
+        }
+    }
+
+    /// Verifies that the internalized interactions of the solution don't take
+    /// more of any token than is available in the settlement contract. Since
+    /// internalized interactions are not executed, the settlement contract pays
+    /// out their outputs from its own buffers.
+    pub fn check_internalized(
+        &self,
+        solution: &competition::Solution,
+    ) -> Result<(), OverdrawnToken> {
+        let mut outflows = HashMap::<eth::TokenAddress, eth::U256>::new();
+        for interaction in solution.interactions.iter().filter(|i| i.internalize()) {
+            let outputs = match interaction {
+                solution::Interaction::Custom(custom) => custom.outputs.clone(),
+                solution::Interaction::Liquidity(liquidity) => vec![liquidity.output],
+            };
+            for output in outputs {
+                let outflow = outflows.entry(output.token).or_default();
+                *outflow = outflow.saturating_add(output.amount);
+            }
+        }
+        let available = |token| {
+            self.tokens
+                .iter()
+                .find(|t| t.address == token)
+                .map(|t| t.available_balance)
+                .unwrap_or_default()
+        };
+        match outflows
+            .into_iter()
+            .find(|(token, outflow)| *outflow > available(*token))
+        {
+            Some((token, _)) => Err(OverdrawnToken(token)),
+            None => Ok(()),
+        }
+    }
+
+    /// Splits the auction into sub-auctions whose orders trade disjoint sets of
+    /// tokens, so that they can be solved in parallel and their solutions
+    /// merged. An auction whose orders are all connected is returned whole.
+    ///
+    /// Every sub-auction keeps all of the auction's tokens, since solvers may
+    /// route through tokens that none of its orders trade. For the same reason,
+    /// solutions to different sub-auctions can use the same liquidity, so
+    /// merged solutions have to be simulated together rather than assuming
+    /// that the individual simulations still hold.
+    ///
+    /// The competition doesn't partition auctions yet and solves them whole.
+    pub fn partition(&self) -> Vec<Auction> {
+        // Union-find over the orders, joining orders that trade a common token.
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let mut parents = (0..self.orders.len()).collect::<Vec<_>>();
+        let mut traded_by = HashMap::new();
+        for (i, order) in self.orders.iter().enumerate() {
+            for token in [order.sell.token, order.buy.token] {
+                let other = *traded_by.entry(token).or_insert(i);
+                let (a, b) = (root(&mut parents, other), root(&mut parents, i));
+                parents[a] = b;
+            }
+        }
+
+        // Group the orders by their root, keeping the order of the auction.
+        let mut groups = Vec::<Vec<competition::Order>>::new();
+        let mut group_of_root = HashMap::new();
+        for (i, order) in self.orders.iter().enumerate() {
+            let group = *group_of_root
+                .entry(root(&mut parents, i))
+                .or_insert_with(|| {
+                    groups.push(Default::default());
+                    groups.len() - 1
+                });
+            groups[group].push(order.clone());
+        }
+        if groups.len() <= 1 {
+            return vec![self.clone()];
+        }
+
+        groups
+            .into_iter()
+            .map(|orders| Auction {
+                id: self.id,
+                tokens: self.tokens.clone(),
+                orders,
+                gas_price: self.gas_price,
+                deadline: self.deadline,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Token {
     pub decimals: Option<u8>,
     pub symbol: Option<String>,
@@ -37,11 +157,51 @@ pub struct Token {
     pub trusted: bool,
 }
 
+impl Token {
+    /// Is this the native token of the network or its wrapped version?
+    pub fn is_native(&self, weth: eth::TokenAddress) -> bool {
+        self.address == weth || self.address == competition::order::BUY_ETH_ADDRESS
+    }
+
+    /// Converts an amount of this token into its value in the native token,
+    /// using the token price. Returns [`None`] if the token has no price or
+    /// the conversion overflows.
+    pub fn to_native(&self, amount: eth::U256) -> Option<eth::Ether> {
+        self.price?.in_eth(amount)
+    }
+}
+
 /// The price of a token in wei. This represents how much wei is needed to buy
 /// 10**18 of another token.
 #[derive(Debug, Clone, Copy)]
 pub struct Price(pub eth::Ether);
 
+impl Price {
+    /// The reciprocal price, i.e. how many units of the token are needed to
+    /// buy 10**18 wei. Returns [`None`] for a zero price.
+    pub fn invert(&self) -> Option<Price> {
+        let price: eth::U256 = (*self).into();
+        eth::U256::exp10(36).checked_div(price).map(Into::into)
+    }
+
+    /// The value in wei of the specified amount of the token. Returns
+    /// [`None`] if the computation overflows.
+    pub fn in_eth(&self, amount: eth::U256) -> Option<eth::Ether> {
+        self.0
+            .checked_mul(amount)
+            .map(|value| (value.0 / eth::U256::exp10(18)).into())
+    }
+
+    /// The amount of the token that is worth the specified value in wei.
+    /// Returns [`None`] for a zero price or if the computation overflows.
+    pub fn token_amount(&self, value: eth::Ether) -> Option<eth::U256> {
+        value
+            .0
+            .checked_mul(eth::U256::exp10(18))?
+            .checked_div(self.0 .0)
+    }
+}
+
 impl From<Price> for eth::U256 {
     fn from(value: Price) -> Self {
         value.0.into()
@@ -62,7 +222,30 @@ pub struct Deadline(chrono::DateTime<chrono::Utc>);
 impl Deadline {
     /// Computes the timeout for solving an auction.
     pub fn timeout(self, now: time::Now) -> Result<solution::SolverTimeout, DeadlineExceeded> {
-        solution::SolverTimeout::new(self.into(), Self::time_buffer(), now).ok_or(DeadlineExceeded)
+        self.timeout_with_jitter(now, chrono::Duration::zero())
+    }
+
+    /// Computes the timeout for solving an auction, shortened by a random
+    /// jitter of up to `max_jitter`. This staggers solvers which share
+    /// infrastructure, so that they don't all stop solving at the same instant.
+    pub fn timeout_with_jitter(
+        self,
+        now: time::Now,
+        max_jitter: chrono::Duration,
+    ) -> Result<solution::SolverTimeout, DeadlineExceeded> {
+        let jitter = match max_jitter.num_milliseconds() {
+            max if max > 0 => chrono::Duration::milliseconds(rand::thread_rng().gen_range(0..=max)),
+            _ => chrono::Duration::zero(),
+        };
+        solution::SolverTimeout::new(self.into(), Self::time_buffer() + jitter, now)
+            .ok_or(DeadlineExceeded)
+    }
+
+    /// The time remaining until the deadline, or [`None`] if the deadline has
+    /// already been reached.
+    pub fn remaining(self, now: time::Now) -> Option<chrono::Duration> {
+        let remaining = self.0 - now.now();
+        (remaining > chrono::Duration::zero()).then_some(remaining)
     }
 
     pub fn time_buffer() -> chrono::Duration {
@@ -98,13 +281,375 @@ impl std::fmt::Display for Id {
 }
 
 impl FromStr for Id {
-    type Err = ParseIntError;
+    type Err = InvalidId;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        FromStr::from_str(s).map(Self)
+        FromStr::from_str(s).map(Self).map_err(|source| InvalidId {
+            input: s.to_owned(),
+            source,
+        })
     }
 }
 
+#[derive(Debug, Error)]
+#[error(
+    "invalid auction ID {input:?}, expected an integer between 0 and {}",
+    u64::MAX
+)]
+pub struct InvalidId {
+    pub input: String,
+    pub source: ParseIntError,
+}
+
 #[derive(Debug, Error)]
 #[error("the solution deadline has been exceeded")]
 pub struct DeadlineExceeded;
+
+#[derive(Debug, Error)]
+#[error("the auction is missing a price for token {0:?}")]
+pub struct MissingPrice(pub eth::TokenAddress);
+
+#[derive(Debug, Error)]
+#[error("internalized interactions take more of token {0:?} than is available")]
+pub struct OverdrawnToken(pub eth::TokenAddress);
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{infra, util},
+    };
+
+    fn token(address: u8, price: Option<u64>) -> Token {
+        Token {
+            decimals: None,
+            symbol: None,
+            address: eth::H160::repeat_byte(address).into(),
+            price: price.map(|price| eth::U256::from(price).into()),
+            available_balance: Default::default(),
+            trusted: false,
+        }
+    }
+
+    fn order(sell: u8, buy: u8) -> competition::Order {
+        competition::Order {
+            uid: Default::default(),
+            receiver: None,
+            valid_to: util::Timestamp::MAX,
+            sell: eth::Asset {
+                amount: 1.into(),
+                token: eth::H160::repeat_byte(sell).into(),
+            },
+            buy: eth::Asset {
+                amount: 1.into(),
+                token: eth::H160::repeat_byte(buy).into(),
+            },
+            side: competition::order::Side::Sell,
+            fee: Default::default(),
+            kind: competition::order::Kind::Market,
+            app_data: Default::default(),
+            partial: competition::order::Partial::No,
+            interactions: Default::default(),
+            sell_token_balance: competition::order::SellTokenBalance::Erc20,
+            buy_token_balance: competition::order::BuyTokenBalance::Erc20,
+            signature: competition::order::Signature {
+                scheme: competition::order::signature::Scheme::Eip712,
+                data: Default::default(),
+                signer: Default::default(),
+            },
+            reward: Default::default(),
+        }
+    }
+
+    fn auction(tokens: Vec<Token>, orders: Vec<competition::Order>) -> Auction {
+        Auction {
+            id: Some(1.into()),
+            tokens,
+            orders,
+            gas_price: eth::U256::one().into(),
+            deadline: Default::default(),
+        }
+    }
+
+    #[test]
+    fn validates_complete_prices() {
+        let auction = auction(
+            vec![token(1, Some(1)), token(2, Some(2)), token(3, None)],
+            vec![order(1, 2), order(2, 1)],
+        );
+        assert!(auction.validate_prices().is_ok());
+    }
+
+    #[test]
+    fn detects_missing_price() {
+        let missing_buy_price = auction(vec![token(1, Some(1)), token(2, None)], vec![order(1, 2)]);
+        let MissingPrice(missing) = missing_buy_price.validate_prices().unwrap_err();
+        assert_eq!(missing, eth::H160::repeat_byte(2).into());
+
+        let missing_sell_token = auction(vec![token(1, Some(1))], vec![order(3, 1)]);
+        let MissingPrice(missing) = missing_sell_token.validate_prices().unwrap_err();
+        assert_eq!(missing, eth::H160::repeat_byte(3).into());
+    }
+
+    fn solution(interactions: Vec<solution::Interaction>) -> competition::Solution {
+        competition::Solution {
+            id: 1.into(),
+            trades: Default::default(),
+            prices: Default::default(),
+            interactions,
+            solver: infra::Solver::new(
+                infra::solver::Config {
+                    endpoint: "http://localhost".parse().unwrap(),
+                    name: "solver".to_owned().into(),
+                    slippage: infra::solver::Slippage {
+                        relative: Default::default(),
+                        absolute: None,
+                    },
+                    private_key: eth::PrivateKey::from_raw([1; 32]).unwrap(),
+                    max_deadline_jitter: chrono::Duration::zero(),
+                    quote_time_buffer: chrono::Duration::zero(),
+                    max_quote_gas: None,
+                    max_quote_interactions: None,
+                },
+                time::Now::Real,
+            ),
+        }
+    }
+
+    fn interaction(token: u8, amount: u64, internalize: bool) -> solution::Interaction {
+        solution::Interaction::Custom(solution::interaction::Custom {
+            target: eth::H160::zero().into(),
+            value: eth::U256::zero().into(),
+            call_data: Default::default(),
+            allowances: Default::default(),
+            // The inputs are received by the settlement contract, so they
+            // don't count towards the buffer usage.
+            inputs: vec![eth::Asset {
+                token: eth::H160::repeat_byte(0xff).into(),
+                amount: eth::U256::max_value(),
+            }],
+            outputs: vec![eth::Asset {
+                token: eth::H160::repeat_byte(token).into(),
+                amount: amount.into(),
+            }],
+            internalize,
+            gas: None,
+        })
+    }
+
+    fn auction_with_balance(balance: u64) -> Auction {
+        let mut token = token(1, Some(1));
+        token.available_balance = balance.into();
+        auction(vec![token], vec![])
+    }
+
+    #[test]
+    fn internalized_within_available_balance() {
+        let within_budget = solution(vec![
+            interaction(1, 60, true),
+            interaction(1, 40, true),
+            // Interactions that aren't internalized don't use the balance.
+            interaction(1, 1_000, false),
+        ]);
+        assert!(auction_with_balance(100)
+            .check_internalized(&within_budget)
+            .is_ok());
+    }
+
+    #[test]
+    fn internalized_overdraws_token() {
+        let overdrawn = solution(vec![interaction(1, 60, true), interaction(1, 41, true)]);
+        let OverdrawnToken(token) = auction_with_balance(100)
+            .check_internalized(&overdrawn)
+            .unwrap_err();
+        assert_eq!(token, eth::H160::repeat_byte(1).into());
+
+        // Tokens that are not part of the auction have no available balance.
+        let unknown = solution(vec![interaction(2, 1, true)]);
+        let OverdrawnToken(token) = auction_with_balance(100)
+            .check_internalized(&unknown)
+            .unwrap_err();
+        assert_eq!(token, eth::H160::repeat_byte(2).into());
+    }
+
+    #[test]
+    fn partitions_disjoint_orders() {
+        let auction = auction(
+            (1..=4).map(|address| token(address, Some(1))).collect(),
+            vec![order(1, 2), order(3, 4), order(2, 1)],
+        );
+
+        let partitions = auction.partition();
+
+        assert_eq!(partitions.len(), 2);
+        let tokens = |auction: &Auction| {
+            auction
+                .tokens
+                .iter()
+                .map(|token| token.address)
+                .collect::<Vec<_>>()
+        };
+        let address = |byte| eth::TokenAddress::from(eth::H160::repeat_byte(byte));
+        let all = (1..=4).map(address).collect::<Vec<_>>();
+        assert_eq!(partitions[0].orders.len(), 2);
+        assert_eq!(tokens(&partitions[0]), all);
+        assert_eq!(partitions[1].orders.len(), 1);
+        assert_eq!(tokens(&partitions[1]), all);
+    }
+
+    #[test]
+    fn connected_orders_are_not_partitioned() {
+        let auction = auction(
+            (1..=4).map(|address| token(address, Some(1))).collect(),
+            vec![order(1, 2), order(3, 4), order(2, 3)],
+        );
+
+        let partitions = auction.partition();
+
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].orders.len(), 3);
+        assert_eq!(partitions[0].tokens.len(), 4);
+    }
+
+    #[test]
+    fn timeout_jitter_is_bounded() {
+        let now = time::Now::Fake(chrono::Utc::now());
+        let deadline = Deadline::from(now.now() + chrono::Duration::seconds(10));
+        let max_jitter = chrono::Duration::seconds(2);
+        let latest = chrono::DateTime::<chrono::Utc>::from(deadline) - Deadline::time_buffer();
+        let earliest = latest - max_jitter;
+        for _ in 0..100 {
+            let timeout = deadline.timeout_with_jitter(now, max_jitter).unwrap();
+            let solver_deadline = timeout.deadline(now);
+            assert!(earliest <= solver_deadline && solver_deadline <= latest);
+        }
+        assert_eq!(deadline.timeout(now).unwrap().deadline(now), latest);
+    }
+
+    #[test]
+    fn remaining_until_future_deadline() {
+        let now = time::Now::Fake(chrono::Utc::now());
+        let deadline = Deadline::from(now.now() + chrono::Duration::milliseconds(1500));
+        assert_eq!(
+            deadline.remaining(now),
+            Some(chrono::Duration::milliseconds(1500))
+        );
+    }
+
+    #[test]
+    fn nothing_remaining_after_past_deadline() {
+        let now = time::Now::Fake(chrono::Utc::now());
+        let deadline = Deadline::from(now.now() - chrono::Duration::seconds(1));
+        assert_eq!(deadline.remaining(now), None);
+        assert_eq!(Deadline::from(now.now()).remaining(now), None);
+    }
+
+    #[test]
+    fn cloned_auction_produces_identical_solve_request() {
+        let original = auction(
+            vec![token(1, Some(1)), token(2, Some(2))],
+            vec![order(1, 2), order(2, 1)],
+        );
+        let cloned = original.clone();
+
+        let now = time::Now::Fake(chrono::Utc::now());
+        let request = |auction: &Auction| {
+            serde_json::to_value(crate::infra::solver::dto::Auction::from_domain(
+                auction,
+                &[],
+                Deadline(now.now() + chrono::Duration::seconds(10))
+                    .timeout(now)
+                    .unwrap(),
+                now,
+            ))
+            .unwrap()
+        };
+        assert_eq!(request(&original), request(&cloned));
+    }
+
+    #[test]
+    fn inverts_price() {
+        // 1 token is worth 0.0005 ETH, so 2000 tokens are worth 1 ETH.
+        let price = Price::from(eth::U256::from(500_000_000_000_000u64));
+        let inverted: eth::U256 = price.invert().unwrap().into();
+        assert_eq!(inverted, eth::U256::exp10(18) * 2000);
+        let round_trip: eth::U256 = price.invert().unwrap().invert().unwrap().into();
+        assert_eq!(round_trip, price.into());
+
+        // Prices that don't divide evenly round trip up to rounding.
+        let price = Price::from(eth::U256::from(3_000_000_000_000_000_007u64));
+        let round_trip: eth::U256 = price.invert().unwrap().invert().unwrap().into();
+        let original: eth::U256 = price.into();
+        let difference = original.max(round_trip) - original.min(round_trip);
+        assert!(difference * 1_000_000 <= original);
+    }
+
+    #[test]
+    fn zero_price_has_no_inverse() {
+        assert!(Price::from(eth::U256::zero()).invert().is_none());
+    }
+
+    #[test]
+    fn values_amount_in_eth() {
+        // 1 token is worth 0.5 ETH.
+        let price = Price::from(eth::U256::from(500_000_000_000_000_000u64));
+        assert_eq!(
+            price.in_eth(eth::U256::exp10(19)).unwrap().0,
+            eth::U256::exp10(18) * 5
+        );
+        assert!(price.in_eth(eth::U256::max_value()).is_none());
+    }
+
+    #[test]
+    fn converts_eth_to_token_amount() {
+        // 1 token is worth 0.5 ETH.
+        let price = Price::from(eth::U256::from(500_000_000_000_000_000u64));
+        assert_eq!(
+            price.token_amount(eth::U256::exp10(18).into()).unwrap(),
+            eth::U256::exp10(18) * 2
+        );
+        assert!(Price::from(eth::U256::zero())
+            .token_amount(eth::U256::exp10(18).into())
+            .is_none());
+    }
+
+    #[test]
+    fn parses_id() {
+        assert_eq!(Id::from_str("42").unwrap().0, 42);
+        assert_eq!(Id::from_str(&u64::MAX.to_string()).unwrap().0, u64::MAX);
+    }
+
+    #[test]
+    fn rejects_overflowing_id() {
+        let input = "123456789012345678901234567890";
+        let err = Id::from_str(input).unwrap_err();
+        assert_eq!(err.input, input);
+        assert_eq!(err.source.kind(), &std::num::IntErrorKind::PosOverflow);
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "invalid auction ID \"{input}\", expected an integer between 0 and {}",
+                u64::MAX
+            )
+        );
+    }
+
+    #[test]
+    fn detects_native_token() {
+        let weth = eth::H160::repeat_byte(1).into();
+        assert!(token(1, None).is_native(weth));
+        assert!(token(0xee, None).is_native(weth));
+        assert!(!token(2, None).is_native(weth));
+    }
+
+    #[test]
+    fn converts_amount_to_native() {
+        // 1 token unit is worth 0.5 ETH.
+        let priced = token(1, Some(500_000_000_000_000_000));
+        let native = priced.to_native(eth::U256::exp10(18) * 3).unwrap();
+        assert_eq!(native.0, eth::U256::exp10(18) * 3 / 2);
+
+        let unpriced = token(2, None);
+        assert!(unpriced.to_native(1.into()).is_none());
+    }
+}
@@ -23,7 +23,7 @@ pub mod solution;
 pub use {
     auction::Auction,
     order::Order,
-    solution::{Score, Solution, SolverTimeout},
+    solution::{Score, Scores, Solution, SolverTimeout},
 };
 
 /// An ongoing competition. There is one competition going on per solver at any
@@ -44,14 +44,17 @@ pub struct Competition {
 
 impl Competition {
     /// Solve an auction as part of this competition.
-    pub async fn solve(&self, auction: &Auction) -> Result<(solution::Id, solution::Score), Error> {
+    pub async fn solve(
+        &self,
+        auction: &Auction,
+    ) -> Result<(solution::Id, solution::Scores), Error> {
         tracing::trace!("fetching liquidity");
         let liquidity = self.liquidity.fetch(&Self::liquidity_pairs(auction)).await;
         tracing::trace!("solving");
-        let solution = self
-            .solver
-            .solve(auction, &liquidity, auction.deadline.timeout(self.now)?)
-            .await?;
+        let timeout = auction
+            .deadline
+            .timeout_with_jitter(self.now, self.solver.max_deadline_jitter())?;
+        let solution = self.solver.solve(auction, &liquidity, timeout).await?;
         // TODO(#1009) Keep in mind that the driver needs to make sure that the solution
         // doesn't fail simulation. Currently this is the case, but this needs to stay
         // the same as this code changes.
@@ -60,10 +63,10 @@ impl Competition {
             .simulate(&self.eth, &self.simulator, auction)
             .await?;
         tracing::trace!("scoring");
-        let score = settlement.score(&self.eth, auction).await?;
+        let scores = settlement.score(&self.eth, auction).await?;
         let id = settlement.id();
         *self.settlement.lock().unwrap() = Some((id, settlement));
-        Ok((id, score))
+        Ok((id, scores))
     }
 
     // TODO Rename this to settle()?
@@ -17,6 +17,14 @@ impl Interaction {
         }
     }
 
+    /// The estimated gas needed to execute this interaction onchain, if known.
+    pub fn gas(&self) -> Option<eth::Gas> {
+        match self {
+            Interaction::Custom(custom) => custom.gas,
+            Interaction::Liquidity(liquidity) => Some(liquidity.liquidity.gas),
+        }
+    }
+
     /// Returns the ERC20 allowances required for executing this interaction
     /// onchain.
     pub fn allowances(&self) -> Vec<eth::allowance::Required> {
@@ -56,6 +64,8 @@ pub struct Custom {
     /// Can the interaction be executed using the liquidity of our settlement
     /// contract?
     pub internalize: bool,
+    /// The gas needed to execute this interaction, if estimated by the solver.
+    pub gas: Option<eth::Gas>,
 }
 
 /// An interaction with one of the smart contracts for which we index
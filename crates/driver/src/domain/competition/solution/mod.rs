@@ -168,17 +168,15 @@ impl Solution {
 
 /// The time allocated for the solver to solve an auction.
 #[derive(Debug, Clone, Copy)]
-pub struct SolverTimeout(std::time::Duration);
-
-impl From<std::time::Duration> for SolverTimeout {
-    fn from(value: std::time::Duration) -> Self {
-        Self(value)
-    }
+pub struct SolverTimeout {
+    timeout: std::time::Duration,
+    /// The point in time at which the allocated time runs out.
+    expires_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl From<SolverTimeout> for std::time::Duration {
     fn from(value: SolverTimeout) -> Self {
-        value.0
+        value.timeout
     }
 }
 
@@ -195,19 +193,44 @@ impl SolverTimeout {
         buffer: chrono::Duration,
         now: time::Now,
     ) -> Option<SolverTimeout> {
-        let deadline = deadline - now.now() - buffer;
-        deadline.to_std().map(Self).ok()
+        let expires_at = deadline - buffer;
+        let timeout = (expires_at - now.now()).to_std().ok()?;
+        Some(Self {
+            timeout,
+            expires_at,
+        })
     }
 
     pub fn deadline(self, now: infra::time::Now) -> chrono::DateTime<chrono::Utc> {
-        now.now() + chrono::Duration::from_std(self.0).expect("reasonable solver timeout")
+        now.now() + chrono::Duration::from_std(self.timeout).expect("reasonable solver timeout")
+    }
+
+    /// The time left before the timeout expires, or zero if it has already
+    /// expired.
+    pub fn remaining(&self, now: time::Now) -> chrono::Duration {
+        (self.expires_at - now.now()).max(chrono::Duration::zero())
+    }
+
+    /// Whether the allocated time has run out.
+    pub fn is_expired(&self, now: time::Now) -> bool {
+        now.now() >= self.expires_at
     }
 }
 
-/// The solution score. This is often referred to as the "objective value".
+/// A solution score.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Score(pub num::BigRational);
 
+/// The scores of a solution.
+#[derive(Debug, Clone)]
+pub struct Scores {
+    /// The solution score. This is often referred to as the "objective value".
+    pub score: Score,
+    /// The surplus and fees of the solution minus the gas it uses times the
+    /// auction's effective gas price.
+    pub net: Score,
+}
+
 impl From<Score> for f64 {
     fn from(score: Score) -> Self {
         score.0.to_f64().expect("value can be represented as f64")
@@ -257,3 +280,46 @@ pub enum Error {
     #[error("boundary error: {0:?}")]
     Boundary(#[from] boundary::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_solver_time() {
+        let start = chrono::Utc::now();
+        let timeout = SolverTimeout::new(
+            start + chrono::Duration::seconds(10),
+            chrono::Duration::seconds(2),
+            time::Now::Fake(start),
+        )
+        .unwrap();
+
+        let now = time::Now::Fake(start);
+        assert_eq!(timeout.remaining(now), chrono::Duration::seconds(8));
+        assert!(!timeout.is_expired(now));
+
+        let now = time::Now::Fake(start + chrono::Duration::seconds(5));
+        assert_eq!(timeout.remaining(now), chrono::Duration::seconds(3));
+        assert!(!timeout.is_expired(now));
+    }
+
+    #[test]
+    fn expired_solver_time() {
+        let start = chrono::Utc::now();
+        let timeout = SolverTimeout::new(
+            start + chrono::Duration::seconds(10),
+            chrono::Duration::seconds(2),
+            time::Now::Fake(start),
+        )
+        .unwrap();
+
+        let now = time::Now::Fake(start + chrono::Duration::seconds(8));
+        assert_eq!(timeout.remaining(now), chrono::Duration::zero());
+        assert!(timeout.is_expired(now));
+
+        let now = time::Now::Fake(start + chrono::Duration::seconds(20));
+        assert_eq!(timeout.remaining(now), chrono::Duration::zero());
+        assert!(timeout.is_expired(now));
+    }
+}
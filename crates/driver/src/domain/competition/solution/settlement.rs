@@ -50,12 +50,12 @@ pub struct Simulated {
 }
 
 impl Simulated {
-    /// Calculate the score for this settlement.
+    /// Calculate the scores for this settlement.
     pub async fn score(
         &self,
         eth: &Ethereum,
         auction: &competition::Auction,
-    ) -> Result<super::Score, boundary::Error> {
+    ) -> Result<super::Scores, boundary::Error> {
         self.inner.boundary.score(eth, auction, self.gas).await
     }
 
@@ -92,6 +92,13 @@ impl From<EffectiveGasPrice> for U256 {
     }
 }
 
+impl EffectiveGasPrice {
+    /// Scales the gas price by the specified factor, rounding down.
+    pub fn scaled(self, factor: f64) -> Self {
+        U256::from_f64_lossy(U256::from(self).to_f64_lossy() * factor).into()
+    }
+}
+
 /// An EIP-2930 access list. This type ensures that the addresses and storage
 /// keys are not repeated, and that the ordering is deterministic.
 ///
@@ -225,6 +232,21 @@ impl From<TokenAddress> for ContractAddress {
     }
 }
 
+/// The optional metadata of an ERC20 token.
+///
+/// https://eips.ethereum.org/EIPS/eip-20#methods
+#[derive(Debug, Clone, Default)]
+pub struct TokenMetadata {
+    pub decimals: Option<u8>,
+    pub symbol: Option<String>,
+}
+
+impl TokenMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.decimals.is_none() && self.symbol.is_none()
+    }
+}
+
 /// An asset on the Ethereum blockchain. Represents a particular amount of a
 /// particular token.
 #[derive(Debug, Clone, Copy)]
@@ -237,6 +259,18 @@ pub struct Asset {
 #[derive(Debug, Clone, Copy)]
 pub struct Ether(pub U256);
 
+impl Ether {
+    /// Adds two amounts, returning [`None`] on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    /// Multiplies the amount by a factor, returning [`None`] on overflow.
+    pub fn checked_mul(self, factor: U256) -> Option<Self> {
+        self.0.checked_mul(factor).map(Self)
+    }
+}
+
 impl From<U256> for Ether {
     fn from(inner: U256) -> Self {
         Self(inner)
@@ -318,3 +352,38 @@ impl From<CodeDigest> for [u8; 32] {
         value.0 .0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_gas_price() {
+        let gas_price = EffectiveGasPrice::from(U256::from(10_000_000_000_u64));
+        assert_eq!(
+            U256::from(gas_price.scaled(1.2)),
+            U256::from(12_000_000_000_u64)
+        );
+        assert_eq!(
+            U256::from(gas_price.scaled(1.)),
+            U256::from(10_000_000_000_u64)
+        );
+    }
+
+    #[test]
+    fn checked_ether_arithmetic() {
+        let ether = Ether::from(U256::from(3));
+        assert_eq!(ether.checked_add(2.into()).unwrap().0, U256::from(5));
+        assert_eq!(ether.checked_mul(4.into()).unwrap().0, U256::from(12));
+    }
+
+    #[test]
+    fn checked_ether_arithmetic_overflows() {
+        let max = Ether::from(U256::max_value());
+        assert!(max.checked_add(1.into()).is_none());
+        assert!(max.checked_mul(2.into()).is_none());
+        assert!(Ether::from(U256::exp10(40))
+            .checked_mul(U256::exp10(40))
+            .is_none());
+    }
+}
@@ -14,7 +14,12 @@ use {
         },
         util::{self, conv},
     },
-    std::{collections::HashSet, iter},
+    num::{ToPrimitive, Zero},
+    std::{
+        cmp::Ordering,
+        collections::{HashMap, HashSet},
+        iter,
+    },
 };
 
 pub const FAKE_AUCTION_REWARD: f64 = 35.;
@@ -26,10 +31,43 @@ pub struct Quote {
     /// was a buy order.
     pub amount: eth::U256,
     pub interactions: Vec<eth::Interaction>,
+    /// The estimated gas needed to execute the interactions, or [`None`] if
+    /// the solver didn't provide a gas estimate for every interaction.
+    pub gas: Option<eth::Gas>,
+    /// The relative difference between the quoted execution price and the
+    /// reference prices of the order tokens, or [`None`] if no reference
+    /// prices are available. A positive price impact means that the quote is
+    /// worse than the reference prices.
+    pub price_impact: Option<f64>,
+    /// The known metadata of the traded tokens. Tokens without any metadata
+    /// are omitted.
+    pub tokens: HashMap<eth::TokenAddress, eth::TokenMetadata>,
+    /// The chain the quote was computed for.
+    pub chain_id: eth::ChainId,
+    /// The fee, in sell tokens, covering the cost of the estimated gas at the
+    /// effective gas price, or [`None`] if the gas estimate or the reference
+    /// price of the sell token is unavailable.
+    pub fee: Option<eth::U256>,
 }
 
 impl Quote {
-    fn new(order: &Order, eth: &Ethereum, solution: competition::Solution) -> Result<Self, Error> {
+    fn new(
+        order: &Order,
+        eth: &Ethereum,
+        auction: &competition::Auction,
+        solution: competition::Solution,
+        max_gas: Option<eth::Gas>,
+        max_interactions: Option<usize>,
+    ) -> Result<Self, Error> {
+        if solution.trades.is_empty() {
+            return Err(Error::NoSolution);
+        }
+        if let Some(limit) = max_interactions {
+            let count = solution.interactions.len();
+            if count > limit {
+                return Err(Error::InteractionLimitExceeded { count, limit });
+            }
+        }
         let sell_price = solution
             .prices
             .get(&order.tokens.sell)
@@ -52,9 +90,41 @@ impl Quote {
                     / conv::u256::to_big_rational(sell_price)),
             ),
         };
+        let gas = solution
+            .interactions
+            .iter()
+            .try_fold(eth::U256::zero(), |gas, interaction| {
+                Some(gas.saturating_add(interaction.gas()?.into()))
+            })
+            .map(Into::into);
+        // Quotes without a complete gas estimate can't be checked against the
+        // limit, so they are let through.
+        if let (Some(gas), Some(limit)) = (gas, max_gas) {
+            if gas.0 > limit.0 {
+                return Err(Error::GasLimitExceeded { gas, limit });
+            }
+        }
         Ok(Self {
             amount,
             interactions: boundary::quote::encode_interactions(eth, &solution.interactions)?,
+            gas,
+            price_impact: order.price_impact(amount),
+            tokens: auction
+                .tokens
+                .iter()
+                .map(|token| {
+                    (
+                        token.address,
+                        eth::TokenMetadata {
+                            decimals: token.decimals,
+                            symbol: token.symbol.clone(),
+                        },
+                    )
+                })
+                .filter(|(_, metadata)| !metadata.is_empty())
+                .collect(),
+            chain_id: eth.chain_id(),
+            fee: order.gas_fee(gas),
         })
     }
 }
@@ -67,6 +137,20 @@ pub struct Order {
     pub side: order::Side,
     pub gas_price: eth::EffectiveGasPrice,
     pub deadline: Deadline,
+    /// The time until which the order is intended to be valid, or [`None`] if
+    /// it isn't known, in which case the order is quoted as never expiring.
+    pub valid_to: Option<util::Timestamp>,
+    /// The receiver of the bought tokens, if different from the owner.
+    pub receiver: Option<eth::Address>,
+    pub reference_prices: Option<ReferencePrices>,
+}
+
+/// Reference prices for the sell and buy tokens of an order which is being
+/// quoted.
+#[derive(Debug, Clone, Copy)]
+pub struct ReferencePrices {
+    pub sell: competition::auction::Price,
+    pub buy: competition::auction::Price,
 }
 
 impl Order {
@@ -81,22 +165,46 @@ impl Order {
         liquidity: &infra::liquidity::Fetcher,
         now: time::Now,
     ) -> Result<Quote, Error> {
-        let liquidity = liquidity.fetch(&self.liquidity_pairs()).await;
-        let timeout = self.deadline.timeout(now)?;
-        let solution = solver
-            .solve(&self.fake_auction(), &liquidity, timeout)
-            .await?;
-        Quote::new(self, eth, solution)
+        let (liquidity, sell_metadata, buy_metadata) = futures::join!(
+            liquidity.fetch(&self.liquidity_pairs()),
+            eth.token_metadata(self.tokens.sell),
+            eth.token_metadata(self.tokens.buy),
+        );
+        let timeout = self.deadline.timeout(now, solver.quote_time_buffer())?;
+        let auction = self.fake_auction(sell_metadata, buy_metadata);
+        let solution = solver.solve(&auction, &liquidity, timeout).await?;
+        Quote::new(
+            self,
+            eth,
+            &auction,
+            solution,
+            solver.max_quote_gas(),
+            solver.max_quote_interactions(),
+        )
     }
 
-    fn fake_auction(&self) -> competition::Auction {
+    /// Orders quotes for this order from best to worst, i.e. by the highest
+    /// bought amount for sell orders and by the lowest sold amount for buy
+    /// orders.
+    pub fn compare_quotes(&self, a: &Quote, b: &Quote) -> Ordering {
+        match self.side {
+            order::Side::Sell => b.amount.cmp(&a.amount),
+            order::Side::Buy => a.amount.cmp(&b.amount),
+        }
+    }
+
+    fn fake_auction(
+        &self,
+        sell_metadata: eth::TokenMetadata,
+        buy_metadata: eth::TokenMetadata,
+    ) -> competition::Auction {
         competition::Auction {
             id: None,
-            tokens: Default::default(),
+            tokens: self.auction_tokens(sell_metadata, buy_metadata),
             orders: vec![competition::Order {
                 uid: Default::default(),
-                receiver: None,
-                valid_to: util::Timestamp::MAX,
+                receiver: self.receiver,
+                valid_to: self.valid_to.unwrap_or(util::Timestamp::MAX),
                 sell: self.sell(),
                 buy: self.buy(),
                 side: self.side,
@@ -119,6 +227,72 @@ impl Order {
         }
     }
 
+    /// The auction tokens carrying the reference prices and the token
+    /// metadata, if any.
+    fn auction_tokens(
+        &self,
+        sell_metadata: eth::TokenMetadata,
+        buy_metadata: eth::TokenMetadata,
+    ) -> Vec<competition::auction::Token> {
+        let (sell_price, buy_price) = match self.reference_prices {
+            Some(prices) => (Some(prices.sell), Some(prices.buy)),
+            None => (None, None),
+        };
+        [
+            (self.tokens.sell, sell_price, sell_metadata),
+            (self.tokens.buy, buy_price, buy_metadata),
+        ]
+        .into_iter()
+        .filter(|(_, price, metadata)| price.is_some() || !metadata.is_empty())
+        .map(|(address, price, metadata)| competition::auction::Token {
+            decimals: metadata.decimals,
+            symbol: metadata.symbol,
+            address,
+            price,
+            available_balance: Default::default(),
+            trusted: false,
+        })
+        .collect()
+    }
+
+    /// Computes the price impact of a quoted amount relative to the reference
+    /// prices. Returns [`None`] if there are no reference prices, or if they
+    /// don't allow computing an expected amount.
+    fn price_impact(&self, quoted: eth::U256) -> Option<f64> {
+        let prices = self.reference_prices?;
+        let sell_price = conv::u256::to_big_rational(prices.sell.into());
+        let buy_price = conv::u256::to_big_rational(prices.buy.into());
+        if sell_price.is_zero() || buy_price.is_zero() {
+            return None;
+        }
+        let amount = conv::u256::to_big_rational(self.amount.into());
+        let quoted = conv::u256::to_big_rational(quoted);
+        let (expected, shortfall) = match self.side {
+            order::Side::Sell => {
+                let expected = amount * sell_price / buy_price;
+                let shortfall = &expected - quoted;
+                (expected, shortfall)
+            }
+            order::Side::Buy => {
+                let expected = amount * buy_price / sell_price;
+                let shortfall = quoted - &expected;
+                (expected, shortfall)
+            }
+        };
+        if expected.is_zero() {
+            return None;
+        }
+        (shortfall / expected).to_f64()
+    }
+
+    /// Computes the fee in sell tokens needed to cover the specified gas at the
+    /// order's effective gas price, valued with the sell token's reference
+    /// price.
+    fn gas_fee(&self, gas: Option<eth::Gas>) -> Option<eth::U256> {
+        let cost = self.gas_price.0.checked_mul(gas?.0)?;
+        self.reference_prices?.sell.token_amount(cost)
+    }
+
     /// The asset being bought, or [`eth::U256::one`] if this is a sell, to
     /// facilitate surplus.
     fn buy(&self) -> eth::Asset {
@@ -162,12 +336,18 @@ impl Order {
 pub struct Deadline(chrono::DateTime<chrono::Utc>);
 
 impl Deadline {
-    /// Computes the timeout for solving an auction.
-    pub fn timeout(self, now: time::Now) -> Result<solution::SolverTimeout, DeadlineExceeded> {
-        solution::SolverTimeout::new(self.into(), Self::time_buffer(), now).ok_or(DeadlineExceeded)
+    /// Computes the timeout for solving an auction, reserving `time_buffer`
+    /// before the deadline.
+    pub fn timeout(
+        self,
+        now: time::Now,
+        time_buffer: chrono::Duration,
+    ) -> Result<solution::SolverTimeout, DeadlineExceeded> {
+        solution::SolverTimeout::new(self.into(), time_buffer, now).ok_or(DeadlineExceeded)
     }
 
-    pub fn time_buffer() -> chrono::Duration {
+    /// The time buffer used for solvers that don't configure one.
+    pub fn default_time_buffer() -> chrono::Duration {
         chrono::Duration::seconds(1)
     }
 }
@@ -217,8 +397,19 @@ pub enum Error {
     /// which the user is trying to trade.
     #[error("solver was unable to generate a quote for this order")]
     QuotingFailed,
+    /// The solver returned an empty solution, meaning that it found no way to
+    /// trade the quoted tokens.
+    #[error("solver found no solution for this order")]
+    NoSolution,
     #[error("{0:?}")]
     DeadlineExceeded(#[from] DeadlineExceeded),
+    /// The interactions of the quote use more gas than the configured limit.
+    #[error("quote uses {} gas, exceeding the limit of {}", gas.0, limit.0)]
+    GasLimitExceeded { gas: eth::Gas, limit: eth::Gas },
+    /// The quote has more interactions than the configured limit, making it
+    /// impractical to execute.
+    #[error("quote has {count} interactions, exceeding the limit of {limit}")]
+    InteractionLimitExceeded { count: usize, limit: usize },
     #[error("solver error: {0:?}")]
     Solver(#[from] solver::Error),
     #[error("boundary error: {0:?}")]
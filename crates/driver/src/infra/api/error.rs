@@ -2,6 +2,7 @@ use {
     crate::{
         domain::{
             competition::{self, solution},
+            eth,
             quote,
         },
         infra::api,
@@ -22,6 +23,10 @@ enum Kind {
     InvalidAuctionId,
     MissingSurplusFee,
     QuoteSameTokens,
+    MissingPrice,
+    QuoteGasLimitExceeded,
+    QuoteInteractionLimitExceeded,
+    NoLiquidity,
 }
 
 #[derive(Debug, Serialize)]
@@ -29,6 +34,28 @@ enum Kind {
 pub struct Error {
     kind: Kind,
     description: &'static str,
+    /// The quoted sell token, for errors specific to a token pair.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sell_token: Option<eth::H160>,
+    /// The quoted buy token, for errors specific to a token pair.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    buy_token: Option<eth::H160>,
+}
+
+impl Error {
+    /// The error returned when the solver finds no solution for quoting the
+    /// specified tokens.
+    pub fn no_liquidity(tokens: quote::Tokens) -> (axum::http::StatusCode, axum::Json<Self>) {
+        let axum::Json(error) = axum::Json::<Self>::from(Kind::NoLiquidity);
+        (
+            axum::http::StatusCode::NOT_FOUND,
+            axum::Json(Self {
+                sell_token: Some(tokens.sell().into()),
+                buy_token: Some(tokens.buy().into()),
+                ..error
+            }),
+        )
+    }
 }
 
 impl From<Kind> for axum::Json<Error> {
@@ -44,10 +71,18 @@ impl From<Kind> for axum::Json<Error> {
             Kind::InvalidAuctionId => "Invalid ID specified in the auction",
             Kind::MissingSurplusFee => "Auction contains a limit order with no surplus fee",
             Kind::QuoteSameTokens => "Invalid quote with same buy and sell tokens",
+            Kind::MissingPrice => "Auction is missing a price for a traded token",
+            Kind::QuoteGasLimitExceeded => "Quote exceeds the maximum gas limit",
+            Kind::QuoteInteractionLimitExceeded => {
+                "Quote exceeds the maximum number of interactions"
+            }
+            Kind::NoLiquidity => "No liquidity available for the quoted tokens",
         };
         axum::Json(Error {
             kind: value,
             description,
+            sell_token: None,
+            buy_token: None,
         })
     }
 }
@@ -56,7 +91,10 @@ impl From<quote::Error> for axum::Json<Error> {
     fn from(value: quote::Error) -> Self {
         let error = match value {
             quote::Error::QuotingFailed => Kind::QuotingFailed,
+            quote::Error::NoSolution => Kind::NoLiquidity,
             quote::Error::DeadlineExceeded(_) => Kind::DeadlineExceeded,
+            quote::Error::GasLimitExceeded { .. } => Kind::QuoteGasLimitExceeded,
+            quote::Error::InteractionLimitExceeded { .. } => Kind::QuoteInteractionLimitExceeded,
             quote::Error::Solver(_) => Kind::SolverFailed,
             quote::Error::Boundary(_) => Kind::Unknown,
         };
@@ -86,6 +124,7 @@ impl From<api::routes::AuctionError> for axum::Json<Error> {
             api::routes::AuctionError::InvalidAuctionId => Kind::InvalidAuctionId,
             api::routes::AuctionError::MissingSurplusFee => Kind::MissingSurplusFee,
             api::routes::AuctionError::GasPrice(_) => Kind::Unknown,
+            api::routes::AuctionError::MissingPrice(_) => Kind::MissingPrice,
         };
         error.into()
     }
@@ -1,11 +1,12 @@
 use {
     crate::{
         domain,
+        domain::eth,
         infra::{self, liquidity, solver::Solver, time, Ethereum, Mempool, Simulator},
     },
     error::Error,
     futures::Future,
-    std::{net::SocketAddr, sync::Arc},
+    std::{collections::HashSet, net::SocketAddr, sync::Arc},
     tokio::sync::oneshot,
 };
 
@@ -20,6 +21,10 @@ pub struct Api {
     pub simulator: Simulator,
     pub eth: Ethereum,
     pub mempools: Vec<Mempool>,
+    /// Factor by which the gas price is scaled when building auctions.
+    pub gas_price_factor: f64,
+    /// Tokens that are marked as trusted when building auctions.
+    pub trusted_tokens: HashSet<eth::TokenAddress>,
     pub now: infra::time::Now,
     pub addr: SocketAddr,
     /// If this channel is specified, the bound address will be sent to it. This
@@ -42,14 +47,16 @@ impl Api {
         );
 
         // Multiplex each solver as part of the API.
+        let mut states = Vec::new();
         for solver in self.solvers {
             let name = solver.name().clone();
             let router = axum::Router::new();
             let router = routes::info(router);
+            let router = routes::liquidity(router);
             let router = routes::quote(router);
             let router = routes::solve(router);
             let router = routes::settle(router);
-            let router = router.with_state(State(Arc::new(Inner {
+            let state = State(Arc::new(Inner {
                 eth: self.eth.clone(),
                 solver: solver.clone(),
                 competition: domain::Competition {
@@ -62,10 +69,16 @@ impl Api {
                     settlement: Default::default(),
                 },
                 liquidity: self.liquidity.clone(),
+                gas_price_factor: self.gas_price_factor,
+                trusted_tokens: self.trusted_tokens.clone(),
                 now: self.now,
-            })));
+            }));
+            let router = router.with_state(state.clone());
+            states.push(state);
             app = app.nest(&format!("/{name}"), router);
         }
+        let app = routes::quote_all(app, states);
+        let app = routes::health(app, self.eth);
 
         // Start the server.
         let server = axum::Server::bind(&self.addr).serve(app.into_make_service());
@@ -96,6 +109,14 @@ impl State {
         &self.0.liquidity
     }
 
+    fn gas_price_factor(&self) -> f64 {
+        self.0.gas_price_factor
+    }
+
+    fn trusted_tokens(&self) -> &HashSet<eth::TokenAddress> {
+        &self.0.trusted_tokens
+    }
+
     fn now(&self) -> time::Now {
         self.0.now
     }
@@ -107,5 +128,7 @@ struct Inner {
     solver: Solver,
     competition: domain::Competition,
     liquidity: liquidity::Fetcher,
+    gas_price_factor: f64,
+    trusted_tokens: HashSet<eth::TokenAddress>,
     now: time::Now,
 }
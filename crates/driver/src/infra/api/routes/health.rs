@@ -0,0 +1,27 @@
+use {crate::infra::Ethereum, serde::Serialize};
+
+pub(in crate::infra::api) fn health(app: axum::Router, eth: Ethereum) -> axum::Router {
+    app.merge(
+        axum::Router::new()
+            .route("/health", axum::routing::get(route))
+            .with_state(eth),
+    )
+}
+
+async fn route(
+    eth: axum::extract::State<Ethereum>,
+) -> Result<axum::Json<Health>, axum::http::StatusCode> {
+    let block = eth.current_block().await.map_err(|err| {
+        tracing::warn!(?err, "failed to fetch the current block");
+        axum::http::StatusCode::SERVICE_UNAVAILABLE
+    })?;
+    Ok(axum::Json(Health {
+        block_number: block.0,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Health {
+    block_number: u64,
+}
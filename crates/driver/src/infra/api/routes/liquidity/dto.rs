@@ -0,0 +1,66 @@
+use {
+    crate::{domain::eth, infra::liquidity},
+    serde::Serialize,
+};
+
+impl Source {
+    pub fn from_config(config: &liquidity::Config) -> Vec<Self> {
+        config
+            .uniswap_v2
+            .iter()
+            .map(|config| Self::UniswapV2 {
+                router: config.router.into(),
+                pool_code: config.pool_code.into(),
+            })
+            .collect()
+    }
+}
+
+/// A liquidity source that the driver fetches liquidity from.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Source {
+    #[serde(rename_all = "camelCase")]
+    UniswapV2 {
+        router: eth::H160,
+        pool_code: eth::H256,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn lists_configured_sources() {
+        let config = liquidity::Config {
+            base_tokens: Default::default(),
+            uniswap_v2: vec![
+                liquidity::config::UniswapV2 {
+                    router: eth::H160::repeat_byte(1).into(),
+                    pool_code: eth::H256::repeat_byte(2).into(),
+                },
+                liquidity::config::UniswapV2 {
+                    router: eth::H160::repeat_byte(3).into(),
+                    pool_code: eth::H256::repeat_byte(4).into(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            serde_json::to_value(Source::from_config(&config)).unwrap(),
+            json!([
+                {
+                    "kind": "uniswapV2",
+                    "router": "0x0101010101010101010101010101010101010101",
+                    "poolCode": "0x0202020202020202020202020202020202020202020202020202020202020202",
+                },
+                {
+                    "kind": "uniswapV2",
+                    "router": "0x0303030303030303030303030303030303030303",
+                    "poolCode": "0x0404040404040404040404040404040404040404040404040404040404040404",
+                },
+            ]),
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use crate::infra::api::State;
+
+mod dto;
+
+pub(in crate::infra::api) fn liquidity(router: axum::Router<State>) -> axum::Router<State> {
+    router.route("/liquidity/sources", axum::routing::get(route))
+}
+
+async fn route(state: axum::extract::State<State>) -> axum::Json<Vec<dto::Source>> {
+    axum::Json(dto::Source::from_config(state.liquidity().config()))
+}
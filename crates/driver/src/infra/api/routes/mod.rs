@@ -1,11 +1,15 @@
+mod health;
 mod info;
+mod liquidity;
 mod quote;
 mod settle;
 mod solve;
 
 pub(super) use {
+    health::health,
     info::info,
-    quote::{quote, OrderError},
+    liquidity::liquidity,
+    quote::{quote, quote_all, OrderError},
     settle::settle,
     solve::{solve, AuctionError},
 };
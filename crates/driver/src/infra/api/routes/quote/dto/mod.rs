@@ -3,5 +3,5 @@ mod quote;
 
 pub use {
     order::{Error as OrderError, Order},
-    quote::Quote,
+    quote::{Quote, SolverQuote},
 };
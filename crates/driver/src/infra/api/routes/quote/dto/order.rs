@@ -19,6 +19,15 @@ impl Order {
             },
             gas_price: self.effective_gas_price.into(),
             deadline: self.deadline.into(),
+            valid_to: self.valid_to.map(Into::into),
+            receiver: self.receiver.map(Into::into),
+            reference_prices: match (self.sell_token_price, self.buy_token_price) {
+                (Some(sell), Some(buy)) => Some(quote::ReferencePrices {
+                    sell: sell.into(),
+                    buy: buy.into(),
+                }),
+                _ => None,
+            },
         })
     }
 }
@@ -32,9 +41,29 @@ pub struct Order {
     #[serde_as(as = "serialize::U256")]
     amount: eth::U256,
     kind: Kind,
+    /// The gas price to quote with. A gas price of zero is accepted, making
+    /// the quote gas-free, which is useful for pure price discovery.
     #[serde_as(as = "serialize::U256")]
     effective_gas_price: eth::U256,
     deadline: chrono::DateTime<chrono::Utc>,
+    /// The time until which the quoted order is intended to be valid, which is
+    /// forwarded to the solver so that it can price for that horizon.
+    #[serde(default)]
+    valid_to: Option<u32>,
+    /// The receiver of the bought tokens, forwarded to the solver so that it
+    /// can account for receiver specific interactions.
+    #[serde(default)]
+    receiver: Option<eth::H160>,
+    /// The reference price of the sell token, used for computing the price
+    /// impact of the quote.
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(default)]
+    sell_token_price: Option<eth::U256>,
+    /// The reference price of the buy token, used for computing the price
+    /// impact of the quote.
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(default)]
+    buy_token_price: Option<eth::U256>,
 }
 
 #[derive(Debug, Deserialize)]
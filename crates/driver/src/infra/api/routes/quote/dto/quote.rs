@@ -1,10 +1,12 @@
 use {
     crate::{
         domain::{eth, quote},
+        infra::solver,
         util::serialize,
     },
     serde::Serialize,
-    serde_with::serde_as,
+    serde_with::{serde_as, DisplayFromStr},
+    std::collections::HashMap,
 };
 
 impl Quote {
@@ -20,6 +22,23 @@ impl Quote {
                     call_data: interaction.call_data.clone(),
                 })
                 .collect(),
+            gas: quote.gas.map(Into::into),
+            price_impact: quote.price_impact,
+            tokens: quote
+                .tokens
+                .iter()
+                .map(|(address, metadata)| {
+                    (
+                        (*address).into(),
+                        Token {
+                            decimals: metadata.decimals,
+                            symbol: metadata.symbol.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            chain_id: quote.chain_id.0,
+            fee: quote.fee,
         }
     }
 }
@@ -31,6 +50,50 @@ pub struct Quote {
     #[serde_as(as = "serialize::U256")]
     amount: eth::U256,
     interactions: Vec<Interaction>,
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas: Option<eth::U256>,
+    /// The relative price impact of the quote, or `null` if the reference
+    /// prices are unavailable.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    price_impact: Option<f64>,
+    /// The known decimals and symbols of the traded tokens.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    tokens: HashMap<eth::H160, Token>,
+    /// The chain the quote was computed for, allowing clients to tell apart
+    /// quotes for different chains.
+    #[serde_as(as = "serialize::U256")]
+    chain_id: eth::U256,
+    /// The fee in sell tokens covering the estimated gas cost, omitted if it
+    /// can't be computed.
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fee: Option<eth::U256>,
+}
+
+impl SolverQuote {
+    pub fn from_domain(solver: &solver::Name, quote: &quote::Quote) -> Self {
+        Self {
+            solver: solver.0.clone(),
+            quote: Quote::from_domain(quote),
+        }
+    }
+}
+
+/// The quote of a single solver, when quoting with all solvers.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolverQuote {
+    solver: String,
+    #[serde(flatten)]
+    quote: Quote,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Token {
+    decimals: Option<u8>,
+    symbol: Option<String>,
 }
 
 #[serde_as]
@@ -1,4 +1,11 @@
-use crate::infra::api::{Error, State};
+use {
+    crate::{
+        domain::quote,
+        infra::api::{Error, State},
+    },
+    axum::response::IntoResponse,
+    std::sync::Arc,
+};
 
 mod dto;
 
@@ -8,13 +15,63 @@ pub(in crate::infra::api) fn quote(router: axum::Router<State>) -> axum::Router<
     router.route("/quote", axum::routing::post(route))
 }
 
+/// Quotes an order with every configured solver at once.
+pub(in crate::infra::api) fn quote_all(app: axum::Router, solvers: Vec<State>) -> axum::Router {
+    app.merge(
+        axum::Router::new()
+            .route("/quote/all", axum::routing::post(route_all))
+            .with_state(Arc::new(solvers)),
+    )
+}
+
 async fn route(
     state: axum::extract::State<State>,
     order: axum::Json<dto::Order>,
-) -> Result<axum::Json<dto::Quote>, axum::Json<Error>> {
-    let order = order.0.into_domain()?;
+) -> Result<axum::Json<dto::Quote>, axum::response::Response> {
+    let order = order
+        .0
+        .into_domain()
+        .map_err(|err| axum::Json::<Error>::from(err).into_response())?;
     let quote = order
         .quote(state.eth(), state.solver(), state.liquidity(), state.now())
-        .await?;
+        .await
+        .map_err(|err| match err {
+            quote::Error::NoSolution => Error::no_liquidity(order.tokens).into_response(),
+            err => axum::Json::<Error>::from(err).into_response(),
+        })?;
     Ok(axum::response::Json(dto::Quote::from_domain(&quote)))
 }
+
+async fn route_all(
+    solvers: axum::extract::State<Arc<Vec<State>>>,
+    order: axum::Json<dto::Order>,
+) -> Result<axum::Json<Vec<dto::SolverQuote>>, axum::response::Response> {
+    let order = order
+        .0
+        .into_domain()
+        .map_err(|err| axum::Json::<Error>::from(err).into_response())?;
+    let results = futures::future::join_all(solvers.iter().map(|state| async {
+        let result = order
+            .quote(state.eth(), state.solver(), state.liquidity(), state.now())
+            .await;
+        (state.solver().name(), result)
+    }))
+    .await;
+    let mut quotes = results
+        .into_iter()
+        .filter_map(|(solver, result)| match result {
+            Ok(quote) => Some((solver, quote)),
+            Err(err) => {
+                tracing::debug!(solver = %solver.0, ?err, "solver failed to quote");
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    quotes.sort_by(|(_, a), (_, b)| order.compare_quotes(a, b));
+    Ok(axum::Json(
+        quotes
+            .iter()
+            .map(|(solver, quote)| dto::SolverQuote::from_domain(solver, quote))
+            .collect(),
+    ))
+}
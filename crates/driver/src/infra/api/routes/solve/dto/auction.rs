@@ -7,26 +7,22 @@ use {
     itertools::Itertools,
     serde::Deserialize,
     serde_with::serde_as,
-    std::collections::HashMap,
+    std::collections::{HashMap, HashSet},
 };
 
 impl Auction {
-    pub async fn into_domain(self, eth: &Ethereum) -> Result<competition::Auction, Error> {
-        Ok(competition::Auction {
+    pub async fn into_domain(
+        self,
+        eth: &Ethereum,
+        gas_price_factor: f64,
+        trusted_tokens: &HashSet<eth::TokenAddress>,
+    ) -> Result<competition::Auction, Error> {
+        let auction = competition::Auction {
             id: Some((self.id as u64).into()),
             tokens: self
                 .prices
                 .into_iter()
-                // TODO: Populate currently hardcoded fields.
-                .map(|(key, value)| competition::auction::Token {
-                    decimals: None,
-                    symbol: None,
-                    address: key.into(),
-                    price: Some(value.into()),
-                    available_balance: 0.into(),
-                    // TODO: Does autopilot communicate this to drivers?
-                    trusted: false,
-                })
+                .map(|(address, price)| token(address, price, trusted_tokens))
                 .collect(),
             orders: self
                 .orders
@@ -116,9 +112,35 @@ impl Auction {
                     })
                 })
                 .try_collect::<_, _, Error>()?,
-            gas_price: eth.gas_price().await.map_err(Error::GasPrice)?,
+            gas_price: eth
+                .gas_price()
+                .await
+                .map_err(Error::GasPrice)?
+                .scaled(gas_price_factor),
             deadline: self.deadline.into(),
-        })
+        };
+        auction.validate_prices()?;
+        Ok(auction)
+    }
+}
+
+/// Converts a priced token of the auction into a domain token. Tokens that are
+/// configured as trusted are trusted regardless of the protocol.
+fn token(
+    address: eth::H160,
+    price: eth::U256,
+    trusted_tokens: &HashSet<eth::TokenAddress>,
+) -> competition::auction::Token {
+    let address = address.into();
+    competition::auction::Token {
+        // TODO: Populate currently hardcoded fields.
+        decimals: None,
+        symbol: None,
+        address,
+        price: Some(price.into()),
+        available_balance: 0.into(),
+        // TODO: Does autopilot communicate this to drivers?
+        trusted: trusted_tokens.contains(&address),
     }
 }
 
@@ -130,6 +152,8 @@ pub enum Error {
     MissingSurplusFee,
     #[error("error getting gas price")]
     GasPrice(#[source] crate::infra::blockchain::Error),
+    #[error("{0}")]
+    MissingPrice(#[from] competition::auction::MissingPrice),
 }
 
 #[serde_as]
@@ -234,3 +258,19 @@ enum Class {
     Limit,
     Liquidity,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_tokens_are_trusted() {
+        let configured = eth::H160::repeat_byte(1);
+        let other = eth::H160::repeat_byte(2);
+        let trusted_tokens = HashSet::from([configured.into()]);
+
+        assert!(token(configured, 1.into(), &trusted_tokens).trusted);
+        assert!(!token(other, 1.into(), &trusted_tokens).trusted);
+        assert!(!token(configured, 1.into(), &Default::default()).trusted);
+    }
+}
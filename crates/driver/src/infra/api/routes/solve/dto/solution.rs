@@ -5,18 +5,45 @@ use {
 };
 
 impl Solution {
-    pub fn from_domain(id: solution::Id, score: competition::Score) -> Self {
+    pub fn from_domain(id: solution::Id, scores: competition::Scores) -> Self {
         Self {
             id: id.into(),
-            score: score.into(),
+            score: scores.score.into(),
+            net_score: scores.net.into(),
         }
     }
 }
 
 #[serde_as]
 #[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Solution {
     #[serde_as(as = "DisplayFromStr")]
     id: u64,
+    /// Serialized as a decimal string, since large wei-denominated scores
+    /// would otherwise be emitted in scientific notation by JSON encoders.
+    #[serde_as(as = "DisplayFromStr")]
     score: f64,
+    /// The surplus and fees of the solution minus the cost of the gas it uses
+    /// at the auction's effective gas price.
+    #[serde_as(as = "DisplayFromStr")]
+    net_score: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_large_score_as_decimal_string() {
+        let solution = Solution {
+            id: 1,
+            score: 1234567890123456789.0,
+            net_score: 1234567890123456789.0,
+        };
+        let json = serde_json::to_value(&solution).unwrap();
+        let score = json.get("score").unwrap().as_str().unwrap();
+        assert!(!score.contains('e') && !score.contains('E'));
+        assert_eq!(score.parse::<f64>().unwrap(), solution.score);
+    }
 }
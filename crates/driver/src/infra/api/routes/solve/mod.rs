@@ -14,12 +14,20 @@ async fn route(
     state: axum::extract::State<State>,
     auction: axum::Json<dto::Auction>,
 ) -> Result<axum::Json<dto::Solution>, axum::Json<Error>> {
-    let auction = auction.0.into_domain(state.eth()).await.tap_err(|err| {
-        tracing::warn!(?err, "error creating auction");
-    })?;
+    let auction = auction
+        .0
+        .into_domain(
+            state.eth(),
+            state.gas_price_factor(),
+            state.trusted_tokens(),
+        )
+        .await
+        .tap_err(|err| {
+            tracing::warn!(?err, "error creating auction");
+        })?;
     let competition = state.competition();
-    let (solution_id, score) = competition.solve(&auction).await.tap_err(|err| {
+    let (solution_id, scores) = competition.solve(&auction).await.tap_err(|err| {
         tracing::warn!(?err, "error solving auction");
     })?;
-    Ok(axum::Json(dto::Solution::from_domain(solution_id, score)))
+    Ok(axum::Json(dto::Solution::from_domain(solution_id, scores)))
 }
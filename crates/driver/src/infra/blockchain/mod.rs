@@ -1,6 +1,10 @@
 use {
     self::contracts::ContractAt,
     crate::domain::eth,
+    std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    },
     thiserror::Error,
     web3::{Transport, Web3},
 };
@@ -18,6 +22,8 @@ pub struct Ethereum {
     chain_id: eth::ChainId,
     network_id: eth::NetworkId,
     contracts: Contracts,
+    /// Token metadata never changes, so it is only fetched once per token.
+    token_metadata: Arc<Mutex<HashMap<eth::TokenAddress, eth::TokenMetadata>>>,
 }
 
 impl Ethereum {
@@ -42,6 +48,7 @@ impl Ethereum {
             chain_id,
             network_id,
             contracts,
+            token_metadata: Default::default(),
         })
     }
 
@@ -79,6 +86,31 @@ impl Ethereum {
         Ok(eth::Allowance { spender, amount }.into())
     }
 
+    /// Fetch the ERC20 decimals and symbol of a token. These methods are
+    /// optional in EIP-20, so the metadata is missing for tokens which don't
+    /// implement them. Fetched metadata is cached, unless it is empty since
+    /// that can also be caused by a failing node.
+    ///
+    /// https://eips.ethereum.org/EIPS/eip-20#methods
+    pub async fn token_metadata(&self, token: eth::TokenAddress) -> eth::TokenMetadata {
+        if let Some(metadata) = self.token_metadata.lock().unwrap().get(&token) {
+            return metadata.clone();
+        }
+        let erc20 = contracts::ERC20::at(&self.web3, token.into());
+        let (decimals, symbol) = futures::join!(erc20.decimals().call(), erc20.symbol().call());
+        let metadata = eth::TokenMetadata {
+            decimals: decimals.ok(),
+            symbol: symbol.ok(),
+        };
+        if !metadata.is_empty() {
+            self.token_metadata
+                .lock()
+                .unwrap()
+                .insert(token, metadata.clone());
+        }
+        metadata
+    }
+
     /// Check if a smart contract is deployed to the given address.
     pub async fn is_contract(&self, address: eth::Address) -> Result<bool, Error> {
         let code = self.web3.eth().code(address.into(), None).await?;
@@ -124,6 +156,16 @@ impl Ethereum {
             .map_err(Into::into)
     }
 
+    /// Fetch the number of the most recent block.
+    pub async fn current_block(&self) -> Result<eth::BlockNo, Error> {
+        self.web3
+            .eth()
+            .block_number()
+            .await
+            .map(|number| eth::BlockNo(number.as_u64()))
+            .map_err(Into::into)
+    }
+
     pub async fn gas_price(&self) -> Result<eth::EffectiveGasPrice, Error> {
         self.web3
             .eth()
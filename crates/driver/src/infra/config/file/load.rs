@@ -1,6 +1,6 @@
 use {
     crate::{
-        domain::eth,
+        domain::{eth, quote},
         infra::{self, config::file, liquidity, mempool, simulator, solver},
     },
     std::path::Path,
@@ -18,6 +18,11 @@ pub async fn load(path: &Path) -> infra::Config {
         .unwrap_or_else(|e| panic!("I/O error while reading {path:?}: {e:?}"));
     let config: file::Config = toml::de::from_str(&data)
         .unwrap_or_else(|e| panic!("TOML syntax error while reading {path:?}: {e:?}"));
+    assert!(
+        config.gas_price_factor >= 1.0,
+        "invalid configuration: gas-price-factor must be at least 1, got {}",
+        config.gas_price_factor,
+    );
     infra::Config {
         solvers: config
             .solvers
@@ -30,6 +35,16 @@ pub async fn load(path: &Path) -> infra::Config {
                     absolute: config.absolute_slippage.map(Into::into),
                 },
                 private_key: eth::PrivateKey::from_raw(config.private_key.0).unwrap(),
+                max_deadline_jitter: milliseconds(
+                    "max-deadline-jitter-ms",
+                    config.max_deadline_jitter_ms,
+                ),
+                quote_time_buffer: config
+                    .quote_time_buffer_ms
+                    .map(|ms| milliseconds("quote-time-buffer-ms", ms))
+                    .unwrap_or_else(quote::Deadline::default_time_buffer),
+                max_quote_gas: config.max_quote_gas.map(Into::into),
+                max_quote_interactions: config.max_quote_interactions,
             })
             .collect(),
         liquidity: liquidity::Config {
@@ -93,5 +108,22 @@ pub async fn load(path: &Path) -> infra::Config {
         }),
         contracts: config.contracts,
         disable_access_list_simulation: config.disable_access_list_simulation,
+        gas_price_factor: config.gas_price_factor,
+        trusted_tokens: config.trusted_tokens.into_iter().map(Into::into).collect(),
     }
 }
+
+/// Converts a configured number of milliseconds into a duration.
+///
+/// # Panics
+///
+/// This function panics if the duration is out of range.
+fn milliseconds(name: &str, ms: u64) -> chrono::Duration {
+    let ms = ms.try_into().unwrap_or_else(|_| {
+        panic!(
+            "invalid configuration: {name} must be at most {}, got {ms}",
+            i64::MAX
+        )
+    });
+    chrono::Duration::milliseconds(ms)
+}
@@ -3,6 +3,7 @@ use {
     reqwest::Url,
     serde::Deserialize,
     serde_with::serde_as,
+    std::collections::HashSet,
 };
 
 mod load;
@@ -33,6 +34,23 @@ struct Config {
 
     #[serde(default)]
     liquidity: LiquidityConfig,
+
+    /// Factor by which the gas price is scaled when building auctions for the
+    /// solvers, to provide a safety margin against rising gas prices. Must be
+    /// at least 1.
+    #[serde(default = "default_gas_price_factor")]
+    gas_price_factor: f64,
+
+    /// Additional tokens to mark as trusted in the auctions sent to the
+    /// solvers, regardless of whether the protocol trusts them. This allows
+    /// internalizing interactions with these tokens using the settlement
+    /// contract buffers.
+    #[serde(default)]
+    trusted_tokens: HashSet<eth::H160>,
+}
+
+fn default_gas_price_factor() -> f64 {
+    1.0
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -139,6 +157,24 @@ struct SolverConfig {
     /// The private key used to sign transactions. Expects a 32-byte hex encoded
     /// string.
     private_key: eth::H256,
+
+    /// The maximum random jitter in milliseconds subtracted from the solving
+    /// deadline, to stagger solvers sharing the same infrastructure.
+    #[serde(default)]
+    max_deadline_jitter_ms: u64,
+
+    /// The time in milliseconds reserved before the deadline of a quote
+    /// request, to account for the overhead of processing the solver's
+    /// response. Solvers that are slow to respond may need more headroom.
+    quote_time_buffer_ms: Option<u64>,
+
+    /// The maximum gas that the interactions of a quote may use. Quotes
+    /// exceeding it are rejected, as they would not fit in a settlement.
+    max_quote_gas: Option<u64>,
+
+    /// The maximum number of interactions a quote may have. Quotes exceeding
+    /// it are rejected, as clients can't realistically execute them.
+    max_quote_interactions: Option<usize>,
 }
 
 #[derive(Debug, Default, Deserialize)]
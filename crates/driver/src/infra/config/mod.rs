@@ -1,6 +1,10 @@
 use {
     self::file::ContractsConfig,
-    crate::infra::{liquidity, mempool, simulator, solver},
+    crate::{
+        domain::eth,
+        infra::{liquidity, mempool, simulator, solver},
+    },
+    std::collections::HashSet,
 };
 
 pub mod file;
@@ -14,4 +18,6 @@ pub struct Config {
     pub tenderly: Option<simulator::tenderly::Config>,
     pub mempools: Vec<mempool::Config>,
     pub contracts: ContractsConfig,
+    pub gas_price_factor: f64,
+    pub trusted_tokens: HashSet<eth::TokenAddress>,
 }
@@ -11,6 +11,7 @@ use {
 #[derive(Clone, Debug)]
 pub struct Fetcher {
     inner: Arc<boundary::liquidity::Fetcher>,
+    config: Arc<infra::liquidity::Config>,
 }
 
 impl Fetcher {
@@ -20,9 +21,15 @@ impl Fetcher {
         let inner = boundary::liquidity::Fetcher::new(eth, config).await?;
         Ok(Self {
             inner: Arc::new(inner),
+            config: Arc::new(config.clone()),
         })
     }
 
+    /// The configuration of the liquidity sources this fetcher uses.
+    pub fn config(&self) -> &infra::liquidity::Config {
+        &self.config
+    }
+
     /// Fetches all relevant liquidity for the specified token pairs. Handles
     /// failures by logging and returning an empty vector.
     pub async fn fetch(&self, pairs: &HashSet<liquidity::TokenPair>) -> Vec<liquidity::Liquidity> {
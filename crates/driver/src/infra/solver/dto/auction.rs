@@ -55,6 +55,10 @@ impl Auction {
                         competition::order::Kind::Liquidity => Class::Liquidity,
                     },
                     reward: order.reward,
+                    receiver: order.receiver.map(Into::into),
+                    // Only quotes forward the validity, so that the
+                    // competition payload stays unchanged.
+                    valid_to: auction.id.is_none().then(|| order.valid_to.into()),
                 })
                 .collect(),
             liquidity: liquidity
@@ -124,6 +128,13 @@ struct Order {
     partially_fillable: bool,
     class: Class,
     reward: f64,
+    /// Omitted for orders without an explicit receiver.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receiver: Option<eth::H160>,
+    /// The validity the order is quoted for, where `u32::MAX` means that it
+    /// never expires. Omitted for competition orders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid_to: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
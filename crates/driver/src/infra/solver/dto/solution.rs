@@ -147,6 +147,7 @@ impl Solution {
                                     })
                                     .collect(),
                                 internalize: interaction.internalize,
+                                gas: interaction.gas.map(Into::into),
                             },
                         ))
                     }
@@ -285,6 +286,9 @@ struct CustomInteraction {
     allowances: Vec<Allowance>,
     inputs: Vec<Asset>,
     outputs: Vec<Asset>,
+    #[serde_as(as = "Option<serialize::U256>")]
+    #[serde(default)]
+    gas: Option<eth::U256>,
 }
 
 #[serde_as]
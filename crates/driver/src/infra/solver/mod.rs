@@ -60,6 +60,15 @@ pub struct Config {
     pub slippage: Slippage,
     /// The private key of this solver.
     pub private_key: eth::PrivateKey,
+    /// The maximum random amount of time by which the solving deadline is
+    /// shortened for this solver.
+    pub max_deadline_jitter: chrono::Duration,
+    /// The time reserved before the deadline of a quote request.
+    pub quote_time_buffer: chrono::Duration,
+    /// The maximum gas that the interactions of a quote may use.
+    pub max_quote_gas: Option<eth::Gas>,
+    /// The maximum number of interactions a quote may have.
+    pub max_quote_interactions: Option<usize>,
 }
 
 impl Solver {
@@ -100,6 +109,27 @@ impl Solver {
         self.config.private_key.clone()
     }
 
+    /// The maximum random amount of time by which the solving deadline is
+    /// shortened for this solver.
+    pub fn max_deadline_jitter(&self) -> chrono::Duration {
+        self.config.max_deadline_jitter
+    }
+
+    /// The time reserved before the deadline of a quote request.
+    pub fn quote_time_buffer(&self) -> chrono::Duration {
+        self.config.quote_time_buffer
+    }
+
+    /// The maximum gas that the interactions of a quote may use.
+    pub fn max_quote_gas(&self) -> Option<eth::Gas> {
+        self.config.max_quote_gas
+    }
+
+    /// The maximum number of interactions a quote may have.
+    pub fn max_quote_interactions(&self) -> Option<usize> {
+        self.config.max_quote_interactions
+    }
+
     /// Make a POST request instructing the solver to solve an auction.
     /// Allocates at most `timeout` time for the solving.
     pub async fn solve(
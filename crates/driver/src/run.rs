@@ -57,6 +57,8 @@ pub async fn run(
         .flatten()
         .collect(),
         eth,
+        gas_price_factor: config.gas_price_factor,
+        trusted_tokens: config.trusted_tokens,
         now,
         addr: args.addr,
         addr_sender,
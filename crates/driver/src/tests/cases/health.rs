@@ -0,0 +1,22 @@
+use crate::{infra, tests::setup};
+
+/// Test that the /health endpoint reports the latest block of the node.
+#[ignore]
+#[tokio::test]
+async fn test() {
+    let geth = setup::blockchain::geth().await;
+    let web3 = setup::blockchain::web3(&geth.url());
+    let example_config_file = std::env::current_dir().unwrap().join("example.toml");
+    let driver = setup::driver::setup(setup::driver::Config {
+        now: infra::time::Now::Real,
+        file: setup::driver::ConfigFile::Load(example_config_file),
+        geth: &geth,
+    })
+    .await;
+
+    let (status, health) = driver.health().await;
+    let block_number = web3.eth().block_number().await.unwrap();
+
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert_eq!(health["blockNumber"], block_number.as_u64());
+}
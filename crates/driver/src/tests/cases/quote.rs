@@ -1,7 +1,7 @@
 use {
     super::SOLVER_NAME,
     crate::{
-        domain::quote,
+        domain::{competition::order, eth, quote},
         infra,
         tests::{hex_address, setup},
     },
@@ -9,10 +9,339 @@ use {
     serde_json::json,
 };
 
-/// Test that the /quote endpoint behaves as expected.
+/// The gas reported by the solver for each Uniswap interaction.
+const UNISWAP_INTERACTION_GAS: u64 = 90_000;
+
+/// The name of the second solver, used when quoting with all solvers.
+const OTHER_SOLVER_NAME: &str = "test2";
+
+/// The time buffer configured for the solver, which differs from the default
+/// one.
+const QUOTE_TIME_BUFFER_MS: u64 = 500;
+
+/// Test that the /quote endpoint behaves as expected for sell orders.
+#[ignore]
+#[tokio::test]
+async fn sell() {
+    test(order::Side::Sell).await;
+}
+
+/// Test that the /quote endpoint behaves as expected for buy orders, where the
+/// quoted amount is the amount of sell tokens needed.
+#[ignore]
+#[tokio::test]
+async fn buy() {
+    test(order::Side::Buy).await;
+}
+
+/// Test that the /quote endpoint rejects quotes whose interactions use more gas
+/// than the configured limit.
+#[ignore]
+#[tokio::test]
+async fn gas_limit_exceeded() {
+    let quoted = quote(Case {
+        interaction_gas: 10_000_000,
+        max_quote_gas: Some(15_000_000),
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(quoted.result.get("kind").unwrap(), "QuoteGasLimitExceeded");
+}
+
+/// Test that the /quote endpoint rejects quotes with more interactions than the
+/// configured limit.
+#[ignore]
+#[tokio::test]
+async fn interaction_limit_exceeded() {
+    let quoted = quote(Case {
+        max_quote_interactions: Some(1),
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert!(quoted.interactions.len() > 1);
+    assert_eq!(
+        quoted.result.get("kind").unwrap(),
+        "QuoteInteractionLimitExceeded"
+    );
+}
+
+/// Test that the /quote endpoint accepts a gas price of zero, quoting the full
+/// amount without any deduction for gas.
+#[ignore]
+#[tokio::test]
+async fn zero_gas_price() {
+    let quoted = quote(Case {
+        gas_price: Some(0.into()),
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(
+        quoted.result.get("amount").unwrap(),
+        quoted.quoted_amount.to_string().as_str()
+    );
+    // Without a gas price, executing the quoted interactions costs nothing.
+    let fee = eth::U256::from_dec_str(quoted.result.get("fee").unwrap().as_str().unwrap()).unwrap();
+    assert!(fee.is_zero());
+}
+
+/// Test that the /quote endpoint returns a fee covering the estimated gas at
+/// the effective gas price, valued in sell tokens using the reference prices.
+#[ignore]
+#[tokio::test]
+async fn fee() {
+    let fee = |gas_price: u64| async move {
+        let quoted = quote(Case {
+            gas_price: Some(gas_price.into()),
+            ..Case::new(order::Side::Sell)
+        })
+        .await;
+        let fee =
+            eth::U256::from_dec_str(quoted.result.get("fee").unwrap().as_str().unwrap()).unwrap();
+        // The reference price of the sell token is the quoted buy amount.
+        let gas = UNISWAP_INTERACTION_GAS * quoted.interactions.len() as u64;
+        assert_eq!(
+            fee,
+            eth::U256::from(gas) * gas_price * eth::U256::exp10(18) / quoted.quoted_amount
+        );
+        fee
+    };
+
+    let fee_1_gwei = fee(1_000_000_000).await;
+    let fee_2_gwei = fee(2_000_000_000).await;
+    assert!(!fee_1_gwei.is_zero());
+    assert!(fee_2_gwei >= fee_1_gwei * 2 && fee_2_gwei <= fee_1_gwei * 2 + 1);
+}
+
+/// Test that the /quote endpoint returns a structured error naming the quoted
+/// tokens when the solver finds no solution for them.
+#[ignore]
+#[tokio::test]
+async fn no_liquidity() {
+    let quoted = quote(Case {
+        no_solution: true,
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(quoted.status, reqwest::StatusCode::NOT_FOUND);
+    assert_eq!(quoted.result.get("kind").unwrap(), "NoLiquidity");
+    assert_eq!(
+        quoted.result.get("sellToken").unwrap(),
+        hex_address(quoted.sell_token).as_str()
+    );
+    assert_eq!(
+        quoted.result.get("buyToken").unwrap(),
+        hex_address(quoted.buy_token).as_str()
+    );
+}
+
+/// Test that the /quote endpoint echoes the chain ID of the network the quote
+/// was computed for.
+#[ignore]
+#[tokio::test]
+async fn chain_id() {
+    let quoted = quote(Case::new(order::Side::Sell)).await;
+
+    assert_eq!(
+        quoted.result.get("chainId").unwrap(),
+        quoted.chain_id.to_string().as_str()
+    );
+}
+
+/// Test that the intended validity of the quoted order is forwarded to the
+/// solver. The mock solver asserts that it receives the expected request.
+#[ignore]
+#[tokio::test]
+async fn valid_to() {
+    let quoted = quote(Case {
+        valid_to: Some(1_700_000_000),
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(quoted.status, reqwest::StatusCode::OK);
+}
+
+/// Test that the receiver of the quoted order is forwarded to the solver. The
+/// receiver differs from the owner, which is unknown to the driver when
+/// quoting. The mock solver asserts that it receives the expected request.
+#[ignore]
+#[tokio::test]
+async fn receiver() {
+    let quoted = quote(Case {
+        receiver: Some(ethcontract::H160::from_low_u64_be(0x42)),
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(quoted.status, reqwest::StatusCode::OK);
+}
+
+/// Test that the /quote/all endpoint returns the quotes of all solvers, sorted
+/// from best to worst. The second solver quotes half the amount.
+#[ignore]
+#[tokio::test]
+async fn all_solvers() {
+    let quoted = quote(Case {
+        other_solver: true,
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(quoted.status, reqwest::StatusCode::OK);
+    let quotes = quoted.result.as_array().unwrap();
+    assert_eq!(quotes.len(), 2);
+    assert_eq!(quotes[0].get("solver").unwrap(), SOLVER_NAME);
+    assert_eq!(
+        quotes[0].get("amount").unwrap(),
+        quoted.quoted_amount.to_string().as_str()
+    );
+    assert_eq!(quotes[1].get("solver").unwrap(), OTHER_SOLVER_NAME);
+    assert_eq!(
+        quotes[1].get("amount").unwrap(),
+        (quoted.quoted_amount / 2).to_string().as_str()
+    );
+}
+
+/// Test that the /quote endpoint returns the metadata of the traded tokens
+/// when it is known. The sell token is WETH, which has decimals and a symbol,
+/// while the buy token has neither.
 #[ignore]
 #[tokio::test]
-async fn test() {
+async fn token_metadata() {
+    let quoted = quote(Case {
+        sell_weth: true,
+        ..Case::new(order::Side::Sell)
+    })
+    .await;
+
+    assert_eq!(
+        quoted.result.get("tokens").unwrap(),
+        &json!({
+            hex_address(quoted.sell_token): {
+                "decimals": 18,
+                "symbol": "WETH",
+            },
+        }),
+    );
+}
+
+async fn test(side: order::Side) {
+    let Quoted {
+        status,
+        result,
+        quoted_amount,
+        interactions: uniswap_interactions,
+        ..
+    } = quote(Case::new(side)).await;
+
+    // Assert.
+    assert_eq!(status, reqwest::StatusCode::OK);
+    assert!(result.is_object());
+    assert_eq!(result.as_object().unwrap().len(), 6);
+    assert!(result.get("amount").is_some());
+    assert!(result.get("interactions").is_some());
+    assert!(result.get("gas").is_some());
+    assert!(result.get("chainId").is_some());
+    assert!(result.get("fee").is_some());
+    let gas: u64 = result
+        .get("gas")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        gas,
+        UNISWAP_INTERACTION_GAS * uniswap_interactions.len() as u64
+    );
+    assert_eq!(
+        result.get("amount").unwrap(),
+        quoted_amount.to_string().as_str()
+    );
+    // The solution clears at the reference prices, so there is no price impact
+    // beyond rounding.
+    let price_impact: f64 = result
+        .get("priceImpact")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(price_impact.abs() < 1e-9);
+
+    let interactions = result.get("interactions").unwrap().as_array().unwrap();
+    assert_eq!(interactions.len(), uniswap_interactions.len());
+    for (interaction, (target, call_data)) in interactions.iter().zip(uniswap_interactions) {
+        assert_eq!(
+            interaction.get("target").unwrap(),
+            hex_address(target).as_str()
+        );
+        assert_eq!(interaction.get("value").unwrap(), "0");
+        assert_eq!(
+            interaction.get("callData").unwrap(),
+            &format!("0x{}", hex::encode(call_data))
+        );
+    }
+}
+
+/// The parameters of a quote test case.
+struct Case {
+    side: order::Side,
+    /// The gas reported by the solver for each interaction.
+    interaction_gas: u64,
+    max_quote_gas: Option<u64>,
+    max_quote_interactions: Option<usize>,
+    /// Overrides the gas price of the node.
+    gas_price: Option<eth::U256>,
+    /// Whether the solver responds with an empty solution.
+    no_solution: bool,
+    /// Whether to sell WETH instead of a token without metadata.
+    sell_weth: bool,
+    /// The intended validity of the quoted order.
+    valid_to: Option<u32>,
+    /// The receiver of the quoted order.
+    receiver: Option<ethcontract::H160>,
+    /// Whether to set up a second solver, which quotes half the amount, and
+    /// to quote with all solvers.
+    other_solver: bool,
+}
+
+impl Case {
+    fn new(side: order::Side) -> Self {
+        Self {
+            side,
+            interaction_gas: UNISWAP_INTERACTION_GAS,
+            max_quote_gas: None,
+            max_quote_interactions: None,
+            gas_price: None,
+            no_solution: false,
+            sell_weth: false,
+            valid_to: None,
+            receiver: None,
+            other_solver: false,
+        }
+    }
+}
+
+/// The response of the /quote endpoint along with the expected quote.
+struct Quoted {
+    status: reqwest::StatusCode,
+    result: serde_json::Value,
+    quoted_amount: eth::U256,
+    interactions: Vec<(ethcontract::H160, Vec<u8>)>,
+    sell_token: ethcontract::H160,
+    buy_token: ethcontract::H160,
+    /// The chain ID of the test network.
+    chain_id: eth::U256,
+}
+
+/// Sets up a Uniswap swap, a solver solving with it and a driver, and calls
+/// the /quote endpoint of the driver.
+async fn quote(case: Case) -> Quoted {
     crate::boundary::initialize_tracing("driver=trace");
     // Set up the uniswap swap.
     let setup::blockchain::Uniswap {
@@ -31,13 +360,48 @@ async fn test() {
     } = setup::blockchain::uniswap::setup().await;
 
     // Values for the auction.
-    let sell_token = token_a.address();
+    let sell_token = if case.sell_weth {
+        weth.address()
+    } else {
+        token_a.address()
+    };
+    let (sell_decimals, sell_symbol) = if case.sell_weth {
+        (json!(18), json!("WETH"))
+    } else {
+        (json!(null), json!(null))
+    };
     let buy_token = token_b.address();
     let sell_amount = token_a_in_amount;
     let buy_amount = token_b_out_amount;
-    let gas_price = web3.eth().gas_price().await.unwrap().to_string();
+    let gas_price = match case.gas_price {
+        Some(gas_price) => gas_price,
+        None => web3.eth().gas_price().await.unwrap(),
+    }
+    .to_string();
+    let chain_id = web3.eth().chain_id().await.unwrap();
     let now = infra::time::Now::Fake(chrono::Utc::now());
     let deadline = now.now() + chrono::Duration::seconds(2);
+    // The solver is asked to sell the maximum amount for buy orders, and to
+    // buy at least 1 wei for sell orders, to facilitate surplus.
+    let (kind, amount, solver_sell_amount, solver_buy_amount, executed_amount, quoted_amount) =
+        match case.side {
+            order::Side::Sell => (
+                "sell",
+                sell_amount,
+                sell_amount,
+                1.into(),
+                sell_amount,
+                buy_amount,
+            ),
+            order::Side::Buy => (
+                "buy",
+                buy_amount,
+                eth::U256::max_value(),
+                buy_amount,
+                buy_amount,
+                sell_amount,
+            ),
+        };
     let interactions = uniswap_interactions
         .iter()
         .map(|(address, interaction)| {
@@ -50,62 +414,120 @@ async fn test() {
                 "allowances": [],
                 "inputs": [],
                 "outputs": [],
+                "gas": case.interaction_gas.to_string(),
             })
         })
         .collect_vec();
+    let solution = if case.no_solution {
+        json!({
+            "prices": {},
+            "trades": [],
+            "interactions": [],
+        })
+    } else {
+        json!({
+            "prices": {
+                hex_address(sell_token): buy_amount.to_string(),
+                hex_address(buy_token): sell_amount.to_string(),
+            },
+            "trades": [
+                {
+                    "kind": "fulfillment",
+                    "order":  "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+                    "executedAmount": executed_amount.to_string(),
+                }
+            ],
+            "interactions": interactions
+        })
+    };
 
-    // Set up the solver.
-    let solver = setup::solver::setup(setup::solver::Config {
-        name: SOLVER_NAME.to_owned(),
+    let mut solver_order = json!({
+        "uid": "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        "sellToken": hex_address(sell_token),
+        "buyToken": hex_address(buy_token),
+        "sellAmount": solver_sell_amount.to_string(),
+        "buyAmount": solver_buy_amount.to_string(),
+        "feeAmount": "0",
+        "kind": kind,
+        "partiallyFillable": false,
+        "class": "market",
+        "reward": quote::FAKE_AUCTION_REWARD,
+    });
+    let mut quote_request = json!({
+        "sellToken": hex_address(sell_token),
+        "buyToken": hex_address(buy_token),
+        "amount": amount.to_string(),
+        "kind": kind,
+        "effectiveGasPrice": gas_price,
+        "deadline": deadline,
+        "sellTokenPrice": buy_amount.to_string(),
+        "buyTokenPrice": sell_amount.to_string(),
+    });
+    // Orders quoted without a validity never expire.
+    solver_order["validTo"] = json!(case.valid_to.unwrap_or(u32::MAX));
+    if let Some(valid_to) = case.valid_to {
+        quote_request["validTo"] = json!(valid_to);
+    }
+    if let Some(receiver) = case.receiver {
+        solver_order["receiver"] = json!(hex_address(receiver));
+        quote_request["receiver"] = json!(hex_address(receiver));
+    }
+
+    let solve_request = json!({
+        "id": null,
+        "tokens": {
+            hex_address(sell_token): {
+                "decimals": sell_decimals,
+                "symbol": sell_symbol,
+                "referencePrice": buy_amount.to_string(),
+                "availableBalance": "0",
+                "trusted": false,
+            },
+            hex_address(buy_token): {
+                "decimals": null,
+                "symbol": null,
+                "referencePrice": sell_amount.to_string(),
+                "availableBalance": "0",
+                "trusted": false,
+            },
+        },
+        "orders": [solver_order],
+        "liquidity": [],
+        "effectiveGasPrice": gas_price,
+        // The solver receives the deadline shortened by the configured
+        // time buffer.
+        "deadline": deadline - chrono::Duration::milliseconds(QUOTE_TIME_BUFFER_MS as i64),
+    });
+    // The other solver values the buy token twice as much, quoting half the
+    // amount.
+    let mut other_solution = solution.clone();
+    other_solution["prices"][hex_address(buy_token)] = json!((sell_amount * 2).to_string());
+
+    // Set up the solvers.
+    let solver_config = |name: &str, solution| setup::solver::Config {
+        name: name.to_owned(),
         absolute_slippage: "0".to_owned(),
         relative_slippage: "0.0".to_owned(),
         address: hex_address(solver_address),
         private_key: format!("0x{}", solver_secret_key.display_secret()),
+        quote_time_buffer_ms: Some(QUOTE_TIME_BUFFER_MS),
+        max_quote_gas: case.max_quote_gas,
+        max_quote_interactions: case.max_quote_interactions,
         solve: vec![setup::solver::Solve {
-            req: json!({
-                "id": null,
-                "tokens": {},
-                "orders": [
-                    {
-                        "uid": "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-                        "sellToken": hex_address(sell_token),
-                        "buyToken": hex_address(buy_token),
-                        "sellAmount": sell_amount.to_string(),
-                        "buyAmount": "1",
-                        "feeAmount": "0",
-                        "kind": "sell",
-                        "partiallyFillable": false,
-                        "class": "market",
-                        "reward": quote::FAKE_AUCTION_REWARD,
-                    }
-                ],
-                "liquidity": [],
-                "effectiveGasPrice": gas_price,
-                "deadline": deadline - quote::Deadline::time_buffer(),
-            }),
-            res: json!({
-                "prices": {
-                    hex_address(sell_token): buy_amount.to_string(),
-                    hex_address(buy_token): sell_amount.to_string(),
-                },
-                "trades": [
-                    {
-                        "kind": "fulfillment",
-                        "order":  "0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
-                        "executedAmount": sell_amount.to_string(),
-                    }
-                ],
-                "interactions": interactions
-            }),
+            req: solve_request.clone(),
+            res: solution,
         }],
-    })
-    .await;
+    };
+    let mut solvers = vec![setup::solver::setup(solver_config(SOLVER_NAME, solution)).await];
+    if case.other_solver {
+        solvers.push(setup::solver::setup(solver_config(OTHER_SOLVER_NAME, other_solution)).await);
+    }
 
     // Set up the driver.
     let client = setup::driver::setup(setup::driver::Config {
         now,
         file: setup::driver::ConfigFile::Create {
-            solvers: vec![solver],
+            solvers,
             contracts: infra::config::file::ContractsConfig {
                 gp_v2_settlement: Some(settlement.address()),
                 weth: Some(weth.address()),
@@ -115,42 +537,20 @@ async fn test() {
     })
     .await;
 
-    // Call /quote.
-    let result = client
-        .quote(
-            SOLVER_NAME,
-            json!({
-                "sellToken": hex_address(sell_token),
-                "buyToken": hex_address(buy_token),
-                "amount": sell_amount.to_string(),
-                "kind": "sell",
-                "effectiveGasPrice": gas_price,
-                "deadline": deadline,
-            }),
-        )
-        .await;
-
-    // Assert.
-    assert!(result.is_object());
-    assert_eq!(result.as_object().unwrap().len(), 2);
-    assert!(result.get("amount").is_some());
-    assert!(result.get("interactions").is_some());
-    assert_eq!(
-        result.get("amount").unwrap(),
-        buy_amount.to_string().as_str()
-    );
+    // Call /quote, or /quote/all when quoting with all solvers.
+    let (status, result) = if case.other_solver {
+        client.quote_all(quote_request).await
+    } else {
+        client.try_quote(SOLVER_NAME, quote_request).await
+    };
 
-    let interactions = result.get("interactions").unwrap().as_array().unwrap();
-    assert_eq!(interactions.len(), uniswap_interactions.len());
-    for (interaction, (target, call_data)) in interactions.iter().zip(uniswap_interactions) {
-        assert_eq!(
-            interaction.get("target").unwrap(),
-            hex_address(target).as_str()
-        );
-        assert_eq!(interaction.get("value").unwrap(), "0");
-        assert_eq!(
-            interaction.get("callData").unwrap(),
-            &format!("0x{}", hex::encode(call_data))
-        );
+    Quoted {
+        status,
+        result,
+        quoted_amount,
+        interactions: uniswap_interactions,
+        sell_token,
+        buy_token,
+        chain_id,
     }
 }
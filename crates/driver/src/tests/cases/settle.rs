@@ -77,6 +77,9 @@ async fn test() {
         relative_slippage: "0.0".to_owned(),
         address: hex_address(solver_address),
         private_key: format!("0x{}", solver_secret_key.display_secret()),
+        quote_time_buffer_ms: None,
+        max_quote_gas: None,
+        max_quote_interactions: None,
         solve: vec![setup::solver::Solve {
             req: json!({
                 "id": "1",
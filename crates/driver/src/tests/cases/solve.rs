@@ -77,6 +77,9 @@ async fn test() {
         relative_slippage: "0.0".to_owned(),
         address: hex_address(solver_address),
         private_key: format!("0x{}", solver_secret_key.display_secret()),
+        quote_time_buffer_ms: None,
+        max_quote_gas: None,
+        max_quote_interactions: None,
         solve: vec![setup::solver::Solve {
             req: json!({
                 "id": "1",
@@ -185,9 +188,10 @@ async fn test() {
 
     // Assert.
     assert!(result.is_object());
-    assert_eq!(result.as_object().unwrap().len(), 2);
+    assert_eq!(result.as_object().unwrap().len(), 3);
     assert!(result.get("id").is_some());
-    assert!(result.get("score").is_some());
-    let score = result.get("score").unwrap().as_f64().unwrap();
-    approx::assert_relative_eq!(score, -74551241429078.0, max_relative = 0.01);
+    let score = |key: &str| -> f64 { result.get(key).unwrap().as_str().unwrap().parse().unwrap() };
+    approx::assert_relative_eq!(score("score"), -74551241429078.0, max_relative = 0.01);
+    // The gas used by the settlement costs more than the surplus it generates.
+    assert!(score("netScore") < 0.);
 }
@@ -56,6 +56,17 @@ impl Client {
     }
 
     pub async fn quote(&self, solver: &str, req: serde_json::Value) -> serde_json::Value {
+        let (status, res) = self.try_quote(solver, req).await;
+        assert_eq!(status, 200);
+        res
+    }
+
+    /// Calls /quote, returning the response status along with the body.
+    pub async fn try_quote(
+        &self,
+        solver: &str,
+        req: serde_json::Value,
+    ) -> (reqwest::StatusCode, serde_json::Value) {
         let res = self
             .client
             .post(format!("http://{}/{solver}/quote", self.addr))
@@ -66,8 +77,38 @@ impl Client {
         let status = res.status();
         let text = res.text().await.unwrap();
         tracing::debug!(?status, ?text, "got a response from /quote");
-        assert_eq!(status, 200);
-        serde_json::from_str(&text).unwrap()
+        (status, serde_json::from_str(&text).unwrap())
+    }
+
+    /// Calls /quote/all, returning the response status along with the body.
+    pub async fn quote_all(
+        &self,
+        req: serde_json::Value,
+    ) -> (reqwest::StatusCode, serde_json::Value) {
+        let res = self
+            .client
+            .post(format!("http://{}/quote/all", self.addr))
+            .json(&req)
+            .send()
+            .await
+            .unwrap();
+        let status = res.status();
+        let text = res.text().await.unwrap();
+        tracing::debug!(?status, ?text, "got a response from /quote/all");
+        (status, serde_json::from_str(&text).unwrap())
+    }
+
+    pub async fn health(&self) -> (reqwest::StatusCode, serde_json::Value) {
+        let res = self
+            .client
+            .get(format!("http://{}/health", self.addr))
+            .send()
+            .await
+            .unwrap();
+        let status = res.status();
+        let text = res.text().await.unwrap();
+        tracing::debug!(?status, ?text, "got a response from /health");
+        (status, serde_json::from_str(&text).unwrap_or_default())
     }
 
     pub async fn settle(&self, solver: &str, solution_id: &str) {
@@ -181,10 +222,22 @@ async fn create_config_file(
                     address,
                     private_key,
                     name,
+                    quote_time_buffer_ms,
+                    max_quote_gas,
+                    max_quote_interactions,
                     ..
                 },
             addr,
         } = solver;
+        let quote_time_buffer = quote_time_buffer_ms
+            .map(|ms| format!("quote-time-buffer-ms = {ms}"))
+            .unwrap_or_default();
+        let max_quote_gas = max_quote_gas
+            .map(|gas| format!("max-quote-gas = {gas}"))
+            .unwrap_or_default();
+        let max_quote_interactions = max_quote_interactions
+            .map(|count| format!("max-quote-interactions = {count}"))
+            .unwrap_or_default();
         #[rustfmt::skip]
             let config = format!(
                 r#"[[solver]]
@@ -193,7 +246,10 @@ async fn create_config_file(
                    absolute-slippage = "{absolute_slippage}"
                    relative-slippage = "{relative_slippage}"
                    address = "{address}"
-                   private-key = "{private_key}""#
+                   private-key = "{private_key}"
+                   {quote_time_buffer}
+                   {max_quote_gas}
+                   {max_quote_interactions}"#
             );
         config
     });
@@ -14,6 +14,12 @@ pub struct Config {
     pub relative_slippage: String,
     pub address: String,
     pub private_key: String,
+    /// Overrides the time in milliseconds reserved before quote deadlines.
+    pub quote_time_buffer_ms: Option<u64>,
+    /// The maximum gas that the interactions of a quote may use.
+    pub max_quote_gas: Option<u64>,
+    /// The maximum number of interactions a quote may have.
+    pub max_quote_interactions: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -10,7 +10,7 @@ use {
         },
         services::{get_auction, solvable_orders, wait_for_condition, API_HOST},
     },
-    anyhow::bail,
+    anyhow::{bail, ensure},
     autopilot::database::onchain_order_events::ethflow_events::WRAP_ALL_SELECTOR,
     chrono::{DateTime, NaiveDateTime, Utc},
     contracts::{CoWSwapEthFlow, ERC20Mintable, WETH9},
@@ -24,6 +24,7 @@ use {
         U256,
     },
     hex_literal::hex,
+    lazy_static::lazy_static,
     model::{
         app_id::AppId,
         auction::AuctionWithId,
@@ -60,7 +61,12 @@ use {
         ethrpc::Web3,
         signature_validator::check_erc1271_result,
     },
-    std::time::Duration,
+    solver::{
+        encoding::{encode_trade, EncodedSettlement},
+        settlement_simulation::settle_method_builder,
+    },
+    std::{collections::HashMap, sync::Mutex, time::Duration},
+    web3::types::BlockNumber,
 };
 const ACCOUNT_ENDPOINT: &str = "/api/v1/account";
 const AUCTION_ENDPOINT: &str = "/api/v1/auction";
@@ -76,12 +82,30 @@ async fn local_node_eth_flow() {
     crate::local_node::test(eth_flow_tx).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn local_node_eth_flow_buy() {
+    crate::local_node::test(eth_flow_buy).await;
+}
+
+#[tokio::test]
+#[ignore]
+async fn local_node_eth_flow_partially_fillable() {
+    crate::local_node::test(eth_flow_partially_fillable).await;
+}
+
 #[tokio::test]
 #[ignore]
 async fn local_node_eth_flow_indexing_after_refund() {
     crate::local_node::test(eth_flow_indexing_after_refund).await;
 }
 
+#[tokio::test]
+#[ignore]
+async fn local_node_eth_flow_order_hash() {
+    crate::local_node::test(eth_flow_order_hash).await;
+}
+
 async fn eth_flow_tx(web3: Web3) {
     shared::tracing::initialize_reentrant(
         "e2e=debug,orderbook=debug,solver=debug,autopilot=debug,\
@@ -130,9 +154,10 @@ async fn eth_flow_tx(web3: Web3) {
     let receiver = H160([0x42; 20]);
     let sell_amount = to_wei(1);
     let intent = EthFlowTradeIntent {
-        sell_amount,
+        amount: EthFlowTradeAmount::Sell(sell_amount),
         buy_token,
         receiver,
+        partially_fillable: false,
     };
 
     let client = reqwest::Client::default();
@@ -150,8 +175,9 @@ async fn eth_flow_tx(web3: Web3) {
     let valid_to = chrono::offset::Utc::now().timestamp() as u32
         + timestamp_of_current_block_in_seconds(&web3).await.unwrap()
         + 3600;
-    let ethflow_order =
-        ExtendedEthFlowOrder::from_quote(&quote, valid_to).include_slippage_bps(300);
+    let ethflow_order = ExtendedEthFlowOrder::from_quote(&quote, valid_to)
+        .include_slippage_bps(300)
+        .unwrap();
 
     sumbit_order(&ethflow_order, &trader, &contracts).await;
 
@@ -232,9 +258,10 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
     let dummy_order = ExtendedEthFlowOrder::from_quote(
         &submit_quote(
             &(EthFlowTradeIntent {
-                sell_amount: 42.into(),
+                amount: EthFlowTradeAmount::Sell(42.into()),
                 buy_token: dai.address(),
                 receiver: H160([42; 20]),
+                partially_fillable: false,
             })
             .to_quote_request(&contracts.ethflow, &contracts.weth),
             &client,
@@ -242,7 +269,8 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
         .await,
         valid_to,
     )
-    .include_slippage_bps(300);
+    .include_slippage_bps(300)
+    .unwrap();
     sumbit_order(&dummy_order, &dummy_trader, &contracts).await;
     web3.api::<TestNodeApi<_>>()
         .set_next_block_timestamp(&DateTime::from_utc(
@@ -254,6 +282,9 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
     dummy_order
         .mine_order_invalidation(&refunder, &contracts.ethflow)
         .await;
+    dummy_order
+        .assert_refunded(dummy_trader.address(), &web3)
+        .await;
 
     // Create the actual order that should be picked up by the services and matched.
     let buy_token = dai.address();
@@ -265,9 +296,10 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
     let ethflow_order = ExtendedEthFlowOrder::from_quote(
         &submit_quote(
             &(EthFlowTradeIntent {
-                sell_amount,
+                amount: EthFlowTradeAmount::Sell(sell_amount),
                 buy_token,
                 receiver,
+                partially_fillable: false,
             })
             .to_quote_request(&contracts.ethflow, &contracts.weth),
             &client,
@@ -275,7 +307,8 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
         .await,
         valid_to,
     )
-    .include_slippage_bps(300);
+    .include_slippage_bps(300)
+    .unwrap();
     sumbit_order(&ethflow_order, &trader, &contracts).await;
 
     tracing::info!("waiting for trade");
@@ -286,6 +319,295 @@ async fn eth_flow_indexing_after_refund(web3: Web3) {
     .unwrap();
     crate::services::start_old_driver(&contracts, &SOLVER_PK, &[]);
     test_order_was_settled(&ethflow_order, &web3).await;
+
+    let valid_to = ethflow_order.0.valid_to;
+    assert_eq!(
+        ExtendedEthFlowOrder::statuses(&[dummy_order, ethflow_order], &contracts).await,
+        vec![
+            EthFlowOrderOnchainStatus::Invalidated,
+            EthFlowOrderOnchainStatus::Created(trader.address(), valid_to),
+        ]
+    );
+}
+
+async fn eth_flow_order_hash(web3: Web3) {
+    let contracts = crate::deploy::deploy(&web3).await.expect("deploy");
+    let order = ExtendedEthFlowOrder(EthflowOrder {
+        buy_token: H160([1; 20]),
+        receiver: H160([2; 20]),
+        sell_amount: to_wei(1),
+        buy_amount: to_wei(1000),
+        app_data: Bytes([0; 32]),
+        fee_amount: 0.into(),
+        valid_to: u32::MAX,
+        partially_fillable: false,
+        quote_id: 0,
+    });
+
+    let domain_separator = DomainSeparator(
+        contracts
+            .gp_settlement
+            .domain_separator()
+            .call()
+            .await
+            .unwrap()
+            .0,
+    );
+    let expected = order.hash_with_domain(&domain_separator, &contracts.ethflow, &contracts.weth);
+
+    // The second call uses the cached domain separator.
+    assert_eq!(order.hash(&contracts).await, expected);
+    assert_eq!(order.hash(&contracts).await, expected);
+}
+
+async fn eth_flow_buy(web3: Web3) {
+    shared::tracing::initialize_reentrant(
+        "e2e=debug,orderbook=debug,solver=debug,autopilot=debug,\
+         orderbook::api::request_summary=off",
+    );
+    shared::exit_process_on_panic::set_panic_hook();
+
+    crate::services::clear_database().await;
+    let contracts = crate::deploy::deploy(&web3).await.expect("deploy");
+
+    const SOLVER_PK: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000001");
+    let solver = Account::Offline(PrivateKey::from_raw(SOLVER_PK).unwrap(), None);
+    contracts
+        .gp_authenticator
+        .add_solver(solver.address())
+        .send()
+        .await
+        .unwrap();
+    const TRADER_PK: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000002");
+    let trader = Account::Offline(PrivateKey::from_raw(TRADER_PK).unwrap(), None);
+    for account in [&solver, &trader] {
+        TransactionBuilder::new(web3.clone())
+            .value(to_wei(2))
+            .to(account.address())
+            .send()
+            .await
+            .unwrap();
+    }
+
+    // Create token with Uniswap pool for price estimation
+    let MintableToken { contract: dai, .. } = deploy_token_with_weth_uniswap_pool(
+        &web3,
+        &contracts,
+        WethPoolConfig {
+            // 1 ETH ≈ 1k DAI
+            token_amount: to_wei(DAI_PER_ETH * 1_000),
+            weth_amount: to_wei(1_000),
+        },
+    )
+    .await;
+
+    crate::services::start_api(&contracts, &[]);
+    crate::services::start_autopilot(&contracts, &[]);
+    crate::services::wait_for_api_to_come_up().await;
+
+    let client = reqwest::Client::default();
+
+    // Buy exactly 500 DAI with ETH.
+    let buy_amount = to_wei(DAI_PER_ETH / 2);
+    let valid_to = chrono::offset::Utc::now().timestamp() as u32
+        + timestamp_of_current_block_in_seconds(&web3).await.unwrap()
+        + 3600;
+    let ethflow_order = ExtendedEthFlowOrder::from_buy_quote(
+        &submit_quote(
+            &(EthFlowTradeIntent {
+                amount: EthFlowTradeAmount::Buy(buy_amount),
+                buy_token: dai.address(),
+                receiver: H160([0x42; 20]),
+                partially_fillable: false,
+            })
+            .to_quote_request(&contracts.ethflow, &contracts.weth),
+            &client,
+        )
+        .await,
+        valid_to,
+        300,
+    );
+    assert_eq!(ethflow_order.0.buy_amount, buy_amount);
+    sumbit_order(&ethflow_order, &trader, &contracts).await;
+
+    test_order_availability_in_api(&client, &ethflow_order, &trader.address(), &contracts).await;
+
+    tracing::info!("waiting for trade");
+    crate::services::start_old_driver(&contracts, &SOLVER_PK, &[]);
+    test_order_was_settled(&ethflow_order, &web3).await;
+}
+
+async fn eth_flow_partially_fillable(web3: Web3) {
+    shared::tracing::initialize_reentrant(
+        "e2e=debug,orderbook=debug,solver=debug,autopilot=debug,\
+         orderbook::api::request_summary=off",
+    );
+    shared::exit_process_on_panic::set_panic_hook();
+
+    crate::services::clear_database().await;
+    let contracts = crate::deploy::deploy(&web3).await.expect("deploy");
+
+    const SOLVER_PK: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000001");
+    let solver = Account::Offline(PrivateKey::from_raw(SOLVER_PK).unwrap(), None);
+    contracts
+        .gp_authenticator
+        .add_solver(solver.address())
+        .send()
+        .await
+        .unwrap();
+    const TRADER_PK: [u8; 32] =
+        hex!("0000000000000000000000000000000000000000000000000000000000000002");
+    let trader = Account::Offline(PrivateKey::from_raw(TRADER_PK).unwrap(), None);
+    for account in [&solver, &trader] {
+        TransactionBuilder::new(web3.clone())
+            .value(to_wei(3))
+            .to(account.address())
+            .send()
+            .await
+            .unwrap();
+    }
+
+    // Create token with Uniswap pool for price estimation
+    let dai = deploy_token_with_weth_uniswap_pool(
+        &web3,
+        &contracts,
+        WethPoolConfig {
+            // 1 ETH ≈ 1k DAI
+            token_amount: to_wei(DAI_PER_ETH * 1_000),
+            weth_amount: to_wei(1_000),
+        },
+    )
+    .await;
+
+    crate::services::start_api(&contracts, &[]);
+    crate::services::start_autopilot(&contracts, &[]);
+    crate::services::wait_for_api_to_come_up().await;
+
+    let client = reqwest::Client::default();
+
+    let receiver = H160([0x42; 20]);
+    let valid_to = chrono::offset::Utc::now().timestamp() as u32
+        + timestamp_of_current_block_in_seconds(&web3).await.unwrap()
+        + 3600;
+    let ethflow_order = ExtendedEthFlowOrder::from_quote(
+        &submit_quote(
+            &(EthFlowTradeIntent {
+                amount: EthFlowTradeAmount::Sell(to_wei(2)),
+                buy_token: dai.contract.address(),
+                receiver,
+                partially_fillable: true,
+            })
+            .to_quote_request(&contracts.ethflow, &contracts.weth),
+            &client,
+        )
+        .await,
+        valid_to,
+    )
+    .include_slippage_bps(300)
+    .unwrap();
+    assert!(ethflow_order.0.partially_fillable);
+    sumbit_order(&ethflow_order, &trader, &contracts).await;
+
+    test_order_availability_in_api(&client, &ethflow_order, &trader.address(), &contracts).await;
+
+    // Settle the first half of the order directly with the solver account so
+    // that the order is guaranteed to be filled over two settlements. The
+    // settlement contract pays out the bought DAI from its own buffers.
+    let cow_order = ethflow_order.to_cow_swap_order(&contracts.ethflow, &contracts.weth);
+    let partial_sell_amount = cow_order.data.sell_amount / 2;
+    dai.mint(
+        contracts.gp_settlement.address(),
+        cow_order.data.buy_amount / 2,
+    )
+    .await;
+    settle_method_builder(
+        &contracts.gp_settlement,
+        EncodedSettlement {
+            tokens: vec![contracts.weth.address(), dai.contract.address()],
+            clearing_prices: vec![cow_order.data.buy_amount, cow_order.data.sell_amount],
+            trades: vec![encode_trade(
+                &cow_order.data,
+                &cow_order.signature,
+                cow_order.metadata.owner,
+                0,
+                1,
+                &partial_sell_amount,
+            )],
+            interactions: [
+                vec![(
+                    contracts.ethflow.address(),
+                    U256::zero(),
+                    Bytes(WRAP_ALL_SELECTOR.to_vec()),
+                )],
+                vec![],
+                vec![],
+            ],
+        },
+        solver.clone(),
+    )
+    .send()
+    .await
+    .unwrap();
+
+    let uid = ethflow_order.uid(&contracts).await;
+    let executed_sell_amount = || async {
+        client
+            .get(&format!("{API_HOST}{ORDERS_ENDPOINT}/{uid}"))
+            .send()
+            .await
+            .unwrap()
+            .json::<Order>()
+            .await
+            .unwrap()
+            .metadata
+            .executed_sell_amount_before_fees
+    };
+    tracing::info!("waiting for the first partial fill to be indexed");
+    wait_for_condition(Duration::from_secs(20), || async {
+        executed_sell_amount().await == partial_sell_amount
+    })
+    .await
+    .unwrap();
+
+    tracing::info!("waiting for the remainder to be settled");
+    crate::services::start_old_driver(&contracts, &SOLVER_PK, &[]);
+    wait_for_condition(Duration::from_secs(20), || async {
+        executed_sell_amount().await == ethflow_order.0.sell_amount
+    })
+    .await
+    .unwrap();
+
+    // The order can be filled over multiple settlements, so the receiver's
+    // balance has to match the cumulative amount bought by all trades.
+    let trades = client
+        .get(&format!("{API_HOST}{TRADES_ENDPOINT}"))
+        .query(&[("orderUid", format!("{uid:?}"))])
+        .send()
+        .await
+        .unwrap()
+        .json::<Vec<Trade>>()
+        .await
+        .unwrap();
+    assert_eq!(trades.len(), 2);
+    let filled_buy_amount = trades.iter().fold(U256::zero(), |sum, trade| {
+        sum + U256::from_dec_str(&trade.buy_amount.to_string()).unwrap()
+    });
+    let filled_sell_amount = trades.iter().fold(U256::zero(), |sum, trade| {
+        sum + U256::from_dec_str(&trade.sell_amount_before_fees.to_string()).unwrap()
+    });
+    assert_eq!(filled_sell_amount, ethflow_order.0.sell_amount);
+
+    let receiver_buy_token_balance = dai
+        .contract
+        .balance_of(receiver)
+        .call()
+        .await
+        .expect("Unable to get token balance");
+    assert_eq!(receiver_buy_token_balance, filled_buy_amount);
+    assert!(receiver_buy_token_balance >= ethflow_order.0.buy_amount);
 }
 
 async fn submit_quote(quote: &OrderQuoteRequest, client: &reqwest::Client) -> OrderQuoteResponse {
@@ -303,22 +625,30 @@ async fn submit_quote(quote: &OrderQuoteRequest, client: &reqwest::Client) -> Or
     assert!(response.id.is_some());
     // Ideally the fee would be nonzero, but this is not the case in the test
     // environment assert_ne!(response.quote.fee_amount, 0.into());
-    // Amount is reasonable (±10% from real price)
-    let approx_output: U256 = response.quote.sell_amount * DAI_PER_ETH;
-    assert!(response.quote.buy_amount.gt(&(approx_output * 9u64 / 10)));
-    assert!(response.quote.buy_amount.lt(&(approx_output * 11u64 / 10)));
-
-    if let OrderQuoteSide::Sell {
-        sell_amount:
-            model::quote::SellAmount::AfterFee {
-                value: sell_amount_after_fees,
-            },
-    } = quote.side
-    {
-        assert_eq!(response.quote.sell_amount, sell_amount_after_fees);
-    } else {
-        panic!("Untested")
-    };
+    match quote.side {
+        OrderQuoteSide::Sell {
+            sell_amount:
+                model::quote::SellAmount::AfterFee {
+                    value: sell_amount_after_fees,
+                },
+        } => {
+            assert_eq!(response.quote.sell_amount, sell_amount_after_fees);
+            // Amount is reasonable (±10% from real price)
+            let approx_output: U256 = response.quote.sell_amount * DAI_PER_ETH;
+            assert!(response.quote.buy_amount.gt(&(approx_output * 9u64 / 10)));
+            assert!(response.quote.buy_amount.lt(&(approx_output * 11u64 / 10)));
+        }
+        OrderQuoteSide::Buy {
+            buy_amount_after_fee,
+        } => {
+            assert_eq!(response.quote.buy_amount, buy_amount_after_fee);
+            // Amount is reasonable (±10% from real price)
+            let approx_input: U256 = response.quote.buy_amount / DAI_PER_ETH;
+            assert!(response.quote.sell_amount.gt(&(approx_input * 9u64 / 10)));
+            assert!(response.quote.sell_amount.lt(&(approx_input * 11u64 / 10)));
+        }
+        _ => panic!("Untested"),
+    }
 
     response
 }
@@ -524,11 +854,37 @@ async fn test_order_parameters(
     );
 
     assert_eq!(response.metadata.class, OrderClass::Market);
+    assert_eq!(response.data.partially_fillable, order.0.partially_fillable);
 
     assert!(order
         .is_valid_cowswap_signature(&response.signature, contracts)
         .await
         .is_ok());
+    // A second eth-flow deployment doesn't know about the order, so only the
+    // deployment the order was placed with validates its signature.
+    let other_ethflow = CoWSwapEthFlow::builder(
+        &contracts.ethflow.raw_instance().web3(),
+        contracts.gp_settlement.address(),
+        contracts.weth.address(),
+    )
+    .deploy()
+    .await
+    .unwrap();
+    assert_eq!(
+        order
+            .recover_signer(
+                &response.signature,
+                &[other_ethflow.clone(), contracts.ethflow.clone()],
+                contracts,
+            )
+            .await
+            .unwrap(),
+        contracts.ethflow.address()
+    );
+    assert!(order
+        .recover_signer(&response.signature, &[other_ethflow], contracts)
+        .await
+        .is_err());
 
     // Requires wrapping first
     assert_eq!(response.interactions.pre.len(), 1);
@@ -557,6 +913,27 @@ impl ExtendedEthFlowOrder {
         })
     }
 
+    /// Builds an eth-flow order from a quote for buying an exact amount of
+    /// tokens. Eth-flow orders are always sell orders, so the slippage is
+    /// added to the quoted sell amount instead of being deducted from the buy
+    /// amount.
+    pub fn from_buy_quote(
+        quote_response: &OrderQuoteResponse,
+        valid_to: u32,
+        slippage_bps: u16,
+    ) -> Self {
+        const MAX_BASE_POINT: u16 = 10000;
+        assert_eq!(quote_response.quote.kind, OrderKind::Buy);
+        if slippage_bps > MAX_BASE_POINT {
+            panic!("Slippage must be specified in base points");
+        }
+        let order = Self::from_quote(quote_response, valid_to).0;
+        ExtendedEthFlowOrder(EthflowOrder {
+            sell_amount: order.sell_amount * (MAX_BASE_POINT + slippage_bps) / MAX_BASE_POINT,
+            ..order
+        })
+    }
+
     fn to_cow_swap_order(&self, ethflow: &CoWSwapEthFlow, weth: &WETH9) -> Order {
         // Each ethflow user order has an order that is representing
         // it as EIP1271 order with a different owner and valid_to
@@ -570,20 +947,55 @@ impl ExtendedEthFlowOrder {
             .with_buy_amount(self.0.buy_amount)
             .with_valid_to(u32::MAX)
             .with_app_data(self.0.app_data.0)
+            .with_partially_fillable(self.0.partially_fillable)
             .with_class(OrderClass::Market) // Eth-flow orders only support market orders at this point in time
             .with_eip1271(ethflow.address(), hex!("").into())
             .build()
     }
 
-    pub fn include_slippage_bps(&self, slippage: u16) -> Self {
+    /// Deducts the specified slippage from the order's buy amount. Fails if
+    /// the slippage would reduce the buy amount to zero, which would make the
+    /// order accept any price.
+    pub fn include_slippage_bps(&self, slippage: u16) -> anyhow::Result<Self> {
+        self.include_slippage_with_floor(slippage, U256::zero())
+    }
+
+    /// Deducts slippage from the order's buy amount, using the larger of the
+    /// relative slippage in basis points and the absolute `min_absolute`
+    /// amount. This ensures small orders still get meaningful slippage. Fails
+    /// if the slippage would reduce the buy amount to zero.
+    pub fn include_slippage_with_floor(
+        &self,
+        slippage: u16,
+        min_absolute: U256,
+    ) -> anyhow::Result<Self> {
         const MAX_BASE_POINT: u16 = 10000;
-        if slippage > MAX_BASE_POINT {
-            panic!("Slippage must be specified in base points");
-        }
-        ExtendedEthFlowOrder(EthflowOrder {
-            buy_amount: self.0.buy_amount * (MAX_BASE_POINT - slippage) / MAX_BASE_POINT,
+        ensure!(
+            slippage <= MAX_BASE_POINT,
+            "Slippage must be specified in base points"
+        );
+        let buy_amount = std::cmp::min(
+            self.0.buy_amount * (MAX_BASE_POINT - slippage) / MAX_BASE_POINT,
+            self.0.buy_amount.saturating_sub(min_absolute),
+        );
+        ensure!(
+            !buy_amount.is_zero(),
+            "slippage of {slippage} bps reduces buy amount {} to zero",
+            self.0.buy_amount
+        );
+        Ok(ExtendedEthFlowOrder(EthflowOrder {
+            buy_amount,
             ..self.0
-        })
+        }))
+    }
+
+    /// Fetches the on-chain statuses of multiple orders concurrently.
+    pub async fn statuses(
+        orders: &[ExtendedEthFlowOrder],
+        contracts: &Contracts,
+    ) -> Vec<EthFlowOrderOnchainStatus> {
+        ethcontract::futures::future::join_all(orders.iter().map(|order| order.status(contracts)))
+            .await
     }
 
     pub async fn status(&self, contracts: &Contracts) -> EthFlowOrderOnchainStatus {
@@ -596,6 +1008,19 @@ impl ExtendedEthFlowOrder {
             .into()
     }
 
+    /// Whether the order can be refunded at the current block, i.e. it was
+    /// created on-chain, hasn't been invalidated yet and has expired.
+    pub async fn is_refundable(&self, contracts: &Contracts, web3: &Web3) -> bool {
+        let is_created = matches!(
+            self.status(contracts).await,
+            EthFlowOrderOnchainStatus::Created(..)
+        );
+        let block_timestamp = timestamp_of_current_block_in_seconds(web3)
+            .await
+            .expect("Couldn't fetch current block timestamp");
+        is_created && block_timestamp > self.0.valid_to
+    }
+
     pub async fn is_valid_cowswap_signature(
         &self,
         cowswap_signature: &Signature,
@@ -624,6 +1049,36 @@ impl ExtendedEthFlowOrder {
             .map_err(|err| anyhow::anyhow!("failed signature verification: {:?}", err))
     }
 
+    /// Returns the address of the eth-flow deployment among `candidates` that
+    /// validates the EIP-1271 signature of the order, i.e. the contract that
+    /// signed it. Fails if none of the candidates validate the signature.
+    pub async fn recover_signer(
+        &self,
+        cowswap_signature: &Signature,
+        candidates: &[CoWSwapEthFlow],
+        contracts: &Contracts,
+    ) -> anyhow::Result<H160> {
+        let bytes = match cowswap_signature {
+            Signature::Eip1271(bytes) => bytes,
+            _ => bail!(
+                "Invalid signature type, expected EIP1271, found {:?}",
+                cowswap_signature
+            ),
+        };
+
+        let hash = Bytes(self.hash(contracts).await.to_fixed_bytes());
+        for ethflow in candidates {
+            let result = ethflow
+                .is_valid_signature(hash, Bytes(bytes.clone()))
+                .call()
+                .await?;
+            if check_erc1271_result(result).is_ok() {
+                return Ok(ethflow.address());
+            }
+        }
+        bail!("none of the eth-flow contracts validates the signature")
+    }
+
     pub async fn mine_order_creation(
         &self,
         owner: &Account,
@@ -644,41 +1099,94 @@ impl ExtendedEthFlowOrder {
         tx!(sender, ethflow.invalidate_order(self.0.encode()))
     }
 
-    async fn hash(&self, contracts: &Contracts) -> H256 {
-        let domain_separator = DomainSeparator(
-            contracts
-                .gp_settlement
-                .domain_separator()
-                .call()
-                .await
-                .expect("Couldn't query domain separator")
-                .0,
+    /// Asserts that the invalidation mined in the latest block refunded the
+    /// order's sell and fee amounts to the owner. Since the owner may have paid
+    /// for the invalidation transaction, the balance is allowed to be short of
+    /// the refund by a small amount to account for gas costs.
+    pub async fn assert_refunded(&self, owner: H160, web3: &Web3) {
+        const GAS_TOLERANCE: u64 = 10_000_000_000_000_000; // 0.01 ETH
+
+        let block = web3
+            .eth()
+            .block_number()
+            .await
+            .expect("Couldn't fetch current block number");
+        let balance_at = |block| web3.eth().balance(owner, Some(BlockNumber::Number(block)));
+        let before = balance_at(block - 1).await.unwrap();
+        let after = balance_at(block).await.unwrap();
+
+        let refund = self.0.sell_amount + self.0.fee_amount;
+        assert!(
+            after <= before + refund,
+            "owner balance increased by more than the refund: {before} -> {after}, refund {refund}",
+        );
+        assert!(
+            after + U256::from(GAS_TOLERANCE) >= before + refund,
+            "owner was not refunded: {before} -> {after}, refund {refund}",
         );
+    }
+
+    async fn hash(&self, contracts: &Contracts) -> H256 {
+        self.hash_with_domain(
+            &domain_separator(contracts).await,
+            &contracts.ethflow,
+            &contracts.weth,
+        )
+    }
+
+    /// Computes the order hash with a precomputed domain separator, which
+    /// doesn't require querying a node.
+    pub fn hash_with_domain(
+        &self,
+        domain_separator: &DomainSeparator,
+        ethflow: &CoWSwapEthFlow,
+        weth: &WETH9,
+    ) -> H256 {
         H256(hashed_eip712_message(
-            &domain_separator,
-            &self
-                .to_cow_swap_order(&contracts.ethflow, &contracts.weth)
-                .data
-                .hash_struct(),
+            domain_separator,
+            &self.to_cow_swap_order(ethflow, weth).data.hash_struct(),
         ))
     }
 
     pub async fn uid(&self, contracts: &Contracts) -> OrderUid {
-        let domain_separator = DomainSeparator(
-            contracts
-                .gp_settlement
-                .domain_separator()
-                .call()
-                .await
-                .expect("Couldn't query domain separator")
-                .0,
-        );
+        let domain_separator = domain_separator(contracts).await;
         self.to_cow_swap_order(&contracts.ethflow, &contracts.weth)
             .data
             .uid(&domain_separator, &contracts.ethflow.address())
     }
 }
 
+lazy_static! {
+    static ref DOMAIN_SEPARATORS: Mutex<HashMap<H160, DomainSeparator>> = Default::default();
+}
+
+/// Returns the domain separator of the settlement contract, only querying it
+/// the first time it is needed. The separator only depends on the chain and
+/// the contract address, so it can be cached by address even across
+/// redeployments.
+async fn domain_separator(contracts: &Contracts) -> DomainSeparator {
+    let settlement = contracts.gp_settlement.address();
+    let cached = DOMAIN_SEPARATORS.lock().unwrap().get(&settlement).copied();
+    if let Some(domain_separator) = cached {
+        return domain_separator;
+    }
+
+    let domain_separator = DomainSeparator(
+        contracts
+            .gp_settlement
+            .domain_separator()
+            .call()
+            .await
+            .expect("Couldn't query domain separator")
+            .0,
+    );
+    DOMAIN_SEPARATORS
+        .lock()
+        .unwrap()
+        .insert(settlement, domain_separator);
+    domain_separator
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum EthFlowOrderOnchainStatus {
     Invalidated,
@@ -696,10 +1204,19 @@ impl From<(H160, u32)> for EthFlowOrderOnchainStatus {
     }
 }
 
+/// The amount of an eth-flow trade intent.
+enum EthFlowTradeAmount {
+    /// Sell exactly this amount of ETH.
+    Sell(U256),
+    /// Buy exactly this amount of the buy token with ETH.
+    Buy(U256),
+}
+
 struct EthFlowTradeIntent {
-    sell_amount: U256,
+    amount: EthFlowTradeAmount,
     buy_token: H160,
     receiver: H160,
+    partially_fillable: bool,
 }
 
 impl EthFlowTradeIntent {
@@ -717,15 +1234,91 @@ impl EthFlowTradeIntent {
                 onchain_order: true,
                 verification_gas_limit: 0,
             },
-            side: OrderQuoteSide::Sell {
-                sell_amount: model::quote::SellAmount::AfterFee {
-                    value: self.sell_amount,
+            side: match self.amount {
+                EthFlowTradeAmount::Sell(sell_amount) => OrderQuoteSide::Sell {
+                    sell_amount: model::quote::SellAmount::AfterFee { value: sell_amount },
+                },
+                EthFlowTradeAmount::Buy(buy_amount) => OrderQuoteSide::Buy {
+                    buy_amount_after_fee: buy_amount,
                 },
             },
             buy_token_balance: BuyTokenDestination::Erc20,
             sell_token_balance: SellTokenSource::Erc20,
-            partially_fillable: false,
+            partially_fillable: self.partially_fillable,
             price_quality: PriceQuality::Optimal,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(buy_amount: U256) -> ExtendedEthFlowOrder {
+        ExtendedEthFlowOrder(EthflowOrder {
+            buy_token: H160([1; 20]),
+            receiver: H160([2; 20]),
+            sell_amount: to_wei(1),
+            buy_amount,
+            app_data: Bytes([0; 32]),
+            fee_amount: 0.into(),
+            valid_to: u32::MAX,
+            partially_fillable: false,
+            quote_id: 0,
+        })
+    }
+
+    #[test]
+    fn hash_with_domain_matches_uid() {
+        let ethflow = shared::dummy_contract!(CoWSwapEthFlow, [0xe1; 20]);
+        let weth = shared::dummy_contract!(WETH9, [0xe2; 20]);
+        let domain_separator = DomainSeparator([0xd5; 32]);
+        let order = order(10_000.into());
+
+        let hash = order.hash_with_domain(&domain_separator, &ethflow, &weth);
+        let uid = order
+            .to_cow_swap_order(&ethflow, &weth)
+            .data
+            .uid(&domain_separator, &ethflow.address());
+
+        assert_eq!(&hash.0[..], &uid.0[..32]);
+        assert_ne!(
+            hash,
+            order.hash_with_domain(&DomainSeparator([0xd6; 32]), &ethflow, &weth)
+        );
+    }
+
+    #[test]
+    fn slippage_reduces_buy_amount() {
+        let order = order(10_000.into()).include_slippage_bps(300).unwrap();
+        assert_eq!(order.0.buy_amount, 9_700.into());
+    }
+
+    #[test]
+    fn slippage_to_zero_buy_amount_is_rejected() {
+        assert!(order(10.into()).include_slippage_bps(9_500).is_err());
+    }
+
+    #[test]
+    fn slippage_floor_dominates_for_small_orders() {
+        let order = order(10_000.into())
+            .include_slippage_with_floor(300, 1_000.into())
+            .unwrap();
+        assert_eq!(order.0.buy_amount, 9_000.into());
+    }
+
+    #[test]
+    fn relative_slippage_dominates_for_large_orders() {
+        let order = order(1_000_000.into())
+            .include_slippage_with_floor(300, 1_000.into())
+            .unwrap();
+        assert_eq!(order.0.buy_amount, 970_000.into());
+    }
+
+    #[test]
+    fn slippage_floor_larger_than_buy_amount_is_rejected() {
+        assert!(order(10.into())
+            .include_slippage_with_floor(300, 1_000.into())
+            .is_err());
+    }
+}
@@ -108,8 +108,9 @@ async fn refunder_tx(web3: Web3) {
     let valid_to = timestamp_of_current_block_in_seconds(&web3).await.unwrap() + validity_duration;
     // Accounting for slippage is necessary for the order to be picked up by the
     // refunder
-    let ethflow_order =
-        ExtendedEthFlowOrder::from_quote(&quote_response, valid_to).include_slippage_bps(9999);
+    let ethflow_order = ExtendedEthFlowOrder::from_quote(&quote_response, valid_to)
+        .include_slippage_bps(9999)
+        .unwrap();
 
     ethflow_order
         .mine_order_creation(&user, &contracts.ethflow)
@@ -134,6 +135,7 @@ async fn refunder_tx(web3: Web3) {
     wait_for_condition(Duration::from_secs(10), order_exists)
         .await
         .unwrap();
+    assert!(!ethflow_order.is_refundable(&contracts, &web3).await);
 
     let time_after_expiration = valid_to as i64 + 60;
     web3.api::<TestNodeApi<_>>()
@@ -148,12 +150,13 @@ async fn refunder_tx(web3: Web3) {
         .mine_pending_block()
         .await
         .expect("Unable to mine next block");
+    assert!(ethflow_order.is_refundable(&contracts, &web3).await);
 
     // Create the refund service and execute the refund tx
     let pg_pool = PgPool::connect_lazy("postgresql://").expect("failed to create database");
     let mut refunder = RefundService::new(
         pg_pool,
-        web3,
+        web3.clone(),
         contracts.ethflow.clone(),
         validity_duration as i64 / 2,
         10u64,
@@ -171,6 +174,7 @@ async fn refunder_tx(web3: Web3) {
         ethflow_order.status(&contracts).await,
         EthFlowOrderOnchainStatus::Invalidated
     );
+    assert!(!ethflow_order.is_refundable(&contracts, &web3).await);
 
     tracing::info!("Waiting for autopilot to index refund tx hash.");
     let has_tx_hash = || async {
@@ -3,7 +3,9 @@ mod cancel_orders;
 mod get_auction;
 mod get_native_price;
 mod get_order_by_uid;
+mod get_orders_by_app_data;
 mod get_orders_by_tx;
+mod get_solvable_order_count;
 mod get_solver_competition;
 mod get_trades;
 mod get_user_orders;
@@ -66,11 +68,19 @@ pub fn handle_all_routes(
             "v1/get_user_orders",
             get_user_orders::get_user_orders(orderbook.clone()).boxed(),
         ),
+        (
+            "v1/get_orders_by_app_data",
+            get_orders_by_app_data::get_orders_by_app_data(orderbook.clone()).boxed(),
+        ),
         (
             "v1/get_orders_by_tx",
             get_orders_by_tx::get_orders_by_tx(orderbook.clone()).boxed(),
         ),
         ("v1/post_quote", post_quote::post_quote(quotes).boxed()),
+        (
+            "v1/solvable_order_count",
+            get_solvable_order_count::get_solvable_order_count(orderbook.clone()).boxed(),
+        ),
         ("v1/auction", get_auction::get_auction(orderbook).boxed()),
         (
             "v1/solver_competition",
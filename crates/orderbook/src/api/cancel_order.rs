@@ -1,8 +1,10 @@
 use {
     crate::orderbook::{OrderCancellationError, Orderbook},
     anyhow::Result,
+    chrono::{DateTime, Utc},
     model::order::{CancellationPayload, OrderCancellation, OrderUid},
-    shared::api::{convert_json_response, extract_payload, IntoWarpReply},
+    serde::Serialize,
+    shared::api::{extract_payload, IntoWarpReply},
     std::{convert::Infallible, sync::Arc},
     warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
 };
@@ -61,17 +63,35 @@ impl IntoWarpReply for OrderCancellationError {
     }
 }
 
-pub fn cancel_order_response(result: Result<(), OrderCancellationError>) -> super::ApiReply {
-    convert_json_response(result.map(|_| "Cancelled"))
+/// Confirms a successful order cancellation.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancellationResponse {
+    pub uid: OrderUid,
+    /// When the order was cancelled according to the server.
+    pub cancelled_at: DateTime<Utc>,
+}
+
+pub fn cancel_order_response(
+    result: Result<CancellationResponse, OrderCancellationError>,
+) -> super::ApiReply {
+    match result {
+        Ok(response) => with_status(warp::reply::json(&response), StatusCode::OK),
+        Err(err) => err.into_warp_reply(),
+    }
 }
 
 pub fn cancel_order(
     orderbook: Arc<Orderbook>,
 ) -> impl Filter<Extract = (super::ApiReply,), Error = Rejection> + Clone {
-    cancel_order_request().and_then(move |order| {
+    cancel_order_request().and_then(move |order: OrderCancellation| {
         let orderbook = orderbook.clone();
         async move {
-            let result = orderbook.cancel_order(order).await;
+            let uid = order.order_uid;
+            let result = orderbook
+                .cancel_order(order)
+                .await
+                .map(|cancelled_at| CancellationResponse { uid, cancelled_at });
             Result::<_, Infallible>::Ok(cancel_order_response(result))
         }
     })
@@ -85,6 +105,7 @@ mod tests {
         hex_literal::hex,
         model::signature::{EcdsaSignature, EcdsaSigningScheme},
         serde_json::json,
+        shared::api::response_body,
         warp::{test::request, Reply},
     };
 
@@ -131,10 +152,25 @@ mod tests {
         assert_eq!(result, cancellation);
     }
 
-    #[test]
-    fn cancel_order_response_ok() {
-        let response = cancel_order_response(Ok(())).into_response();
+    #[tokio::test]
+    async fn cancel_order_response_ok() {
+        let response = cancel_order_response(Ok(CancellationResponse {
+            uid: OrderUid([1; 56]),
+            cancelled_at: DateTime::parse_from_rfc3339("2023-01-02T03:04:05Z")
+                .unwrap()
+                .into(),
+        }))
+        .into_response();
         assert_eq!(response.status(), StatusCode::OK);
+        let body = response_body(response).await;
+        let body: serde_json::Value = serde_json::from_slice(body.as_slice()).unwrap();
+        assert_eq!(
+            body,
+            json!({
+                "uid": OrderUid([1; 56]),
+                "cancelledAt": "2023-01-02T03:04:05Z",
+            })
+        );
     }
 
     #[test]
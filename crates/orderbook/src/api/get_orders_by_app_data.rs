@@ -0,0 +1,93 @@
+use {
+    crate::orderbook::Orderbook,
+    anyhow::Result,
+    model::app_id::AppId,
+    serde::Deserialize,
+    shared::api::ApiReply,
+    std::{convert::Infallible, sync::Arc},
+    warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
+};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Query {
+    app_data: AppId,
+    offset: Option<u64>,
+    limit: Option<u64>,
+}
+
+fn request() -> impl Filter<Extract = (Query,), Error = Rejection> + Clone {
+    warp::path!("v1" / "orders")
+        .and(warp::get())
+        .and(warp::query::<Query>())
+}
+
+pub fn get_orders_by_app_data(
+    orderbook: Arc<Orderbook>,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move |query: Query| {
+        let orderbook = orderbook.clone();
+        async move {
+            const DEFAULT_OFFSET: u64 = 0;
+            const DEFAULT_LIMIT: u64 = 10;
+            const MIN_LIMIT: u64 = 1;
+            const MAX_LIMIT: u64 = 1000;
+            let offset = query.offset.unwrap_or(DEFAULT_OFFSET);
+            let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+            if !(MIN_LIMIT..=MAX_LIMIT).contains(&limit) {
+                return Ok(with_status(
+                    super::error(
+                        "LIMIT_OUT_OF_BOUNDS",
+                        format!("The pagination limit is [{MIN_LIMIT},{MAX_LIMIT}]."),
+                    ),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+            let result = orderbook
+                .orders_by_app_data(&query.app_data, offset, limit)
+                .await;
+            Result::<_, Infallible>::Ok(match result {
+                Ok(reply) => with_status(warp::reply::json(&reply), StatusCode::OK),
+                Err(err) => {
+                    tracing::error!(?err, "get_orders_by_app_data");
+                    shared::api::internal_error_reply()
+                }
+            })
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn request_() {
+        let app_data = "0x0000000000000000000000000000000000000000000000000000000000000001";
+        let result = warp::test::request()
+            .path(&format!("/v1/orders?appData={app_data}"))
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(result.app_data, app_data.parse::<AppId>().unwrap());
+        assert_eq!(result.offset, None);
+        assert_eq!(result.limit, None);
+
+        let result = warp::test::request()
+            .path(&format!("/v1/orders?appData={app_data}&offset=1&limit=2"))
+            .method("GET")
+            .filter(&request())
+            .await
+            .unwrap();
+        assert_eq!(result.offset, Some(1));
+        assert_eq!(result.limit, Some(2));
+
+        let result = warp::test::request()
+            .path("/v1/orders")
+            .method("GET")
+            .filter(&request())
+            .await;
+        assert!(result.is_err());
+    }
+}
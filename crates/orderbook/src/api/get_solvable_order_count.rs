@@ -0,0 +1,43 @@
+use {
+    crate::orderbook::Orderbook,
+    anyhow::Result,
+    reqwest::StatusCode,
+    serde::Serialize,
+    shared::api::ApiReply,
+    std::{convert::Infallible, sync::Arc},
+    warp::{reply::with_status, Filter, Rejection},
+};
+
+#[derive(Debug, Serialize)]
+struct Response {
+    count: usize,
+}
+
+fn request() -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path!("v1" / "solvable_orders" / "count").and(warp::get())
+}
+
+pub fn get_solvable_order_count(
+    orderbook: Arc<Orderbook>,
+) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
+    request().and_then(move || {
+        let orderbook = orderbook.clone();
+        async move {
+            let result = orderbook.solvable_order_count().await;
+            let reply = match result {
+                Ok(Some(count)) => {
+                    with_status(warp::reply::json(&Response { count }), StatusCode::OK)
+                }
+                Ok(None) => with_status(
+                    super::error("NotFound", "There is no active auction"),
+                    StatusCode::NOT_FOUND,
+                ),
+                Err(err) => {
+                    tracing::error!(?err, "/api/v1/solvable_orders/count");
+                    shared::api::internal_error_reply()
+                }
+            };
+            Result::<_, Infallible>::Ok(reply)
+        }
+    })
+}
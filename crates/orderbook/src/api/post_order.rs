@@ -5,6 +5,7 @@ use {
         order::{OrderCreation, OrderUid},
         quote::QuoteId,
     },
+    serde::Serialize,
     shared::{
         api::{error, extract_payload, ApiReply, IntoWarpReply},
         order_validation::{OrderValidToError, PartialValidationError, ValidationError},
@@ -13,9 +14,31 @@ use {
     warp::{hyper::StatusCode, reply::with_status, Filter, Rejection},
 };
 
-pub fn create_order_request() -> impl Filter<Extract = (OrderCreation,), Error = Rejection> + Clone
-{
+/// The shape of a successful order creation response. Which one is used
+/// depends on the API version of the request so that existing clients keep
+/// receiving the bare uid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ResponseFormat {
+    /// `POST /v1/orders` responds with the order uid.
+    Uid,
+    /// `POST /v1.1/orders` responds with a [`CreateOrderResponse`].
+    UidAndQuoteId,
+}
+
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateOrderResponse {
+    pub uid: OrderUid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<QuoteId>,
+}
+
+pub fn create_order_request(
+) -> impl Filter<Extract = (ResponseFormat, OrderCreation), Error = Rejection> + Clone {
     warp::path!("v1" / "orders")
+        .map(|| ResponseFormat::Uid)
+        .or(warp::path!("v1.1" / "orders").map(|| ResponseFormat::UidAndQuoteId))
+        .unify()
         .and(warp::post())
         .and(extract_payload())
 }
@@ -157,10 +180,13 @@ impl IntoWarpReply for ValidationErrorWrapper {
                 ),
                 StatusCode::INTERNAL_SERVER_ERROR,
             ),
-            ValidationError::TransferSimulationFailed => with_status(
+            ValidationError::TransferSimulationFailed(reason) => with_status(
                 error(
                     "TransferSimulationFailed",
-                    "sell token cannot be transferred",
+                    match reason {
+                        Some(reason) => format!("transfer simulation reverted: {reason}"),
+                        None => "sell token cannot be transferred".to_owned(),
+                    },
                 ),
                 StatusCode::BAD_REQUEST,
             ),
@@ -205,17 +231,24 @@ impl IntoWarpReply for AddOrderError {
 
 pub fn create_order_response(
     result: Result<(OrderUid, Option<QuoteId>), AddOrderError>,
+    format: ResponseFormat,
 ) -> ApiReply {
-    match result {
-        Ok((uid, _)) => with_status(warp::reply::json(&uid), StatusCode::CREATED),
-        Err(err) => err.into_warp_reply(),
+    match (result, format) {
+        (Ok((uid, _)), ResponseFormat::Uid) => {
+            with_status(warp::reply::json(&uid), StatusCode::CREATED)
+        }
+        (Ok((uid, quote_id)), ResponseFormat::UidAndQuoteId) => with_status(
+            warp::reply::json(&CreateOrderResponse { uid, quote_id }),
+            StatusCode::CREATED,
+        ),
+        (Err(err), _) => err.into_warp_reply(),
     }
 }
 
 pub fn post_order(
     orderbook: Arc<Orderbook>,
 ) -> impl Filter<Extract = (ApiReply,), Error = Rejection> + Clone {
-    create_order_request().and_then(move |order: OrderCreation| {
+    create_order_request().and_then(move |format, order: OrderCreation| {
         let orderbook = orderbook.clone();
         async move {
             let result = orderbook.add_order(order.clone()).await;
@@ -226,7 +259,7 @@ pub fn post_order(
                 Err(err) => tracing::debug!(?order, ?err, "error creating order"),
             }
 
-            Result::<_, Infallible>::Ok(create_order_response(result))
+            Result::<_, Infallible>::Ok(create_order_response(result, format))
         }
     })
 }
@@ -251,13 +284,22 @@ mod tests {
             .header("content-type", "application/json")
             .json(&order_payload);
         let result = request.filter(&filter).await.unwrap();
-        assert_eq!(result, order_payload);
+        assert_eq!(result, (ResponseFormat::Uid, order_payload.clone()));
+
+        let request = request()
+            .path("/v1.1/orders")
+            .method("POST")
+            .header("content-type", "application/json")
+            .json(&order_payload);
+        let result = request.filter(&filter).await.unwrap();
+        assert_eq!(result, (ResponseFormat::UidAndQuoteId, order_payload));
     }
 
     #[tokio::test]
     async fn create_order_response_created() {
         let uid = OrderUid([1u8; 56]);
-        let response = create_order_response(Ok((uid, Some(42)))).into_response();
+        let response =
+            create_order_response(Ok((uid, Some(42))), ResponseFormat::Uid).into_response();
         assert_eq!(response.status(), StatusCode::CREATED);
         let body = response_body(response).await;
         let body: serde_json::Value = serde_json::from_slice(body.as_slice()).unwrap();
@@ -267,9 +309,52 @@ mod tests {
         assert_eq!(body, expected);
     }
 
+    #[tokio::test]
+    async fn create_order_response_with_quote_id() {
+        let uid = OrderUid([1u8; 56]);
+        let body = |quote_id| async move {
+            let response =
+                create_order_response(Ok((uid, quote_id)), ResponseFormat::UidAndQuoteId)
+                    .into_response();
+            assert_eq!(response.status(), StatusCode::CREATED);
+            let body = response_body(response).await;
+            serde_json::from_slice::<serde_json::Value>(body.as_slice()).unwrap()
+        };
+
+        assert_eq!(body(Some(42)).await, json!({"uid": uid, "quoteId": 42}));
+        assert_eq!(body(None).await, json!({ "uid": uid }));
+    }
+
+    #[tokio::test]
+    async fn transfer_simulation_failure_reason() {
+        let description = |reason: Option<&str>| async move {
+            let response = ValidationErrorWrapper(ValidationError::TransferSimulationFailed(
+                reason.map(str::to_owned),
+            ))
+            .into_warp_reply()
+            .into_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+            let body = response_body(response).await;
+            let body: serde_json::Value = serde_json::from_slice(body.as_slice()).unwrap();
+            body["description"].as_str().unwrap().to_owned()
+        };
+
+        assert_eq!(
+            description(Some("ERC20: transfer amount exceeds balance")).await,
+            "transfer simulation reverted: ERC20: transfer amount exceeds balance"
+        );
+        assert_eq!(
+            description(Some("Pausable: paused")).await,
+            "transfer simulation reverted: Pausable: paused"
+        );
+        assert_eq!(description(None).await, "sell token cannot be transferred");
+    }
+
     #[tokio::test]
     async fn create_order_response_duplicate() {
-        let response = create_order_response(Err(AddOrderError::DuplicatedOrder)).into_response();
+        let response =
+            create_order_response(Err(AddOrderError::DuplicatedOrder), ResponseFormat::Uid)
+                .into_response();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
         let body = response_body(response).await;
         let body: serde_json::Value = serde_json::from_slice(body.as_slice()).unwrap();
@@ -19,4 +19,15 @@ impl super::Postgres {
         let auction = AuctionWithId { id, auction };
         Ok(Some(auction))
     }
+
+    pub async fn most_recent_auction_order_count(&self) -> Result<Option<usize>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["most_recent_auction_order_count"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        let count = database::auction::most_recent_order_count(&mut ex).await?;
+        Ok(count.map(usize::try_from).transpose()?)
+    }
 }
@@ -74,6 +74,14 @@ pub trait OrderStoring: Send + Sync {
         offset: u64,
         limit: Option<u64>,
     ) -> Result<Vec<Order>>;
+    /// All orders with the specified app data ordered by creation date
+    /// descending (newest orders first).
+    async fn app_data_orders(
+        &self,
+        app_data: &AppId,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<Order>>;
 }
 
 pub struct SolvableOrders {
@@ -308,6 +316,32 @@ impl OrderStoring for Postgres {
         .try_collect()
         .await
     }
+
+    async fn app_data_orders(
+        &self,
+        app_data: &AppId,
+        offset: u64,
+        limit: Option<u64>,
+    ) -> Result<Vec<Order>> {
+        let _timer = super::Metrics::get()
+            .database_queries
+            .with_label_values(&["app_data_orders"])
+            .start_timer();
+
+        let mut ex = self.pool.acquire().await?;
+        database::orders::app_data_orders(
+            &mut ex,
+            &ByteArray(app_data.0),
+            offset as i64,
+            limit.map(|l| l as i64),
+        )
+        .map(|result| match result {
+            Ok(order) => full_order_into_model_order(order),
+            Err(err) => Err(anyhow::Error::from(err)),
+        })
+        .try_collect()
+        .await
+    }
 }
 
 #[async_trait]
@@ -916,4 +950,53 @@ mod tests {
         assert_eq!(order_status(2).await, OrderStatus::Cancelled);
         assert_eq!(order_status(3).await, OrderStatus::Open);
     }
+
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_app_data_orders() {
+        let db = Postgres::new("postgresql://").unwrap();
+        database::clear_DANGER(&db.pool).await.unwrap();
+
+        let now = Utc::now();
+        let order = |byte: u8, app_data: AppId| Order {
+            data: OrderData {
+                app_data,
+                ..Default::default()
+            },
+            metadata: OrderMetadata {
+                uid: OrderUid([byte; 56]),
+                creation_date: now + Duration::seconds(byte as i64),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let uids = |orders: Vec<Order>| {
+            orders
+                .into_iter()
+                .map(|order| order.metadata.uid)
+                .collect::<Vec<_>>()
+        };
+
+        db.insert_order(&order(1, AppId([1; 32])), None)
+            .await
+            .unwrap();
+        db.insert_order(&order(2, AppId([1; 32])), None)
+            .await
+            .unwrap();
+        db.insert_order(&order(3, AppId([2; 32])), None)
+            .await
+            .unwrap();
+
+        let orders = db.app_data_orders(&AppId([3; 32]), 0, None).await.unwrap();
+        assert!(orders.is_empty());
+
+        let orders = db.app_data_orders(&AppId([1; 32]), 0, None).await.unwrap();
+        assert_eq!(uids(orders), [OrderUid([2; 56]), OrderUid([1; 56])]);
+
+        let orders = db
+            .app_data_orders(&AppId([1; 32]), 1, Some(1))
+            .await
+            .unwrap();
+        assert_eq!(uids(orders), [OrderUid([1; 56])]);
+    }
 }
@@ -1,9 +1,10 @@
 use {
     crate::database::orders::{InsertionError, OrderStoring},
     anyhow::{Context, Result},
-    chrono::Utc,
+    chrono::{DateTime, Utc},
     ethcontract::H256,
     model::{
+        app_id::AppId,
         auction::AuctionWithId,
         order::{
             Order,
@@ -251,10 +252,11 @@ impl Orderbook {
         Ok(())
     }
 
+    /// Cancels an order and returns the time of the cancellation.
     pub async fn cancel_order(
         &self,
         cancellation: OrderCancellation,
-    ) -> Result<(), OrderCancellationError> {
+    ) -> Result<DateTime<Utc>, OrderCancellationError> {
         let order = self
             .find_order_for_cancellation(&cancellation.order_uid)
             .await?;
@@ -269,14 +271,13 @@ impl Orderbook {
 
         // order is already known to exist in DB at this point, and signer is
         // known to be correct!
-        self.database
-            .cancel_order(&order.metadata.uid, Utc::now())
-            .await?;
+        let now = Utc::now();
+        self.database.cancel_order(&order.metadata.uid, now).await?;
 
         tracing::debug!(order_uid =% order.metadata.uid, "order cancelled");
         Metrics::on_order_operation(&order, OrderOperation::Cancelled);
 
-        Ok(())
+        Ok(now)
     }
 
     pub async fn replace_order(
@@ -344,6 +345,12 @@ impl Orderbook {
         Ok(Some(auction))
     }
 
+    /// The number of orders in the current auction. The orders are counted
+    /// in the database, so the auction doesn't need to be deserialized.
+    pub async fn solvable_order_count(&self) -> Result<Option<usize>> {
+        self.database.most_recent_auction_order_count().await
+    }
+
     pub async fn get_user_orders(
         &self,
         owner: &H160,
@@ -355,6 +362,18 @@ impl Orderbook {
             .await
             .context("get_user_orders error")
     }
+
+    pub async fn orders_by_app_data(
+        &self,
+        app_data: &AppId,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<Order>> {
+        self.database
+            .app_data_orders(app_data, offset, Some(limit))
+            .await
+            .context("orders_by_app_data error")
+    }
 }
 
 #[async_trait::async_trait]
@@ -372,13 +391,37 @@ mod tests {
         ethcontract::H160,
         mockall::predicate::eq,
         model::{
-            app_id::AppId,
+            auction::Auction,
             order::{OrderData, OrderMetadata},
             signature::Signature,
         },
         shared::order_validation::MockOrderValidating,
     };
 
+    #[tokio::test]
+    #[ignore]
+    async fn postgres_solvable_order_count() {
+        let database = crate::database::Postgres::new("postgresql://").unwrap();
+        database::clear_DANGER(&database.pool).await.unwrap();
+        let orderbook = Orderbook {
+            database,
+            order_validator: Arc::new(MockOrderValidating::new()),
+            domain_separator: Default::default(),
+            settlement_contract: Default::default(),
+        };
+        assert_eq!(orderbook.solvable_order_count().await.unwrap(), None);
+
+        let auction = Auction {
+            orders: vec![Default::default(); 3],
+            ..Default::default()
+        };
+        let mut ex = orderbook.database.pool.acquire().await.unwrap();
+        database::auction::save(&mut ex, &serde_json::to_value(&auction).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(orderbook.solvable_order_count().await.unwrap(), Some(3));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn postgres_replace_order_verifies_signer_and_app_data() {
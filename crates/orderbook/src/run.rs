@@ -67,6 +67,9 @@ use {
 };
 
 pub async fn run(args: Arguments) {
+    args.order_quoting
+        .validate()
+        .expect("invalid order quoting arguments");
     let http_factory = HttpClientFactory::new(&args.http_client);
 
     let web3 = shared::ethrpc::web3(
@@ -149,11 +152,26 @@ pub async fn run(args: Arguments) {
             &http_factory,
             &web3,
             args.shared.gas_estimators.as_slice(),
+            args.shared.gas_estimation_mode,
             args.shared.blocknative_api_key.clone(),
         )
         .await
         .expect("failed to create gas price estimator"),
     ));
+    let native_gas_price_estimator = match args.shared.native_price_gas_estimator {
+        Some(estimator) => Some(Arc::new(
+            shared::gas_price_estimation::create_priority_estimator(
+                &http_factory,
+                &web3,
+                &[estimator],
+                Default::default(),
+                args.shared.blocknative_api_key.clone(),
+            )
+            .await
+            .expect("failed to create native price gas estimator"),
+        )),
+        None => None,
+    };
 
     let baseline_sources = args.shared.baseline_sources.clone().unwrap_or_else(|| {
         sources::defaults_for_chain(chain_id).expect("failed to get default baseline sources")
@@ -178,7 +196,7 @@ pub async fn run(args: Arguments) {
 
     let base_tokens = Arc::new(BaseTokens::new(
         native_token.address(),
-        &args.shared.base_tokens,
+        &args.shared.effective_base_tokens(),
     ));
     let mut allowed_tokens = args.allowed_tokens.clone();
     allowed_tokens.extend(base_tokens.tokens().iter().copied());
@@ -352,6 +370,7 @@ pub async fn run(args: Arguments) {
             uniswap_v3_pools: uniswap_v3_pool_fetcher.clone().map(|a| a as _),
             tokens: token_info_fetcher.clone(),
             gas_price: gas_price_estimator.clone(),
+            native_gas_price: native_gas_price_estimator.map(|a| a as _),
             zeroex: zeroex_api.clone(),
             oneinch: one_inch_api.ok().map(|a| a as _),
         },
@@ -362,6 +381,7 @@ pub async fn run(args: Arguments) {
         .price_estimator(
             &args.order_quoting.price_estimators,
             &args.order_quoting.price_estimation_drivers,
+            args.order_quoting.max_price_estimation_drivers,
         )
         .unwrap();
     let fast_price_estimator = price_estimator_factory
@@ -369,12 +389,14 @@ pub async fn run(args: Arguments) {
             &args.order_quoting.price_estimators,
             args.fast_price_estimation_results_required,
             &args.order_quoting.price_estimation_drivers,
+            args.order_quoting.max_price_estimation_drivers,
         )
         .unwrap();
     let native_price_estimator = price_estimator_factory
         .native_price_estimator(
             &args.native_price_estimators,
             &args.order_quoting.price_estimation_drivers,
+            args.order_quoting.max_price_estimation_drivers,
         )
         .unwrap();
 
@@ -2,7 +2,7 @@ use {
     crate::ethrpc::{Web3, Web3Transport},
     anyhow::{anyhow, Context, Result},
     contracts::{BalancerV2Vault, ERC20},
-    ethcontract::{batch::CallBatch, Account},
+    ethcontract::{batch::CallBatch, errors::ExecutionError, Account},
     futures::{FutureExt, StreamExt},
     model::order::{Order, SellTokenSource},
     primitive_types::{H160, U256},
@@ -31,7 +31,9 @@ impl Query {
 pub enum TransferSimulationError {
     InsufficientAllowance,
     InsufficientBalance,
-    TransferFailed,
+    /// The transfer failed despite sufficient balance and allowance, with the
+    /// revert reason if it is known.
+    TransferFailed(Option<String>),
     Other(anyhow::Error),
 }
 
@@ -85,7 +87,14 @@ impl Web3BalanceFetcher {
         }
     }
 
-    async fn can_transfer_call(&self, token: H160, from: H160, amount: U256) -> bool {
+    /// Simulates the transfer. Returns the revert reason if it failed and the
+    /// reason is known.
+    async fn transfer_call(
+        &self,
+        token: H160,
+        from: H160,
+        amount: U256,
+    ) -> Result<(), Option<String>> {
         let instance = ERC20::at(&self.web3, token);
         let calldata = instance
             .transfer_from(from, self.settlement_contract, amount)
@@ -99,16 +108,25 @@ impl Web3BalanceFetcher {
             ..Default::default()
         };
         let block = Some(BlockId::Number(BlockNumber::Latest));
-        let response = self.web3.eth().call(call_request, block).await;
-        response
-            .map(|bytes| is_empty_or_truthy(bytes.0.as_slice()))
-            .unwrap_or(false)
+        match self.web3.eth().call(call_request, block).await {
+            Ok(bytes) if is_empty_or_truthy(bytes.0.as_slice()) => Ok(()),
+            Ok(_) => Err(Some("transfer returned false".to_owned())),
+            Err(web3::Error::Rpc(err)) => Err(Some(revert_reason(&err.message))),
+            Err(_) => Err(None),
+        }
     }
 
-    async fn can_manage_user_balance_call(&self, token: H160, from: H160, amount: U256) -> bool {
+    /// Simulates the transfer through the Vault. Returns the revert reason if
+    /// it failed and the reason is known.
+    async fn manage_user_balance_call(
+        &self,
+        token: H160,
+        from: H160,
+        amount: U256,
+    ) -> Result<(), Option<String>> {
         let vault = match self.vault.as_ref() {
             Some(vault) => vault,
-            None => return false,
+            None => return Err(None),
         };
 
         const USER_BALANCE_OP_TRANSFER_EXTERNAL: u8 = 3;
@@ -123,7 +141,10 @@ impl Web3BalanceFetcher {
             .from(Account::Local(from, None))
             .call()
             .await
-            .is_ok()
+            .map_err(|err| match err.inner {
+                ExecutionError::Revert(reason) => reason,
+                _ => None,
+            })
     }
 }
 
@@ -229,9 +250,10 @@ impl BalanceFetching for Web3BalanceFetcher {
                 // In the very likely case that we can transfer we only do one RPC call.
                 // Only do more calls in case we need to closer assess why the transfer is
                 // failing
-                if self.can_transfer_call(token, from, amount).await {
-                    return Ok(());
-                }
+                let reason = match self.transfer_call(token, from, amount).await {
+                    Ok(()) => return Ok(()),
+                    Err(reason) => reason,
+                };
                 let mut batch = CallBatch::new(self.web3.transport().clone());
                 let token = ERC20::at(&self.web3, token);
                 let balance_future =
@@ -245,12 +267,13 @@ impl BalanceFetching for Web3BalanceFetcher {
                 if allowance < amount {
                     return Err(TransferSimulationError::InsufficientAllowance);
                 }
-                return Err(TransferSimulationError::TransferFailed);
+                return Err(TransferSimulationError::TransferFailed(reason));
             }
             (SellTokenSource::External, Some(vault)) => {
-                if self.can_manage_user_balance_call(token, from, amount).await {
-                    return Ok(());
-                }
+                let reason = match self.manage_user_balance_call(token, from, amount).await {
+                    Ok(()) => return Ok(()),
+                    Err(reason) => reason,
+                };
                 let mut batch = CallBatch::new(self.web3.transport().clone());
                 let token = ERC20::at(&self.web3, token);
                 let balance_future = erc20_balance_query(&mut batch, token, from, vault.address());
@@ -263,7 +286,7 @@ impl BalanceFetching for Web3BalanceFetcher {
                 if allowance < amount {
                     return Err(TransferSimulationError::InsufficientAllowance);
                 }
-                return Err(TransferSimulationError::TransferFailed);
+                return Err(TransferSimulationError::TransferFailed(reason));
             }
             (SellTokenSource::External, None) => {
                 return Err(TransferSimulationError::Other(anyhow!(
@@ -279,6 +302,14 @@ impl BalanceFetching for Web3BalanceFetcher {
     }
 }
 
+/// Strips the generic prefix nodes add to the revert reason of a failed call.
+fn revert_reason(message: &str) -> String {
+    message
+        .strip_prefix("execution reverted: ")
+        .unwrap_or(message)
+        .to_owned()
+}
+
 fn is_empty_or_truthy(bytes: &[u8]) -> bool {
     match bytes.len() {
         0 => true,
@@ -6,7 +6,7 @@ use {
         current_block,
         ethrpc,
         fee_subsidy::cow_token::SubsidyTiers,
-        gas_price_estimation::GasEstimatorType,
+        gas_price_estimation::{GasEstimationMode, GasEstimatorType},
         price_estimation::PriceEstimatorType,
         rate_limiter::RateLimitingStrategy,
         sources::{
@@ -22,7 +22,7 @@ use {
     std::{
         collections::HashMap,
         fmt::{self, Display, Formatter},
-        num::{NonZeroU64, ParseFloatError},
+        num::{NonZeroU64, NonZeroUsize, ParseFloatError},
         str::FromStr,
         time::Duration,
     },
@@ -69,6 +69,12 @@ pub struct OrderQuotingArguments {
     #[clap(long, env, use_value_delimiter = true)]
     pub price_estimation_drivers: Vec<Driver>,
 
+    /// The maximum number of `price_estimation_drivers` to query for a quote.
+    /// The queried drivers are rotated between requests so that all of them
+    /// get used. By default all drivers are queried.
+    #[clap(long, env)]
+    pub max_price_estimation_drivers: Option<NonZeroUsize>,
+
     /// The configured addresses whose orders should be considered liquidity and
     /// not regular user orders.
     ///
@@ -150,6 +156,39 @@ pub struct OrderQuotingArguments {
     pub cow_fee_factors: Option<SubsidyTiers>,
 }
 
+impl OrderQuotingArguments {
+    /// Checks that the fee arguments don't contradict each other.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.fee_discount >= 0.,
+            "fee discount {} is negative",
+            self.fee_discount
+        );
+        ensure!(
+            self.min_discounted_fee >= 0.,
+            "minimum discounted fee {} is negative",
+            self.min_discounted_fee
+        );
+        ensure!(
+            self.fee_factor >= 0.,
+            "fee factor {} is negative",
+            self.fee_factor
+        );
+        ensure!(
+            self.fee_factor > 0. || self.min_discounted_fee == 0.,
+            "minimum discounted fee {} has no effect with a zero fee factor",
+            self.min_discounted_fee
+        );
+        for (app_id, factor) in &self.partner_additional_fee_factors {
+            ensure!(
+                *factor >= 0.,
+                "partner fee factor {factor} for {app_id:?} is negative"
+            );
+        }
+        Ok(())
+    }
+}
+
 logging_args_with_default_filter!(
     LoggingArguments,
     "warn,autopilot=debug,driver=debug,orderbook=debug,solver=debug,shared=debug"
@@ -197,6 +236,22 @@ pub struct Arguments {
     )]
     pub gas_estimators: Vec<GasEstimatorType>,
 
+    /// How the estimates of multiple `gas_estimators` are combined. By default
+    /// the first estimator that succeeds is used.
+    #[clap(
+        long,
+        env,
+        default_value = "Sequential",
+        value_enum,
+        ignore_case = true
+    )]
+    pub gas_estimation_mode: GasEstimationMode,
+
+    /// Optional gas estimator to use for native price estimation only,
+    /// instead of the `gas_estimators` which are used for everything else.
+    #[clap(long, env, value_enum, ignore_case = true)]
+    pub native_price_gas_estimator: Option<GasEstimatorType>,
+
     /// BlockNative requires api key to work. Optional since BlockNative could
     /// be skipped in gas estimators.
     #[clap(long, env)]
@@ -207,6 +262,12 @@ pub struct Arguments {
     #[clap(long, env, use_value_delimiter = true)]
     pub base_tokens: Vec<H160>,
 
+    /// Tokens that are never used as base tokens, even if they are listed in
+    /// `--base-tokens`. Allows quickly excluding a problematic token (for
+    /// example a depegged stablecoin) without editing the full list.
+    #[clap(long, env, use_value_delimiter = true)]
+    pub base_token_deny_list: Vec<H160>,
+
     /// Which Liquidity sources to be used by Price Estimator.
     #[clap(long, env, value_enum, ignore_case = true, use_value_delimiter = true)]
     pub baseline_sources: Option<Vec<BaselineSource>>,
@@ -321,6 +382,21 @@ pub struct Arguments {
     pub balancer_v2_vault_address: Option<H160>,
 }
 
+impl Arguments {
+    /// The configured base tokens without the ones on the deny list.
+    pub fn effective_base_tokens(&self) -> Vec<H160> {
+        let (denied, allowed): (Vec<_>, Vec<_>) = self
+            .base_tokens
+            .iter()
+            .copied()
+            .partition(|token| self.base_token_deny_list.contains(token));
+        if !denied.is_empty() {
+            tracing::warn!(?denied, "removed denied tokens from base tokens");
+        }
+        allowed
+    }
+}
+
 pub fn display_secret_option<T>(
     f: &mut Formatter<'_>,
     name: &str,
@@ -387,6 +463,11 @@ impl Display for OrderQuotingArguments {
             "price_estimation_drivers",
             &self.price_estimation_drivers,
         )?;
+        display_option(
+            f,
+            "max_price_estimation_drivers",
+            &self.max_price_estimation_drivers,
+        )?;
         writeln!(
             f,
             "liquidity_order_owners: {:?}",
@@ -395,6 +476,7 @@ impl Display for OrderQuotingArguments {
         Ok(())
     }
 }
+
 // We have a custom Display implementation so that we can log the arguments on
 // start up without leaking any potentially secret values.
 impl Display for Arguments {
@@ -411,8 +493,15 @@ impl Display for Arguments {
         writeln!(f, "node_url: {}", self.node_url)?;
         display_option(f, "chain_id", &self.chain_id)?;
         writeln!(f, "gas_estimators: {:?}", self.gas_estimators)?;
+        writeln!(f, "gas_estimation_mode: {:?}", self.gas_estimation_mode)?;
+        writeln!(
+            f,
+            "native_price_gas_estimator: {:?}",
+            self.native_price_gas_estimator
+        )?;
         display_secret_option(f, "blocknative_api_key", &self.blocknative_api_key)?;
         writeln!(f, "base_tokens: {:?}", self.base_tokens)?;
+        writeln!(f, "base_token_deny_list: {:?}", self.base_token_deny_list)?;
         writeln!(f, "baseline_sources: {:?}", self.baseline_sources)?;
         writeln!(f, "pool_cache_blocks: {}", self.pool_cache_blocks)?;
         writeln!(
@@ -503,21 +592,77 @@ pub fn duration_from_seconds(s: &str) -> Result<Duration, ParseFloatError> {
 }
 
 pub fn wei_from_base_unit(s: &str) -> anyhow::Result<U256> {
-    Ok(U256::from_dec_str(s)? * U256::exp10(18))
+    wei_from_base_unit_with_decimals(s, 18)
+}
+
+/// Converts an amount in base units of a token with the specified number of
+/// decimals to its smallest unit, for example 1 USDC to 1_000_000.
+pub fn wei_from_base_unit_with_decimals(s: &str, decimals: u8) -> anyhow::Result<U256> {
+    U256::from(10)
+        .checked_pow(decimals.into())
+        .and_then(|unit| U256::from_dec_str(s).ok()?.checked_mul(unit))
+        .with_context(|| format!("invalid amount {s:?} with {decimals} decimals"))
+}
+
+/// Converts an amount in gwei to wei without going through floating point
+/// numbers. Amounts with more than 9 decimals are rejected since they can't be
+/// represented in wei.
+pub fn wei_from_gwei_u256(s: &str) -> anyhow::Result<U256> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    ensure!(
+        fraction.len() <= 9,
+        "gwei amount {s:?} has more than 9 decimals"
+    );
+    ensure!(
+        !(integer.is_empty() && fraction.is_empty()),
+        "empty gwei amount"
+    );
+    let digits = format!("{integer}{fraction:0<9}");
+    ensure!(
+        digits.bytes().all(|b| b.is_ascii_digit()),
+        "invalid gwei amount {s:?}"
+    );
+    Ok(U256::from_dec_str(&digits)?)
 }
 
-pub fn wei_from_gwei(s: &str) -> anyhow::Result<f64> {
-    let in_gwei: f64 = s.parse()?;
-    Ok(in_gwei * 1e9)
+/// Converts an amount in wei to gwei for display.
+pub fn gwei_from_wei(wei: U256) -> f64 {
+    wei.to_f64_lossy() / 1e9
+}
+
+impl Driver {
+    /// Parses a list of drivers separated by commas, semicolons or newlines,
+    /// for example from a single blob stored in a secrets manager. Blank
+    /// entries are skipped, so trailing separators are allowed.
+    pub fn parse_many(drivers: &str) -> Result<Vec<Self>> {
+        drivers
+            .split([',', ';', '\n'])
+            .map(str::trim)
+            .filter(|driver| !driver.is_empty())
+            .map(|driver| Ok(Driver::from_str(driver)?))
+            .collect()
+    }
+}
+
+/// An error parsing a [`Driver`] from its `<NAME>|<URL>` representation.
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+pub enum DriverParseError {
+    #[error("not enough arguments for driver, missing URL")]
+    MissingUrl,
+    #[error("invalid driver URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("too many arguments for driver")]
+    TooManyFields,
 }
 
 impl FromStr for Driver {
-    type Err = anyhow::Error;
+    type Err = DriverParseError;
 
-    fn from_str(driver: &str) -> Result<Self> {
-        let (name, url) = driver
-            .split_once('|')
-            .context("not enough arguments for driver")?;
+    fn from_str(driver: &str) -> Result<Self, Self::Err> {
+        let (name, url) = driver.split_once('|').ok_or(DriverParseError::MissingUrl)?;
+        if url.contains('|') {
+            return Err(DriverParseError::TooManyFields);
+        }
         let url: Url = url.parse()?;
         Ok(Driver {
             name: name.to_owned(),
@@ -578,7 +723,11 @@ fn parse_partner_fee_factor(s: &str) -> Result<HashMap<AppId, f64>> {
 
 #[cfg(test)]
 mod test {
-    use {super::*, maplit::hashmap};
+    use {
+        super::*,
+        clap::{CommandFactory, Parser},
+        maplit::hashmap,
+    };
     #[test]
     fn parse_partner_fee_factor_ok() {
         let x = "0x0000000000000000000000000000000000000000000000000000000000000000";
@@ -619,6 +768,190 @@ mod test {
         assert!(parse_partner_fee_factor("").unwrap().is_empty());
     }
 
+    #[test]
+    fn validate_order_quoting_arguments() {
+        let args = |fee_factor: &str, min_discounted_fee: &str| {
+            OrderQuotingArguments::try_parse_from([
+                "".to_owned(),
+                format!("--fee-factor={fee_factor}"),
+                format!("--min-discounted-fee={min_discounted_fee}"),
+            ])
+            .unwrap()
+        };
+
+        assert!(args("1", "0").validate().is_ok());
+        assert!(args("0.5", "0.001").validate().is_ok());
+        assert!(args("0", "0").validate().is_ok());
+        assert!(args("0", "0.001").validate().is_err());
+        assert!(args("1", "-1").validate().is_err());
+    }
+
+    #[test]
+    fn parse_max_price_estimation_drivers() {
+        let args = OrderQuotingArguments::try_parse_from([""]).unwrap();
+        assert_eq!(args.max_price_estimation_drivers, None);
+
+        let args = OrderQuotingArguments::try_parse_from(["", "--max-price-estimation-drivers=2"])
+            .unwrap();
+        assert_eq!(args.max_price_estimation_drivers, NonZeroUsize::new(2));
+
+        assert!(
+            OrderQuotingArguments::try_parse_from(["", "--max-price-estimation-drivers=-1"])
+                .is_err()
+        );
+        assert!(
+            OrderQuotingArguments::try_parse_from(["", "--max-price-estimation-drivers=0"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_native_price_gas_estimator() {
+        let args = Arguments::try_parse_from([""]).unwrap();
+        assert!(args.native_price_gas_estimator.is_none());
+
+        let args = Arguments::try_parse_from(["", "--native-price-gas-estimator=native"]).unwrap();
+        assert!(matches!(
+            args.native_price_gas_estimator,
+            Some(GasEstimatorType::Native)
+        ));
+
+        assert!(Arguments::try_parse_from(["", "--native-price-gas-estimator=Unknown"]).is_err());
+    }
+
+    #[test]
+    fn base_token_deny_list_is_excluded() {
+        let token = |byte: u8| H160([byte; 20]);
+        let args = Arguments::try_parse_from([
+            "".to_owned(),
+            format!("--base-tokens={:?},{:?},{:?}", token(1), token(2), token(3)),
+            format!("--base-token-deny-list={:?},{:?}", token(2), token(4)),
+        ])
+        .unwrap();
+
+        assert_eq!(args.effective_base_tokens(), [token(1), token(3)]);
+    }
+
+    #[test]
+    fn display_redacts_secrets() {
+        let secrets = [
+            ("blocknative_api_key", "blocknative-secret"),
+            ("paraswap_partner", "paraswap-secret"),
+            ("zeroex_api_key", "zeroex-secret"),
+            ("solver_competition_auth", "competition-secret"),
+            ("tenderly_api_key", "tenderly-secret"),
+        ];
+        let args = Arguments::try_parse_from(
+            std::iter::once("".to_owned()).chain(
+                secrets
+                    .iter()
+                    .map(|(name, value)| format!("--{}={value}", name.replace('_', "-"))),
+            ),
+        )
+        .unwrap();
+
+        let display = args.to_string();
+        for (name, value) in secrets {
+            assert!(!display.contains(value), "{name} is displayed in plaintext");
+            assert!(display.contains(&format!("{name}: SECRET\n")));
+        }
+    }
+
+    /// Fails when an argument whose name looks like a secret is displayed in
+    /// plaintext, so that new secrets don't get logged by accident.
+    #[test]
+    fn secret_fields_are_redacted() {
+        let secrets = Arguments::command()
+            .get_arguments()
+            .filter_map(|arg| arg.get_long())
+            .filter(|name| {
+                ["api-key", "secret", "auth", "password"]
+                    .iter()
+                    .any(|pattern| name.contains(pattern))
+            })
+            .map(|name| (name.to_owned(), format!("value-of-{name}")))
+            .collect::<Vec<_>>();
+        assert!(!secrets.is_empty());
+        let args = Arguments::try_parse_from(
+            std::iter::once("".to_owned()).chain(
+                secrets
+                    .iter()
+                    .map(|(name, value)| format!("--{name}={value}")),
+            ),
+        )
+        .unwrap();
+
+        let display = args.to_string();
+        for (name, value) in &secrets {
+            assert!(
+                !display.contains(value.as_str()),
+                "{name} is displayed in plaintext"
+            );
+        }
+    }
+
+    #[test]
+    fn wei_from_base_unit_with_different_decimals() {
+        assert_eq!(
+            wei_from_base_unit_with_decimals("5", 6).unwrap(),
+            U256::from(5_000_000)
+        );
+        assert_eq!(
+            wei_from_base_unit_with_decimals("5", 8).unwrap(),
+            U256::from(500_000_000)
+        );
+        assert_eq!(
+            wei_from_base_unit_with_decimals("5", 18).unwrap(),
+            U256::from(5) * U256::exp10(18)
+        );
+        assert_eq!(
+            wei_from_base_unit("5").unwrap(),
+            wei_from_base_unit_with_decimals("5", 18).unwrap()
+        );
+        assert!(wei_from_base_unit_with_decimals("abc", 6).is_err());
+    }
+
+    #[test]
+    fn wei_from_base_unit_overflow() {
+        let max = U256::max_value().to_string();
+        assert_eq!(
+            wei_from_base_unit_with_decimals(&max, 0).unwrap(),
+            U256::max_value()
+        );
+        assert!(wei_from_base_unit_with_decimals(&max, 6).is_err());
+        assert!(wei_from_base_unit_with_decimals("1", 78).is_err());
+        assert!(wei_from_base_unit(
+            "1000000000000000000000000000000000000000000000000000000000000000"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn wei_from_gwei_is_exact() {
+        assert_eq!(wei_from_gwei_u256("1").unwrap(), U256::from(1_000_000_000));
+        assert_eq!(
+            wei_from_gwei_u256("1.5").unwrap(),
+            U256::from(1_500_000_000)
+        );
+        assert_eq!(wei_from_gwei_u256("0.000000001").unwrap(), U256::one());
+        assert_eq!(wei_from_gwei_u256(".5").unwrap(), U256::from(500_000_000));
+        assert_eq!(
+            wei_from_gwei_u256("3.").unwrap(),
+            U256::from(3_000_000_000u64)
+        );
+        assert!(wei_from_gwei_u256("0.0000000001").is_err());
+        assert!(wei_from_gwei_u256("").is_err());
+        assert!(wei_from_gwei_u256(".").is_err());
+        assert!(wei_from_gwei_u256("-1").is_err());
+        assert!(wei_from_gwei_u256("1e9").is_err());
+    }
+
+    #[test]
+    fn gwei_from_wei_roundtrip() {
+        assert_eq!(gwei_from_wei(U256::from(1_000_000_000)), 1.);
+        assert_eq!(gwei_from_wei(wei_from_gwei_u256("1500").unwrap()), 1500.);
+    }
+
     #[test]
     fn parse_driver() {
         let argument = "name1|http://localhost:8080";
@@ -633,13 +966,64 @@ mod test {
     #[test]
     fn parse_drivers_wrong_arguments() {
         // too few arguments
-        assert!(Driver::from_str("").is_err());
-        assert!(Driver::from_str("name").is_err());
+        assert_eq!(Driver::from_str(""), Err(DriverParseError::MissingUrl));
+        assert_eq!(Driver::from_str("name"), Err(DriverParseError::MissingUrl));
 
         // broken URL
-        assert!(Driver::from_str("name1|sdfsdfds").is_err());
+        assert!(matches!(
+            Driver::from_str("name1|sdfsdfds"),
+            Err(DriverParseError::InvalidUrl(_))
+        ));
 
         // too many arguments
-        assert!(Driver::from_str("name1|http://localhost:8080|additional_argument").is_err());
+        assert_eq!(
+            Driver::from_str("name1|http://localhost:8080|additional_argument"),
+            Err(DriverParseError::TooManyFields)
+        );
+    }
+
+    #[test]
+    fn parse_price_estimation_drivers() {
+        let args = OrderQuotingArguments::try_parse_from([""]).unwrap();
+        assert!(args.price_estimation_drivers.is_empty());
+
+        let args = OrderQuotingArguments::try_parse_from([
+            "",
+            "--price-estimation-drivers=name1|http://localhost:8080,name2|http://localhost:8081",
+        ])
+        .unwrap();
+        assert_eq!(
+            args.price_estimation_drivers
+                .iter()
+                .map(|driver| driver.name.as_str())
+                .collect::<Vec<_>>(),
+            ["name1", "name2"]
+        );
+    }
+
+    #[test]
+    fn parse_many_drivers() {
+        let driver = |name: &str, url: &str| Driver {
+            name: name.into(),
+            url: Url::parse(url).unwrap(),
+        };
+
+        assert_eq!(
+            Driver::parse_many(
+                "name1|http://localhost:8080,name2|http://localhost:8081;\n name3|http://localhost:8082\n"
+            )
+            .unwrap(),
+            [
+                driver("name1", "http://localhost:8080"),
+                driver("name2", "http://localhost:8081"),
+                driver("name3", "http://localhost:8082"),
+            ]
+        );
+        assert_eq!(
+            Driver::parse_many("name1|http://localhost:8080;").unwrap(),
+            [driver("name1", "http://localhost:8080")]
+        );
+        assert!(Driver::parse_many("").unwrap().is_empty());
+        assert!(Driver::parse_many("name1|http://localhost:8080;name2").is_err());
     }
 }
@@ -1,6 +1,8 @@
 pub mod blockscout;
+pub mod caching;
 pub mod ethplorer;
 pub mod liquidity;
+pub mod multi;
 pub mod solvers;
 pub mod token_owner_list;
 
@@ -47,10 +49,17 @@ use {
 
 /// This trait abstracts various sources for proposing token owner candidates
 /// which are likely, but not guaranteed, to have some token balance.
+#[mockall::automock]
 #[async_trait::async_trait]
 pub trait TokenOwnerProposing: Send + Sync {
     /// Find candidate addresses that might own the token.
     async fn find_candidate_owners(&self, token: H160) -> Result<Vec<H160>>;
+
+    /// Whether the upstream source of candidates is currently reachable. This
+    /// allows telling a failing source apart from tokens without owners.
+    async fn healthy(&self) -> bool {
+        true
+    }
 }
 
 /// To detect bad tokens we need to find some address on the network that owns
@@ -80,6 +89,23 @@ pub struct Arguments {
     #[clap(long, env, value_parser = duration_from_seconds, default_value = "45")]
     pub blockscout_http_timeout: Duration,
 
+    /// Override the Blockscout API URL, for example to use a self-hosted
+    /// instance (e.g. `https://host/api`). Defaults to the hosted Blockscout
+    /// API of the current network.
+    #[clap(long, env)]
+    pub blockscout_api_url: Option<Url>,
+
+    /// The maximum size in bytes of a Blockscout response body. Larger
+    /// responses are rejected.
+    #[clap(long, env, default_value = "1000000")]
+    pub blockscout_response_size_limit: usize,
+
+    /// The minimum token balance, in atoms, a Blockscout token holder needs
+    /// to be proposed as a token owner candidate. Holders below it are
+    /// skipped when the API reports balances.
+    #[clap(long, env, value_parser = U256::from_dec_str)]
+    pub blockscout_min_balance: Option<U256>,
+
     /// The Ethplorer token holder API key.
     #[clap(long, env)]
     pub ethplorer_api_key: Option<String>,
@@ -173,6 +199,17 @@ impl Display for Arguments {
             "token_owner_finder_http_timeout: {:?}",
             self.token_owner_finders
         )?;
+        writeln!(f, "blockscout_api_url: {:?}", self.blockscout_api_url)?;
+        writeln!(
+            f,
+            "blockscout_response_size_limit: {}",
+            self.blockscout_response_size_limit
+        )?;
+        writeln!(
+            f,
+            "blockscout_min_balance: {:?}",
+            self.blockscout_min_balance
+        )?;
 
         Ok(())
     }
@@ -225,10 +262,17 @@ pub async fn init(
     }
 
     if finders.contains(&TokenOwnerFindingStrategy::Blockscout) {
-        proposers.push(Arc::new(BlockscoutTokenOwnerFinder::try_with_network(
-            http_factory.configure(|builder| builder.timeout(args.blockscout_http_timeout)),
-            chain_id,
-        )?));
+        let client =
+            http_factory.configure(|builder| builder.timeout(args.blockscout_http_timeout));
+        let mut blockscout = match &args.blockscout_api_url {
+            Some(url) => BlockscoutTokenOwnerFinder::try_with_url(client, url.clone())?,
+            None => BlockscoutTokenOwnerFinder::try_with_network(client, chain_id)?,
+        };
+        blockscout.with_response_size_limit(args.blockscout_response_size_limit);
+        if let Some(min_balance) = args.blockscout_min_balance {
+            blockscout.with_min_balance(min_balance);
+        }
+        proposers.push(Arc::new(blockscout));
     }
 
     if finders.contains(&TokenOwnerFindingStrategy::Ethplorer) {
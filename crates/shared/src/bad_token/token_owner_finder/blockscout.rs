@@ -1,18 +1,35 @@
 use {
     super::TokenOwnerProposing,
-    anyhow::{bail, Result},
-    ethcontract::H160,
+    crate::http_client::response_body_with_size_limit,
+    anyhow::{bail, ensure, Context, Result},
+    ethcontract::{H160, U256},
+    model::u256_decimal::DecimalU256,
     prometheus::IntCounterVec,
     prometheus_metric_storage::MetricStorage,
     reqwest::{Client, Url},
     serde::Deserialize,
+    serde_with::serde_as,
 };
 
 const BASE: &str = "https://blockscout.com/";
 
+/// We technically only need one candidate, returning the top 2 in case there is
+/// a race condition and tokens have just been transferred out.
+const NUM_CANDIDATES: usize = 2;
+
+/// The default maximum size in bytes of a Blockscout response body.
+pub const DEFAULT_RESPONSE_SIZE_LIMIT: usize = 1_000_000;
+
 pub struct BlockscoutTokenOwnerFinder {
     client: Client,
     base: Url,
+
+    /// Holders reported with a balance below this threshold are skipped, as
+    /// they can't be used as a source for transfer simulations.
+    min_balance: Option<U256>,
+
+    /// Responses with larger bodies are rejected without being parsed.
+    response_size_limit: usize,
 }
 
 impl BlockscoutTokenOwnerFinder {
@@ -23,55 +40,101 @@ impl BlockscoutTokenOwnerFinder {
             _ => bail!("Unsupported Network"),
         };
 
-        Ok(Self {
+        Self::try_with_url(
             client,
-            base: Url::try_from(BASE)
+            Url::try_from(BASE)
                 .expect("Invalid Blockscout Base URL")
                 .join(network)
                 .expect("Invalid Blockscout URL Segement")
                 .join("mainnet/api")
                 .expect("Invalid Blockscout URL Segement"),
+        )
+    }
+
+    /// Creates a token owner finder for the Blockscout API at the specified
+    /// URL, for example a self-hosted instance (`https://host/api`).
+    pub fn try_with_url(client: Client, base: Url) -> Result<Self> {
+        ensure!(!base.cannot_be_a_base(), "Invalid Blockscout URL");
+        Ok(Self {
+            client,
+            base,
+            min_balance: None,
+            response_size_limit: DEFAULT_RESPONSE_SIZE_LIMIT,
         })
     }
 
-    async fn query_owners(&self, token: H160) -> Result<Vec<H160>> {
+    pub fn with_min_balance(&mut self, min_balance: U256) -> &mut Self {
+        self.min_balance = Some(min_balance);
+        self
+    }
+
+    pub fn with_response_size_limit(&mut self, limit: usize) -> &mut Self {
+        self.response_size_limit = limit;
+        self
+    }
+
+    fn holders_url(&self, token: H160) -> Url {
         let mut url = self.base.clone();
         url.query_pairs_mut()
             .append_pair("module", "token")
             .append_pair("action", "getTokenHolders")
             .append_pair("contractaddress", &format!("{token:#x}"));
+        url
+    }
 
+    async fn query_owners(&self, token: H160) -> Result<Vec<H160>> {
+        let url = self.holders_url(token);
         tracing::debug!(%url, "Querying Blockscout API");
 
-        let response = self.client.get(url).send().await?;
+        let mut response = self.client.get(url).send().await?;
         let status = response.status();
         let status_result = response.error_for_status_ref().map(|_| ());
-        let body = response.text().await?;
+        let body = response_body_with_size_limit(&mut response, self.response_size_limit)
+            .await
+            .with_context(|| {
+                format!(
+                    "Blockscout response body (limit {} bytes)",
+                    self.response_size_limit
+                )
+            })?;
+        let body = String::from_utf8_lossy(&body);
 
         tracing::debug!(%status, %body, "Response from Blockscout API");
 
         status_result?;
         let parsed = serde_json::from_str::<Response>(&body)?;
 
-        // We technically only need one candidate, returning the top 2 in case there is
-        // a race condition and tokens have just been transferred out
-        Ok(parsed
-            .result
-            .into_iter()
-            .map(|owner| owner.address)
-            .take(2)
-            .collect())
+        Ok(candidates(parsed.result, self.min_balance))
     }
 }
 
+/// Selects the top candidates from the holder list, skipping holders whose
+/// reported balance is below the minimum. Holders without a reported balance
+/// are kept since the API version doesn't provide them.
+fn candidates(owners: Vec<TokenOwner>, min_balance: Option<U256>) -> Vec<H160> {
+    owners
+        .into_iter()
+        .filter(|owner| match (owner.value, min_balance) {
+            (Some(value), Some(min_balance)) => value >= min_balance,
+            _ => true,
+        })
+        .map(|owner| owner.address)
+        .take(NUM_CANDIDATES)
+        .collect()
+}
+
 #[derive(Deserialize)]
 struct Response {
     result: Vec<TokenOwner>,
 }
 
+#[serde_as]
 #[derive(Deserialize)]
 struct TokenOwner {
     address: H160,
+    #[serde_as(as = "Option<DecimalU256>")]
+    #[serde(default)]
+    value: Option<U256>,
 }
 
 #[derive(MetricStorage, Clone, Debug)]
@@ -101,11 +164,154 @@ impl TokenOwnerProposing for BlockscoutTokenOwnerFinder {
             }
         }
     }
+
+    async fn healthy(&self) -> bool {
+        // Querying the holders of the zero address is cheap as it isn't a
+        // token, so this only checks that the API is reachable.
+        match self.client.get(self.holders_url(H160::zero())).send().await {
+            Ok(response) => response.status().is_success(),
+            Err(err) => {
+                tracing::warn!(?err, "Blockscout health check failed");
+                false
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use {super::*, hex_literal::hex};
+    use {
+        super::*,
+        hex_literal::hex,
+        tokio::{
+            io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+            net::TcpListener,
+            sync::mpsc,
+        },
+    };
+
+    /// Starts a server that answers every request with the specified raw HTTP
+    /// response and reports the request lines it receives.
+    async fn mock_blockscout(response: String) -> (Url, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::task::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut stream = BufReader::new(stream);
+                let mut request_line = String::new();
+                stream.read_line(&mut request_line).await.unwrap();
+                // Skip the remaining headers.
+                let mut line = String::new();
+                while stream.read_line(&mut line).await.unwrap() > 2 {
+                    line.clear();
+                }
+                let _ = sender.send(request_line.trim_end().to_owned());
+
+                let mut stream = stream.into_inner();
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.unwrap();
+            }
+        });
+        (url.parse().unwrap(), receiver)
+    }
+
+    fn http_response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: \
+             {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[tokio::test]
+    async fn queries_custom_base_url() {
+        let (url, mut requests) = mock_blockscout(http_response(
+            "200 OK",
+            r#"{"result":[{"address":"0x0000000000000000000000000000000000000001"}]}"#,
+        ))
+        .await;
+        let finder = BlockscoutTokenOwnerFinder::try_with_url(Client::new(), url).unwrap();
+
+        let owners = finder
+            .find_candidate_owners(H160::from_low_u64_be(0x42))
+            .await
+            .unwrap();
+
+        assert_eq!(owners, [H160::from_low_u64_be(1)]);
+        assert_eq!(
+            requests.recv().await.unwrap(),
+            "GET /api?module=token&action=getTokenHolders&\
+             contractaddress=0x0000000000000000000000000000000000000042 HTTP/1.1",
+        );
+    }
+
+    #[test]
+    fn filters_candidates_below_min_balance() {
+        let owners = serde_json::from_value::<Response>(serde_json::json!({
+            "result": [
+                { "address": "0x0000000000000000000000000000000000000001", "value": "1000" },
+                { "address": "0x0000000000000000000000000000000000000002", "value": "1" },
+                { "address": "0x0000000000000000000000000000000000000003" },
+                { "address": "0x0000000000000000000000000000000000000004", "value": "500" },
+            ],
+        }))
+        .unwrap()
+        .result;
+
+        assert_eq!(
+            candidates(owners, Some(100.into())),
+            [H160::from_low_u64_be(1), H160::from_low_u64_be(3)],
+        );
+    }
+
+    #[test]
+    fn keeps_top_candidates_without_min_balance() {
+        let owners = serde_json::from_value::<Response>(serde_json::json!({
+            "result": [
+                { "address": "0x0000000000000000000000000000000000000001", "value": "1" },
+                { "address": "0x0000000000000000000000000000000000000002", "value": "0" },
+                { "address": "0x0000000000000000000000000000000000000003", "value": "1000" },
+            ],
+        }))
+        .unwrap()
+        .result;
+
+        assert_eq!(
+            candidates(owners, None),
+            [H160::from_low_u64_be(1), H160::from_low_u64_be(2)],
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_health() {
+        let (url, _) = mock_blockscout(http_response("200 OK", r#"{"result":[]}"#)).await;
+        let finder = BlockscoutTokenOwnerFinder::try_with_url(Client::new(), url).unwrap();
+        assert!(finder.healthy().await);
+
+        let (url, _) = mock_blockscout(http_response("500 Internal Server Error", "")).await;
+        let finder = BlockscoutTokenOwnerFinder::try_with_url(Client::new(), url).unwrap();
+        assert!(!finder.healthy().await);
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_response() {
+        let body = format!(r#"{{"result":[],"padding":"{}"}}"#, "0".repeat(1_000));
+        let (url, _) = mock_blockscout(http_response("200 OK", &body)).await;
+        let mut finder = BlockscoutTokenOwnerFinder::try_with_url(Client::new(), url).unwrap();
+        finder.with_response_size_limit(100);
+
+        let err = finder
+            .find_candidate_owners(H160::from_low_u64_be(0x42))
+            .await
+            .unwrap_err();
+
+        assert!(
+            format!("{err:#}").contains("size limit exceeded"),
+            "unexpected error: {err:#}"
+        );
+    }
 
     #[tokio::test]
     #[ignore]
@@ -0,0 +1,128 @@
+use {
+    super::TokenOwnerProposing,
+    anyhow::Result,
+    cached::{Cached, TimedSizedCache},
+    ethcontract::H160,
+    std::{sync::Mutex, time::Duration},
+};
+
+/// A `TokenOwnerProposing` implementation that caches the candidates of an
+/// inner finder in a size bounded LRU cache whose entries expire after a
+/// fixed lifespan.
+pub struct CachingTokenOwnerFinder<Inner> {
+    inner: Inner,
+    cache: Mutex<TimedSizedCache<H160, Vec<H160>>>,
+}
+
+impl<Inner> CachingTokenOwnerFinder<Inner> {
+    /// Creates a new caching finder. The cache only has a resolution of
+    /// seconds, so the lifespan is rounded up to whole seconds.
+    pub fn new(inner: Inner, size: usize, lifespan: Duration) -> Self {
+        let lifespan_secs = lifespan.as_secs() + u64::from(lifespan.subsec_nanos() > 0);
+        Self {
+            inner,
+            cache: Mutex::new(TimedSizedCache::with_size_and_lifespan_and_refresh(
+                size,
+                lifespan_secs,
+                false,
+            )),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Inner> TokenOwnerProposing for CachingTokenOwnerFinder<Inner>
+where
+    Inner: TokenOwnerProposing,
+{
+    async fn find_candidate_owners(&self, token: H160) -> Result<Vec<H160>> {
+        if let Some(owners) = self.cache.lock().unwrap().cache_get(&token).cloned() {
+            return Ok(owners);
+        }
+        // Errors are not cached so that transient failures get retried, but
+        // empty results are since the token is known to have no owners.
+        let owners = self.inner.find_candidate_owners(token).await?;
+        self.cache.lock().unwrap().cache_set(token, owners.clone());
+        Ok(owners)
+    }
+
+    async fn healthy(&self) -> bool {
+        self.inner.healthy().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::bad_token::token_owner_finder::MockTokenOwnerProposing};
+
+    #[tokio::test]
+    async fn second_lookup_is_cached() {
+        let token = H160::from_low_u64_be(1);
+        let mut inner = MockTokenOwnerProposing::new();
+        inner
+            .expect_find_candidate_owners()
+            .times(1)
+            .returning(|_| Ok(vec![H160::from_low_u64_be(2)]));
+        let finder = CachingTokenOwnerFinder::new(inner, 10, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert_eq!(
+                finder.find_candidate_owners(token).await.unwrap(),
+                [H160::from_low_u64_be(2)],
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_results_are_cached() {
+        let mut inner = MockTokenOwnerProposing::new();
+        inner
+            .expect_find_candidate_owners()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        let finder = CachingTokenOwnerFinder::new(inner, 10, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(finder
+                .find_candidate_owners(H160::from_low_u64_be(1))
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let mut inner = MockTokenOwnerProposing::new();
+        inner
+            .expect_find_candidate_owners()
+            .times(2)
+            .returning(|_| Err(anyhow::anyhow!("unavailable")));
+        let finder = CachingTokenOwnerFinder::new(inner, 10, Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(finder
+                .find_candidate_owners(H160::from_low_u64_be(1))
+                .await
+                .is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn sub_second_lifespan_is_cached() {
+        let mut inner = MockTokenOwnerProposing::new();
+        inner
+            .expect_find_candidate_owners()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+        let finder = CachingTokenOwnerFinder::new(inner, 10, Duration::from_millis(500));
+
+        for _ in 0..2 {
+            assert!(finder
+                .find_candidate_owners(H160::from_low_u64_be(1))
+                .await
+                .unwrap()
+                .is_empty());
+        }
+    }
+}
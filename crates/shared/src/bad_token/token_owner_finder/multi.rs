@@ -0,0 +1,133 @@
+use {
+    super::TokenOwnerProposing,
+    anyhow::{anyhow, Result},
+    ethcontract::H160,
+    prometheus::IntCounterVec,
+    prometheus_metric_storage::MetricStorage,
+    std::{collections::HashSet, sync::Arc},
+};
+
+/// A `TokenOwnerProposing` implementation that concurrently queries multiple
+/// finders and merges their candidates.
+pub struct MultiTokenOwnerFinder {
+    pub finders: Vec<Arc<dyn TokenOwnerProposing>>,
+}
+
+#[derive(MetricStorage, Clone, Debug)]
+#[metric(subsystem = "multi_token_owner_finding")]
+struct Metrics {
+    /// Tracks number of "ok" or "err" responses per finder, identified by its
+    /// index.
+    #[metric(labels("finder", "result"))]
+    results: IntCounterVec,
+}
+
+#[async_trait::async_trait]
+impl TokenOwnerProposing for MultiTokenOwnerFinder {
+    async fn find_candidate_owners(&self, token: H160) -> Result<Vec<H160>> {
+        let metric = &Metrics::instance(global_metrics::get_metric_storage_registry())
+            .unwrap()
+            .results;
+
+        let results = futures::future::join_all(
+            self.finders
+                .iter()
+                .map(|finder| finder.find_candidate_owners(token)),
+        )
+        .await;
+
+        let mut seen = HashSet::new();
+        let mut owners = Vec::new();
+        let mut last_err = None;
+        let mut any_ok = false;
+        for (i, result) in results.into_iter().enumerate() {
+            let finder = i.to_string();
+            match result {
+                Ok(candidates) => {
+                    metric.with_label_values(&[&finder, "ok"]).inc();
+                    any_ok = true;
+                    owners.extend(candidates.into_iter().filter(|owner| seen.insert(*owner)));
+                }
+                Err(err) => {
+                    tracing::warn!(?err, %finder, "error finding token owners");
+                    metric.with_label_values(&[&finder, "err"]).inc();
+                    last_err = Some(err);
+                }
+            }
+        }
+
+        if any_ok {
+            return Ok(owners);
+        }
+        Err(last_err.unwrap_or_else(|| anyhow!("no token owner finders configured")))
+    }
+
+    async fn healthy(&self) -> bool {
+        futures::future::join_all(self.finders.iter().map(|finder| finder.healthy()))
+            .await
+            .into_iter()
+            .any(|healthy| healthy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::bad_token::token_owner_finder::MockTokenOwnerProposing};
+
+    fn finder(result: Result<Vec<H160>>) -> Arc<dyn TokenOwnerProposing> {
+        let mut finder = MockTokenOwnerProposing::new();
+        finder
+            .expect_find_candidate_owners()
+            .return_once(move |_| result);
+        Arc::new(finder)
+    }
+
+    #[tokio::test]
+    async fn deduplicates_owners_in_first_seen_order() {
+        let owner = H160::from_low_u64_be;
+        let multi = MultiTokenOwnerFinder {
+            finders: vec![
+                finder(Ok(vec![owner(1), owner(2)])),
+                finder(Ok(vec![owner(2), owner(3), owner(1)])),
+            ],
+        };
+
+        let owners = multi
+            .find_candidate_owners(H160::from_low_u64_be(42))
+            .await
+            .unwrap();
+        assert_eq!(owners, [owner(1), owner(2), owner(3)]);
+    }
+
+    #[tokio::test]
+    async fn succeeds_if_any_finder_succeeds() {
+        let owner = H160::from_low_u64_be;
+        let multi = MultiTokenOwnerFinder {
+            finders: vec![
+                finder(Err(anyhow!("unavailable"))),
+                finder(Ok(vec![owner(1)])),
+            ],
+        };
+
+        let owners = multi
+            .find_candidate_owners(H160::from_low_u64_be(42))
+            .await
+            .unwrap();
+        assert_eq!(owners, [owner(1)]);
+    }
+
+    #[tokio::test]
+    async fn fails_if_all_finders_fail() {
+        let multi = MultiTokenOwnerFinder {
+            finders: vec![
+                finder(Err(anyhow!("unavailable"))),
+                finder(Err(anyhow!("unavailable"))),
+            ],
+        };
+
+        assert!(multi
+            .find_candidate_owners(H160::from_low_u64_be(42))
+            .await
+            .is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use {
     crate::{ethrpc::Web3, http_client::HttpClientFactory},
     anyhow::{ensure, Context, Result},
+    futures::future,
     gas_estimation::{
         blocknative::BlockNative,
         nativegasestimator::NativeGasEstimator,
@@ -14,7 +15,10 @@ use {
     },
     reqwest::header::{self, HeaderMap, HeaderValue},
     serde::de::DeserializeOwned,
-    std::sync::{Arc, Mutex},
+    std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
 };
 
 #[derive(Copy, Clone, Debug, clap::ValueEnum)]
@@ -28,6 +32,20 @@ pub enum GasEstimatorType {
     Native,
 }
 
+/// How the estimates of multiple gas estimators are combined.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+#[clap(rename_all = "verbatim")]
+pub enum GasEstimationMode {
+    /// Use the estimate of the first estimator that succeeds, in the order in
+    /// which they are configured.
+    #[default]
+    Sequential,
+    /// Query all estimators and use the median of the successful estimates.
+    Median,
+    /// Query all estimators and use the highest of the successful estimates.
+    Max,
+}
+
 #[derive(Clone)]
 pub struct Client(pub reqwest::Client);
 
@@ -52,6 +70,7 @@ pub async fn create_priority_estimator(
     http_factory: &HttpClientFactory,
     web3: &Web3,
     estimator_types: &[GasEstimatorType],
+    mode: GasEstimationMode,
     blocknative_api_key: Option<String>,
 ) -> Result<impl GasPriceEstimating> {
     let client = || Client(http_factory.create());
@@ -108,7 +127,75 @@ pub async fn create_priority_estimator(
         !estimators.is_empty(),
         "all gas estimators failed to initialize"
     );
-    Ok(PriorityGasPriceEstimating::new(estimators))
+    Ok(CombinedGasPriceEstimator::new(estimators, mode))
+}
+
+/// Gas price estimator that combines multiple estimators according to a
+/// [`GasEstimationMode`].
+pub enum CombinedGasPriceEstimator {
+    Sequential(PriorityGasPriceEstimating),
+    Combined {
+        estimators: Vec<Box<dyn GasPriceEstimating>>,
+        mode: GasEstimationMode,
+    },
+}
+
+impl CombinedGasPriceEstimator {
+    pub fn new(estimators: Vec<Box<dyn GasPriceEstimating>>, mode: GasEstimationMode) -> Self {
+        match mode {
+            GasEstimationMode::Sequential => {
+                Self::Sequential(PriorityGasPriceEstimating::new(estimators))
+            }
+            GasEstimationMode::Median | GasEstimationMode::Max => {
+                Self::Combined { estimators, mode }
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl GasPriceEstimating for CombinedGasPriceEstimator {
+    async fn estimate_with_limits(
+        &self,
+        gas_limit: f64,
+        time_limit: Duration,
+    ) -> Result<GasPrice1559> {
+        let (estimators, mode) = match self {
+            Self::Sequential(inner) => {
+                return inner.estimate_with_limits(gas_limit, time_limit).await
+            }
+            Self::Combined { estimators, mode } => (estimators, *mode),
+        };
+        let estimates = future::join_all(
+            estimators
+                .iter()
+                .map(|estimator| estimator.estimate_with_limits(gas_limit, time_limit)),
+        )
+        .await
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, result)| match result {
+            Ok(estimate) => Some(estimate),
+            Err(err) => {
+                tracing::warn!(?err, "gas estimator {i} failed");
+                None
+            }
+        })
+        .collect();
+        combine(estimates, mode).context("all gas estimators failed")
+    }
+}
+
+/// Combines the successful estimates according to the mode. Estimates are
+/// compared by their effective gas price, and for an even number of estimates
+/// the higher of the two middle ones is the median.
+fn combine(mut estimates: Vec<GasPrice1559>, mode: GasEstimationMode) -> Option<GasPrice1559> {
+    estimates.sort_by(|a, b| a.effective_gas_price().total_cmp(&b.effective_gas_price()));
+    match mode {
+        GasEstimationMode::Sequential => unreachable!("sequential estimates are not combined"),
+        GasEstimationMode::Median => estimates.get(estimates.len() / 2).copied(),
+        GasEstimationMode::Max => estimates.last().copied(),
+    }
 }
 
 pub fn is_mainnet(network_id: &str) -> bool {
@@ -129,3 +216,31 @@ impl GasPriceEstimating for FakeGasPriceEstimator {
         Ok(*self.0.lock().unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas_price(max_fee_per_gas: f64) -> GasPrice1559 {
+        GasPrice1559 {
+            base_fee_per_gas: 1.,
+            max_fee_per_gas,
+            max_priority_fee_per_gas: max_fee_per_gas,
+        }
+    }
+
+    #[tokio::test]
+    async fn median_of_three_estimates() {
+        let estimator = CombinedGasPriceEstimator::new(
+            vec![
+                Box::new(FakeGasPriceEstimator::new(gas_price(30.))),
+                Box::new(FakeGasPriceEstimator::new(gas_price(10.))),
+                Box::new(FakeGasPriceEstimator::new(gas_price(20.))),
+            ],
+            GasEstimationMode::Median,
+        );
+
+        let estimate = estimator.estimate().await.unwrap();
+        assert_eq!(estimate.max_fee_per_gas, 20.);
+    }
+}
@@ -76,7 +76,8 @@ impl Display for Arguments {
 
 /// Extracts the bytes of the response up to some size limit.
 ///
-/// Returns an error if the byte limit was exceeded.
+/// Returns an error naming the limit and the observed size if the byte limit
+/// was exceeded.
 pub async fn response_body_with_size_limit(
     response: &mut Response,
     limit: usize,
@@ -85,7 +86,10 @@ pub async fn response_body_with_size_limit(
     while let Some(chunk) = response.chunk().await? {
         let slice: &[u8] = &chunk;
         if bytes.len() + slice.len() > limit {
-            return Err(anyhow!("size limit exceeded"));
+            return Err(anyhow!(
+                "size limit exceeded: limit {limit} bytes, observed at least {} bytes",
+                bytes.len() + slice.len()
+            ));
         }
         bytes.extend_from_slice(slice);
     }
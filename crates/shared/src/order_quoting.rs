@@ -659,6 +659,15 @@ impl From<&OrderQuoteRequest> for PreOrderData {
             sell_token_balance: quote_request.sell_token_balance,
             signing_scheme: quote_request.signing_scheme.into(),
             class: OrderClass::Market,
+            onchain_order: matches!(
+                quote_request.signing_scheme,
+                QuoteSigningScheme::Eip1271 {
+                    onchain_order: true,
+                    ..
+                } | QuoteSigningScheme::PreSign {
+                    onchain_order: true
+                }
+            ),
         }
     }
 }
@@ -718,6 +727,18 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn pre_order_data_from_onchain_quote_request() {
+        let quote_request = OrderQuoteRequest {
+            signing_scheme: QuoteSigningScheme::new_eip1271_with_default_gas(true),
+            partially_fillable: true,
+            ..Default::default()
+        };
+        let result = PreOrderData::from(&quote_request);
+        assert!(result.onchain_order);
+        assert!(result.partially_fillable);
+    }
+
     #[test]
     fn pre_order_data_from_quote_request_with_valid_for() {
         let quote_request = OrderQuoteRequest {
@@ -113,7 +113,9 @@ pub enum ValidationError {
     InvalidSignature,
     /// If fee and sell amount overflow u256
     SellAmountOverflow,
-    TransferSimulationFailed,
+    /// Transferring the sell token failed, with the revert reason if it is
+    /// known.
+    TransferSimulationFailed(Option<String>),
     /// The specified on-chain signature requires the from address of the
     /// order signer.
     MissingFrom,
@@ -229,6 +231,9 @@ pub struct PreOrderData {
     pub sell_token_balance: SellTokenSource,
     pub signing_scheme: SigningScheme,
     pub class: OrderClass,
+    /// Whether the order is placed onchain (e.g. eth-flow orders) instead of
+    /// being submitted through the API.
+    pub onchain_order: bool,
 }
 
 fn actual_receiver(owner: H160, order: &OrderData) -> H160 {
@@ -262,6 +267,7 @@ impl PreOrderData {
                 (false, true) => OrderClass::Limit(Default::default()),
                 (true, _) => OrderClass::Liquidity,
             },
+            onchain_order: false,
         }
     }
 }
@@ -336,7 +342,11 @@ impl OrderValidating for OrderValidator {
             return Err(PartialValidationError::Forbidden);
         }
 
-        if order.partially_fillable && order.class != OrderClass::Liquidity {
+        // Partially fillable orders are only supported for liquidity orders and
+        // onchain orders (eth-flow orders can be filled over multiple
+        // settlements and get the remainder refunded).
+        if order.partially_fillable && order.class != OrderClass::Liquidity && !order.onchain_order
+        {
             return Err(PartialValidationError::UnsupportedOrderType);
         }
 
@@ -479,10 +489,10 @@ impl OrderValidating for OrderValidator {
         } else {
             // We don't try to get quotes for liquidity and limit orders
             // for two reasons:
-            // 1. They don't pay fees, meaning we don't need to know what the
-            //    min fee amount is.
-            // 2. We don't really care about the equivalent quote since they
-            //    aren't expected to follow regular order creation flow.
+            // 1. They don't pay fees, meaning we don't need to know what the min fee amount
+            //    is.
+            // 2. We don't really care about the equivalent quote since they aren't expected
+            //    to follow regular order creation flow.
             None
         };
 
@@ -528,12 +538,12 @@ impl OrderValidating for OrderValidator {
                 TransferSimulationError::InsufficientBalance => {
                     return Err(ValidationError::InsufficientBalance);
                 }
-                TransferSimulationError::TransferFailed => {
-                    return Err(ValidationError::TransferSimulationFailed);
+                TransferSimulationError::TransferFailed(reason) => {
+                    return Err(ValidationError::TransferSimulationFailed(reason));
                 }
                 TransferSimulationError::Other(err) => {
                     tracing::warn!("TransferSimulation failed: {:?}", err);
-                    return Err(ValidationError::TransferSimulationFailed);
+                    return Err(ValidationError::TransferSimulationFailed(None));
                 }
             },
         }
@@ -906,6 +916,16 @@ mod tests {
                 .await,
             Err(PartialValidationError::UnsupportedOrderType)
         ));
+        assert!(!matches!(
+            validator
+                .partial_validate(PreOrderData {
+                    partially_fillable: true,
+                    onchain_order: true,
+                    ..Default::default()
+                })
+                .await,
+            Err(PartialValidationError::UnsupportedOrderType)
+        ));
         assert!(matches!(
             validator
                 .partial_validate(PreOrderData {
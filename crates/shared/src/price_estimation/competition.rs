@@ -8,7 +8,14 @@ use {
     },
     futures::stream::StreamExt,
     model::order::OrderKind,
-    std::{cmp::Ordering, num::NonZeroUsize, sync::Arc},
+    std::{
+        cmp::Ordering,
+        num::NonZeroUsize,
+        sync::{
+            atomic::{AtomicUsize, Ordering as AtomicOrdering},
+            Arc,
+        },
+    },
 };
 
 /// Price estimator that pulls estimates from various sources
@@ -18,6 +25,16 @@ use {
 pub struct RacingCompetitionPriceEstimator {
     inner: Vec<(String, Arc<dyn PriceEstimating>)>,
     successful_results_for_early_return: NonZeroUsize,
+    rotation: Option<Rotation>,
+}
+
+/// Limits how many of the trailing inner estimators get queried per request.
+struct Rotation {
+    /// Index of the first inner estimator that is rotated. The estimators
+    /// before it are queried for every request.
+    first: usize,
+    max: NonZeroUsize,
+    next: AtomicUsize,
 }
 
 impl RacingCompetitionPriceEstimator {
@@ -29,8 +46,35 @@ impl RacingCompetitionPriceEstimator {
         Self {
             inner,
             successful_results_for_early_return,
+            rotation: None,
         }
     }
+
+    /// Only queries up to `max` of the inner estimators starting at index
+    /// `first` for each request. The queried estimators are rotated between
+    /// requests so that all of them get used.
+    pub fn with_rotation(mut self, first: usize, max: NonZeroUsize) -> Self {
+        assert!(first <= self.inner.len());
+        self.rotation = Some(Rotation {
+            first,
+            max,
+            next: AtomicUsize::new(0),
+        });
+        self
+    }
+
+    /// Returns the indices of the inner estimators to query for a request.
+    fn queried_estimators(&self) -> Vec<usize> {
+        let rotation = match &self.rotation {
+            Some(rotation) if self.inner.len() - rotation.first > rotation.max.get() => rotation,
+            _ => return (0..self.inner.len()).collect(),
+        };
+        let rotated = self.inner.len() - rotation.first;
+        let start = rotation.next.fetch_add(1, AtomicOrdering::Relaxed);
+        (0..rotation.first)
+            .chain((0..rotation.max.get()).map(|i| rotation.first + (start + i) % rotated))
+            .collect()
+    }
 }
 
 impl PriceEstimating for RacingCompetitionPriceEstimator {
@@ -44,15 +88,19 @@ impl PriceEstimating for RacingCompetitionPriceEstimator {
                 && query.sell_token != query.buy_token
         }));
 
-        // Turn the streams from all inner price estimators into a single stream.
-        let combined_stream = futures::stream::select_all(self.inner.iter().enumerate().map(
-            |(i, (_, estimator))| estimator.estimates(queries).map(move |result| (i, result)),
-        ));
+        // Turn the streams from all queried price estimators into a single stream.
+        let queried = self.queried_estimators();
+        let combined_stream = futures::stream::select_all(queried.iter().map(|&i| {
+            self.inner[i]
+                .1
+                .estimates(queries)
+                .map(move |result| (i, result))
+        }));
         // Stores the estimates for each query and estimator. When we have collected
         // enough results to produce a result of our own the corresponding
         // element is set to None.
         let mut estimates: Vec<Option<Vec<(usize, PriceEstimateResult)>>> =
-            vec![Some(Vec::with_capacity(self.inner.len())); queries.len()];
+            vec![Some(Vec::with_capacity(queried.len())); queries.len()];
         // Receives items from the combined stream.
         let mut handle_single_result = move |estimator_index: usize, query_index: usize, result| {
             let query = &queries[query_index];
@@ -65,7 +113,7 @@ impl PriceEstimating for RacingCompetitionPriceEstimator {
 
             // Check if we have enough results to emit a result of our own.
             let successes = results.iter().filter(|result| result.1.is_ok()).count();
-            let remaining = self.inner.len() - results.len();
+            let remaining = queried.len() - results.len();
             if successes < self.successful_results_for_early_return.get() && remaining > 0 {
                 return None;
             }
@@ -112,6 +160,13 @@ impl CompetitionPriceEstimator {
             inner: RacingCompetitionPriceEstimator::new(inner, number_of_estimators),
         }
     }
+
+    /// See [`RacingCompetitionPriceEstimator::with_rotation`].
+    pub fn with_rotation(self, first: usize, max: NonZeroUsize) -> Self {
+        Self {
+            inner: self.inner.with_rotation(first, max),
+        }
+    }
 }
 
 impl PriceEstimating for CompetitionPriceEstimator {
@@ -394,6 +449,41 @@ mod tests {
         assert_eq!(result.as_ref().unwrap(), &estimate(2));
     }
 
+    #[tokio::test]
+    async fn rotates_queried_estimators() {
+        let queries = [Query {
+            from: None,
+            sell_token: H160::from_low_u64_le(0),
+            buy_token: H160::from_low_u64_le(1),
+            in_amount: 1.into(),
+            kind: OrderKind::Sell,
+        }];
+        let estimator = |times: usize| -> Arc<dyn PriceEstimating> {
+            let mut estimator = MockPriceEstimating::new();
+            estimator.expect_estimates().times(times).returning(|_| {
+                futures::stream::iter([Ok(Estimate::default())])
+                    .enumerate()
+                    .boxed()
+            });
+            Arc::new(estimator)
+        };
+
+        // The first estimator is always queried, and two of the remaining three
+        // are queried per request, starting at a different one each time.
+        let competition = CompetitionPriceEstimator::new(vec![
+            ("fixed".to_owned(), estimator(3)),
+            ("a".to_owned(), estimator(2)),
+            ("b".to_owned(), estimator(2)),
+            ("c".to_owned(), estimator(2)),
+        ])
+        .with_rotation(1, NonZeroUsize::new(2).unwrap());
+
+        for _ in 0..3 {
+            let result = vec_estimates(&competition, &queries).await;
+            assert!(result[0].is_ok());
+        }
+    }
+
     #[tokio::test]
     async fn result_ordering() {
         fn estimate(amount: u64) -> Estimate {
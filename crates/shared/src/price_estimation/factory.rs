@@ -85,6 +85,9 @@ pub struct Components {
     pub uniswap_v3_pools: Option<Arc<dyn UniswapV3PoolFetching>>,
     pub tokens: Arc<dyn TokenInfoFetching>,
     pub gas_price: Arc<dyn GasPriceEstimating>,
+    /// The gas price estimator to use for native price estimation, or
+    /// [`None`] to use `gas_price`.
+    pub native_gas_price: Option<Arc<dyn GasPriceEstimating>>,
     pub zeroex: Arc<dyn ZeroExApi>,
     pub oneinch: Option<Arc<dyn OneInchClient>>,
 }
@@ -169,7 +172,12 @@ impl<'a> PriceEstimatorFactory<'a> {
         T: PriceEstimating + PriceEstimatorCreating,
         T::Params: Clone,
     {
-        let estimator = T::init(self, kind, params.clone())?;
+        let estimator = T::init(
+            self,
+            kind,
+            params.clone(),
+            self.components.gas_price.clone(),
+        )?;
         let verified = self
             .trade_verifier
             .as_ref()
@@ -188,7 +196,12 @@ impl<'a> PriceEstimatorFactory<'a> {
         // price estimator (this is because request sharing isn't benificial),
         // nor do we configure the trade verifier (because external price
         // precision is less critical).
-        let native = instrument(T::init(self, kind, params)?, kind.name());
+        let native_gas_price = self
+            .components
+            .native_gas_price
+            .clone()
+            .unwrap_or_else(|| self.components.gas_price.clone());
+        let native = instrument(T::init(self, kind, params, native_gas_price)?, kind.name());
 
         Ok(EstimatorEntry {
             optimal,
@@ -311,12 +324,15 @@ impl<'a> PriceEstimatorFactory<'a> {
         &mut self,
         kinds: &[PriceEstimatorType],
         drivers: &[Driver],
+        max_drivers: Option<NonZeroUsize>,
     ) -> Result<Arc<dyn PriceEstimating>> {
         let mut estimators = self.get_estimators(kinds, |entry| &entry.optimal)?;
         estimators.append(&mut self.get_external_estimators(drivers, |entry| &entry.optimal)?);
-        Ok(Arc::new(
-            self.sanitized(CompetitionPriceEstimator::new(estimators)),
-        ))
+        let mut competition = CompetitionPriceEstimator::new(estimators);
+        if let Some(max) = max_drivers {
+            competition = competition.with_rotation(kinds.len(), max);
+        }
+        Ok(Arc::new(self.sanitized(competition)))
     }
 
     pub fn fast_price_estimator(
@@ -324,21 +340,25 @@ impl<'a> PriceEstimatorFactory<'a> {
         kinds: &[PriceEstimatorType],
         fast_price_estimation_results_required: NonZeroUsize,
         drivers: &[Driver],
+        max_drivers: Option<NonZeroUsize>,
     ) -> Result<Arc<dyn PriceEstimating>> {
         let mut estimators = self.get_estimators(kinds, |entry| &entry.fast)?;
         estimators.append(&mut self.get_external_estimators(drivers, |entry| &entry.fast)?);
-        Ok(Arc::new(self.sanitized(
-            RacingCompetitionPriceEstimator::new(
-                estimators,
-                fast_price_estimation_results_required,
-            ),
-        )))
+        let mut competition = RacingCompetitionPriceEstimator::new(
+            estimators,
+            fast_price_estimation_results_required,
+        );
+        if let Some(max) = max_drivers {
+            competition = competition.with_rotation(kinds.len(), max);
+        }
+        Ok(Arc::new(self.sanitized(competition)))
     }
 
     pub fn native_price_estimator(
         &mut self,
         kinds: &[PriceEstimatorType],
         drivers: &[Driver],
+        max_drivers: Option<NonZeroUsize>,
     ) -> Result<Arc<CachingNativePriceEstimator>> {
         anyhow::ensure!(
             self.args.native_price_cache_max_age_secs > self.args.native_price_prefetch_time_secs,
@@ -346,9 +366,13 @@ impl<'a> PriceEstimatorFactory<'a> {
         );
         let mut estimators = self.get_estimators(kinds, |entry| &entry.native)?;
         estimators.append(&mut self.get_external_estimators(drivers, |entry| &entry.native)?);
+        let mut competition = CompetitionPriceEstimator::new(estimators);
+        if let Some(max) = max_drivers {
+            competition = competition.with_rotation(kinds.len(), max);
+        }
         let native_estimator = Arc::new(CachingNativePriceEstimator::new(
             Box::new(NativePriceEstimator::new(
-                Arc::new(self.sanitized(CompetitionPriceEstimator::new(estimators))),
+                Arc::new(self.sanitized(competition)),
                 self.network.native_token,
                 self.native_token_price_estimation_amount()?,
             )),
@@ -372,6 +396,7 @@ trait PriceEstimatorCreating: Sized {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         params: Self::Params,
+        gas_price: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self>;
 
     fn verified(&self, _: &TradeVerifier) -> Option<Self> {
@@ -386,10 +411,11 @@ impl PriceEstimatorCreating for BaselinePriceEstimator {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         _: Self::Params,
+        gas_price: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(BaselinePriceEstimator::new(
             factory.components.uniswap_v2_pools.clone(),
-            factory.components.gas_price.clone(),
+            gas_price,
             factory.network.base_tokens.clone(),
             factory.network.native_token,
             factory.native_token_price_estimation_amount()?,
@@ -404,6 +430,7 @@ impl PriceEstimatorCreating for ParaswapPriceEstimator {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         _: Self::Params,
+        _: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(ParaswapPriceEstimator::new(
             Arc::new(DefaultParaswapApi {
@@ -437,6 +464,7 @@ impl PriceEstimatorCreating for ZeroExPriceEstimator {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         _: Self::Params,
+        _: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(ZeroExPriceEstimator::new(
             factory.components.zeroex.clone(),
@@ -458,6 +486,7 @@ impl PriceEstimatorCreating for OneInchPriceEstimator {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         _: Self::Params,
+        _: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(OneInchPriceEstimator::new(
             factory
@@ -484,6 +513,7 @@ impl PriceEstimatorCreating for BalancerSor {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         _: Self::Params,
+        gas_price: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(BalancerSor::new(
             Arc::new(DefaultBalancerSorApi::new(
@@ -496,7 +526,7 @@ impl PriceEstimatorCreating for BalancerSor {
                 factory.network.chain_id,
             )?),
             factory.rate_limiter(kind),
-            factory.components.gas_price.clone(),
+            gas_price,
         ))
     }
 }
@@ -508,6 +538,7 @@ impl PriceEstimatorCreating for HttpPriceEstimator {
         factory: &PriceEstimatorFactory,
         kind: PriceEstimatorType,
         (base, solve_path): Self::Params,
+        gas_price: Arc<dyn GasPriceEstimating>,
     ) -> Result<Self> {
         Ok(HttpPriceEstimator::new(
             Arc::new(DefaultHttpSolverApi {
@@ -527,7 +558,7 @@ impl PriceEstimatorCreating for HttpPriceEstimator {
             factory.components.balancer_pools.clone(),
             factory.components.uniswap_v3_pools.clone(),
             factory.components.tokens.clone(),
-            factory.components.gas_price.clone(),
+            gas_price,
             factory.network.native_token,
             factory.network.base_tokens.clone(),
             factory.network.name.clone(),
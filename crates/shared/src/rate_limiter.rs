@@ -93,9 +93,13 @@ impl RateLimitingStrategy {
 
     /// Calculates back off based on how often we got rate limited in a row.
     fn get_current_back_off(&self) -> Duration {
-        let factor = self
-            .back_off_growth_factor
-            .powf(self.times_rate_limited as f64);
+        self.back_off(self.times_rate_limited)
+    }
+
+    /// Calculates the back off after the specified number of failed attempts
+    /// in a row, without affecting the state of the strategy.
+    pub fn back_off(&self, attempts: u64) -> Duration {
+        let factor = self.back_off_growth_factor.powf(attempts as f64);
         let back_off_secs = self.min_back_off.as_secs_f64() * factor;
         std::cmp::min(
             Duration::try_from_secs_f64(back_off_secs).unwrap_or(self.max_back_off),
@@ -264,6 +268,20 @@ mod tests {
         assert_eq!(Duration::from_millis(16 * 8), back_off);
     }
 
+    #[test]
+    fn back_off_grows_with_attempts() {
+        let strategy = RateLimitingStrategy::try_new(
+            2.0,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        )
+        .unwrap();
+        assert_eq!(strategy.back_off(0), Duration::from_millis(10));
+        assert_eq!(strategy.back_off(2), Duration::from_millis(40));
+        assert_eq!(strategy.back_off(3), Duration::from_millis(50));
+        assert_eq!(strategy.get_current_back_off(), Duration::from_millis(10));
+    }
+
     #[tokio::test]
     async fn drops_requests_correctly() {
         let strategy = RateLimitingStrategy::try_new(
@@ -60,6 +60,10 @@ impl Inner {
                 slippage_percentage: None,
                 excluded_sources: self.excluded_sources.clone(),
                 enable_slippage_protection: false,
+                skip_validation: None,
+                taker_address: None,
+                sell_entire_balance: None,
+                intent_on_filling: None,
             })
             .await?;
 
@@ -102,7 +106,8 @@ impl From<ZeroExResponseError> for TradeError {
             | ZeroExResponseError::UnknownZeroExError(_)
             | ZeroExResponseError::DeserializeError(_, _)
             | ZeroExResponseError::TextFetch(_)
-            | ZeroExResponseError::Send(_) => TradeError::Other(err.into()),
+            | ZeroExResponseError::Send(_)
+            | ZeroExResponseError::Timeout(_) => TradeError::Other(err.into()),
         }
     }
 }
@@ -26,6 +26,7 @@ use {
     std::{
         collections::HashSet,
         fmt::{self, Display, Formatter},
+        time::Duration,
     },
     thiserror::Error,
 };
@@ -64,6 +65,22 @@ pub struct SwapQuery {
     /// Requests trade routes which aim to protect against high slippage and MEV
     /// attacks.
     pub enable_slippage_protection: bool,
+    /// Whether 0x should skip validating the swap transaction. When left
+    /// unspecified, the client's default is used, falling back to skipping
+    /// validation since it requires a `taker_address` holding the sell tokens.
+    pub skip_validation: Option<bool>,
+    /// Address which will fill the quote.
+    pub taker_address: Option<H160>,
+    /// Sells the taker's entire balance of `sell_token` instead of a fixed
+    /// `sell_amount`. This requires `taker_address` to be set and causes the
+    /// `sell_amount` to be ignored.
+    pub sell_entire_balance: Option<bool>,
+    /// Signals that the quote will actually be filled, which makes RFQ makers
+    /// provide binding quotes. This is only sent when set to `Some(true)`
+    /// together with a `taker_address`, as makers need to know who fills the
+    /// quote. Since binding quotes are expected to be filled, they should be
+    /// validated by 0x, i.e. `skip_validation` should be `Some(false)`.
+    pub intent_on_filling: Option<bool>,
 }
 
 impl SwapQuery {
@@ -81,7 +98,10 @@ impl SwapQuery {
                 "enableSlippageProtection",
                 &self.enable_slippage_protection.to_string(),
             );
-        if let Some(amount) = self.sell_amount {
+        if self.sell_entire_balance == Some(true) {
+            url.query_pairs_mut()
+                .append_pair("sellEntireBalance", "true");
+        } else if let Some(amount) = self.sell_amount {
             url.query_pairs_mut()
                 .append_pair("sellAmount", &amount.to_string());
         }
@@ -97,14 +117,23 @@ impl SwapQuery {
             url.query_pairs_mut()
                 .append_pair("excludedSources", &self.excluded_sources.join(","));
         }
+        if let Some(taker_address) = self.taker_address {
+            url.query_pairs_mut()
+                .append_pair("takerAddress", &addr2str(taker_address));
+        }
         url.query_pairs_mut()
             .append_pair("affiliateAddress", AFFILIATE_ADDRESS);
-        // We do not provide a takerAddress so validation does not make sense.
-        url.query_pairs_mut().append_pair("skipValidation", "true");
-        // Ensure that we do not request binding quotes that we might be penalized for
-        // not taking.
-        url.query_pairs_mut()
-            .append_pair("intentOnFilling", "false");
+        // Skip validation unless explicitly requested, since it only makes
+        // sense when the `takerAddress` is known to hold the sell tokens.
+        url.query_pairs_mut().append_pair(
+            "skipValidation",
+            &self.skip_validation.unwrap_or(true).to_string(),
+        );
+        // Only request binding quotes when explicitly asked to, as we might be
+        // penalized for not taking them. 0x defaults to non-binding quotes.
+        if self.intent_on_filling == Some(true) && self.taker_address.is_some() {
+            url.query_pairs_mut().append_pair("intentOnFilling", "true");
+        }
         url
     }
 }
@@ -304,10 +333,24 @@ pub struct PriceResponse {
     pub allowance_target: H160,
     #[serde_as(as = "DisplayFromStr")]
     pub price: f64,
-    #[serde_as(as = "DisplayFromStr")]
+    #[serde(deserialize_with = "deserialize_truncated_decimal")]
     pub estimated_gas: u64,
 }
 
+/// Deserializes a stringified number, truncating any fractional part. 0x
+/// occasionally formats integer fields as decimals (e.g. `"111000.0"`).
+fn deserialize_truncated_decimal<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    let integer = match value.split_once('.') {
+        Some((integer, fraction)) if fraction.bytes().all(|b| b.is_ascii_digit()) => integer,
+        _ => &value,
+    };
+    integer.parse().map_err(serde::de::Error::custom)
+}
+
 /// A Ox API `swap` response.
 #[derive(Clone, Default, Derivative, Deserialize, PartialEq)]
 #[derivative(Debug)]
@@ -355,6 +398,7 @@ pub trait ZeroExApi: Send + Sync {
 pub struct DefaultZeroExApi {
     client: Client,
     base_url: Url,
+    default_skip_validation: Option<bool>,
 }
 
 impl DefaultZeroExApi {
@@ -387,6 +431,7 @@ impl DefaultZeroExApi {
         Ok(Self {
             client,
             base_url: base_url.into_url().context("zeroex api url")?,
+            default_skip_validation: None,
         })
     }
 
@@ -395,9 +440,27 @@ impl DefaultZeroExApi {
         Self {
             client,
             base_url: Self::DEFAULT_URL.parse().unwrap(),
+            default_skip_validation: None,
         }
     }
 
+    /// Sets the `skipValidation` value used for swap queries that do not
+    /// specify one themselves.
+    pub fn with_default_skip_validation(mut self, skip_validation: bool) -> Self {
+        self.default_skip_validation = Some(skip_validation);
+        self
+    }
+
+    /// Create a new 0x HTTP API client with the specified base URL whose
+    /// requests fail after `timeout`.
+    pub fn with_timeout(base_url: impl IntoUrl, timeout: Duration) -> Result<Self> {
+        Ok(Self {
+            client: HttpClientFactory::default().configure(|builder| builder.timeout(timeout)),
+            base_url: base_url.into_url().context("zeroex api url")?,
+            default_skip_validation: None,
+        })
+    }
+
     /// Create a 0x HTTP API client using the default URL and HTTP client.
     pub fn test() -> Self {
         Self::new(&HttpClientFactory::default(), Self::DEFAULT_URL, None).unwrap()
@@ -416,6 +479,16 @@ impl DefaultZeroExApi {
             .append_pair("perPage", &results_per_page.to_string());
         self.request(url).await
     }
+
+    /// Encodes a swap query for the specified endpoint, applying the client
+    /// defaults for any parameters the query leaves unspecified.
+    fn swap_url(&self, query: &SwapQuery, endpoint: &str) -> Url {
+        SwapQuery {
+            skip_validation: query.skip_validation.or(self.default_skip_validation),
+            ..query.clone()
+        }
+        .format_url(&self.base_url, endpoint)
+    }
 }
 
 impl Default for DefaultZeroExApi {
@@ -452,18 +525,30 @@ pub enum ZeroExResponseError {
     // Connectivity or non-response error
     #[error("Failed on send")]
     Send(reqwest::Error),
+
+    // The request did not complete within the client's timeout
+    #[error("request timed out")]
+    Timeout(reqwest::Error),
+}
+
+impl ZeroExResponseError {
+    fn from_reqwest(err: reqwest::Error, default: fn(reqwest::Error) -> Self) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err)
+        } else {
+            default(err)
+        }
+    }
 }
 
 #[async_trait::async_trait]
 impl ZeroExApi for DefaultZeroExApi {
     async fn get_swap(&self, query: SwapQuery) -> Result<SwapResponse, ZeroExResponseError> {
-        self.request(query.format_url(&self.base_url, "quote"))
-            .await
+        self.request(self.swap_url(&query, "quote")).await
     }
 
     async fn get_price(&self, query: SwapQuery) -> Result<PriceResponse, ZeroExResponseError> {
-        self.request(query.format_url(&self.base_url, "price"))
-            .await
+        self.request(self.swap_url(&query, "price")).await
     }
 
     async fn get_orders(
@@ -521,10 +606,12 @@ impl DefaultZeroExApi {
         let response_text = request
             .send()
             .await
-            .map_err(ZeroExResponseError::Send)?
+            .map_err(|err| ZeroExResponseError::from_reqwest(err, ZeroExResponseError::Send))?
             .text()
             .await
-            .map_err(ZeroExResponseError::TextFetch)?;
+            .map_err(|err| {
+                ZeroExResponseError::from_reqwest(err, ZeroExResponseError::TextFetch)
+            })?;
         tracing::trace!("Response from 0x API: {}", response_text);
 
         match serde_json::from_str::<RawResponse<T>>(&response_text) {
@@ -545,7 +632,13 @@ impl DefaultZeroExApi {
 
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::addr, chrono::TimeZone};
+    use {
+        super::*,
+        crate::addr,
+        chrono::TimeZone,
+        std::collections::HashMap,
+        tokio::net::TcpListener,
+    };
 
     #[tokio::test]
     #[ignore]
@@ -559,6 +652,10 @@ mod tests {
             slippage_percentage: Some(Slippage::new(0.012345678)),
             excluded_sources: Vec::new(),
             enable_slippage_protection: false,
+            skip_validation: None,
+            taker_address: None,
+            sell_entire_balance: None,
+            intent_on_filling: None,
         };
 
         let price_response = zeroex_client.get_swap(swap_query).await;
@@ -581,6 +678,10 @@ mod tests {
             slippage_percentage: Some(Slippage::ONE_PERCENT),
             excluded_sources: Vec::new(),
             enable_slippage_protection: false,
+            skip_validation: None,
+            taker_address: None,
+            sell_entire_balance: None,
+            intent_on_filling: None,
         };
 
         let price_response = zeroex_client.get_price(swap_query.clone()).await;
@@ -603,6 +704,10 @@ mod tests {
             slippage_percentage: Some(Slippage::ONE_PERCENT),
             excluded_sources: Vec::new(),
             enable_slippage_protection: false,
+            skip_validation: None,
+            taker_address: None,
+            sell_entire_balance: None,
+            intent_on_filling: None,
         };
 
         let swap = zeroex.get_swap(query.clone()).await;
@@ -619,6 +724,110 @@ mod tests {
         assert!(swap.is_ok());
     }
 
+    #[test]
+    fn swap_query_uses_default_skip_validation() {
+        let skip_validation = |api: &DefaultZeroExApi, query: &SwapQuery| {
+            api.swap_url(query, "quote")
+                .query_pairs()
+                .find(|(key, _)| key == "skipValidation")
+                .map(|(_, value)| value.into_owned())
+                .unwrap()
+        };
+        let query = SwapQuery::default();
+
+        let api = DefaultZeroExApi::default();
+        assert_eq!(skip_validation(&api, &query), "true");
+
+        let api = DefaultZeroExApi::default().with_default_skip_validation(false);
+        assert_eq!(skip_validation(&api, &query), "false");
+        assert_eq!(
+            skip_validation(
+                &api,
+                &SwapQuery {
+                    skip_validation: Some(true),
+                    ..query
+                }
+            ),
+            "true"
+        );
+    }
+
+    #[test]
+    fn swap_query_sell_entire_balance() {
+        let query = SwapQuery {
+            sell_amount: Some(1337.into()),
+            taker_address: Some(H160([0x42; 20])),
+            sell_entire_balance: Some(true),
+            ..Default::default()
+        };
+        let url = query.format_url(&DefaultZeroExApi::DEFAULT_URL.parse().unwrap(), "quote");
+        let params = url.query_pairs().collect::<HashMap<_, _>>();
+
+        assert_eq!(params["sellEntireBalance"], "true");
+        assert_eq!(
+            params["takerAddress"],
+            "0x4242424242424242424242424242424242424242"
+        );
+        assert!(!params.contains_key("sellAmount"));
+
+        let url = SwapQuery {
+            sell_entire_balance: Some(false),
+            ..query
+        }
+        .format_url(&DefaultZeroExApi::DEFAULT_URL.parse().unwrap(), "quote");
+        let params = url.query_pairs().collect::<HashMap<_, _>>();
+
+        assert!(!params.contains_key("sellEntireBalance"));
+        assert_eq!(params["sellAmount"], "1337");
+    }
+
+    #[test]
+    fn swap_query_intent_on_filling() {
+        let base_url = DefaultZeroExApi::DEFAULT_URL.parse().unwrap();
+        let intent_on_filling = |query: &SwapQuery| {
+            query
+                .format_url(&base_url, "quote")
+                .query_pairs()
+                .find(|(key, _)| key == "intentOnFilling")
+                .map(|(_, value)| value.into_owned())
+        };
+
+        assert_eq!(intent_on_filling(&SwapQuery::default()), None);
+        assert_eq!(
+            intent_on_filling(&SwapQuery {
+                taker_address: Some(H160([0x42; 20])),
+                intent_on_filling: Some(true),
+                ..Default::default()
+            }),
+            Some("true".to_owned())
+        );
+        assert_eq!(
+            intent_on_filling(&SwapQuery {
+                intent_on_filling: Some(true),
+                ..Default::default()
+            }),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn request_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::task::spawn(async move {
+            let mut connections = Vec::new();
+            loop {
+                // Accept connections but never respond to them.
+                let (stream, _) = listener.accept().await.unwrap();
+                connections.push(stream);
+            }
+        });
+
+        let api = DefaultZeroExApi::with_timeout(url, Duration::from_millis(100)).unwrap();
+        let result = api.get_price(SwapQuery::default()).await;
+        assert!(matches!(result, Err(ZeroExResponseError::Timeout(_))));
+    }
+
     #[tokio::test]
     #[ignore]
     async fn test_get_orders() {
@@ -771,6 +980,23 @@ mod tests {
             );
     }
 
+    #[test]
+    fn deserialize_decimal_estimated_gas() {
+        let price = serde_json::from_str::<PriceResponse>(
+            r#"{"price":"13.121","allowanceTarget":"0xdef1c0ded9bec7f1a1670819833240f027b25eff","buyAmount":"1312100257517027783","sellAmount":"100000000000000000","estimatedGas":"111000.0"}"#,
+        )
+        .unwrap();
+        assert_eq!(price.estimated_gas, 111000);
+
+        let estimated_gas = |value: &str| {
+            deserialize_truncated_decimal(serde_json::Value::String(value.to_string())).ok()
+        };
+        assert_eq!(estimated_gas("111000"), Some(111000));
+        assert_eq!(estimated_gas("111000.75"), Some(111000));
+        assert_eq!(estimated_gas("111000.0x"), None);
+        assert_eq!(estimated_gas("-1"), None);
+    }
+
     #[test]
     fn compute_remaining_maker_amount() {
         let bogous_order = OrderRecord {
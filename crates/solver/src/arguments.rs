@@ -141,7 +141,7 @@ pub struct Arguments {
         long,
         env,
         default_value = "1500",
-        value_parser = shared::arguments::wei_from_gwei
+        value_parser = wei_f64_from_gwei
     )]
     pub gas_price_cap: f64,
 
@@ -202,7 +202,7 @@ pub struct Arguments {
         long,
         env,
         default_value = "3",
-        value_parser = shared::arguments::wei_from_gwei
+        value_parser = wei_f64_from_gwei
     )]
     pub max_additional_eden_tip: f64,
 
@@ -222,7 +222,7 @@ pub struct Arguments {
         long,
         env,
         default_value = "3",
-        value_parser = shared::arguments::wei_from_gwei
+        value_parser = wei_f64_from_gwei
     )]
     pub max_additional_flashbot_tip: f64,
 
@@ -442,3 +442,10 @@ pub enum TransactionStrategyArg {
     Gelato,
     DryRun,
 }
+
+/// Parses a gwei amount into wei. The amount is converted exactly and only then
+/// turned into a float, so unlike the old float based parsing, amounts with
+/// more than 9 decimals or in exponent notation (e.g. `1e3`) are rejected.
+fn wei_f64_from_gwei(s: &str) -> anyhow::Result<f64> {
+    shared::arguments::wei_from_gwei_u256(s).map(|wei| wei.to_f64_lossy())
+}
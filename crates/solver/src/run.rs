@@ -121,7 +121,7 @@ pub async fn run(args: Arguments) {
     };
     let base_tokens = Arc::new(BaseTokens::new(
         native_token.address(),
-        &args.shared.base_tokens,
+        &args.shared.effective_base_tokens(),
     ));
 
     let block_retriever = args.shared.current_block.retriever(web3.clone());
@@ -133,6 +133,7 @@ pub async fn run(args: Arguments) {
             &http_factory,
             &web3,
             args.shared.gas_estimators.as_slice(),
+            args.shared.gas_estimation_mode,
             args.shared.blocknative_api_key,
         )
         .await
@@ -109,6 +109,10 @@ impl SingleOrderSolving for ZeroExSolver {
             )),
             excluded_sources: self.excluded_sources.clone(),
             enable_slippage_protection: false,
+            skip_validation: None,
+            taker_address: None,
+            sell_entire_balance: None,
+            intent_on_filling: None,
         };
         let swap = match self.api.get_swap(query).await {
             Ok(swap) => swap,
@@ -62,6 +62,8 @@ impl Auction {
                         fee: order::Fee(order.fee_amount),
                         partially_fillable: order.partially_fillable,
                         reward: order::Reward(order.reward),
+                        receiver: order.receiver,
+                        valid_to: order.valid_to,
                     }
                 })
                 .collect(),
@@ -113,6 +115,10 @@ struct Order {
     partially_fillable: bool,
     class: Class,
     reward: f64,
+    #[serde(default)]
+    receiver: Option<H160>,
+    #[serde(default)]
+    valid_to: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
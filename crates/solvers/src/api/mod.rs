@@ -4,6 +4,7 @@ use {
     crate::domain::solver::Solver,
     std::{future::Future, net::SocketAddr, sync::Arc},
     tokio::sync::oneshot,
+    tokio_util::sync::CancellationToken,
 };
 
 pub mod dto;
@@ -53,9 +54,24 @@ async fn solve(
         }
     };
 
-    let solution = state
-        .solve(auction)
-        .await
+    // Stop solving once the auction deadline is reached.
+    let cancel = CancellationToken::new();
+    let timer = tokio::spawn({
+        let cancel = cancel.clone();
+        let remaining = (auction.deadline - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        async move {
+            tokio::time::sleep(remaining).await;
+            cancel.cancel();
+        }
+    });
+    let outcome = state.solve(auction, &cancel).await;
+    timer.abort();
+    tracing::debug!(skipped = ?outcome.skipped, "skipped orders");
+
+    let solution = outcome
+        .solutions
         .first()
         .map(dto::Solution::from_domain)
         .unwrap_or_else(dto::Solution::trivial);
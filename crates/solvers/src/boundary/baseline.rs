@@ -3,7 +3,12 @@
 use {
     crate::{
         boundary,
-        domain::{eth, liquidity, order, solver::baseline},
+        domain::{
+            eth,
+            liquidity,
+            order,
+            solver::{baseline, SkipReason},
+        },
     },
     ethereum_types::{H160, U256},
     model::TokenPair,
@@ -14,6 +19,7 @@ use {
 pub struct Solver<'a> {
     base_tokens: BaseTokens,
     amms: HashMap<TokenPair, Vec<Amm>>,
+    transfer_fees: HashMap<H160, u16>,
     liquidity: HashMap<liquidity::Id, &'a liquidity::Liquidity>,
 }
 
@@ -21,11 +27,16 @@ impl<'a> Solver<'a> {
     pub fn new(
         weth: &eth::WethAddress,
         base_tokens: &HashSet<eth::TokenAddress>,
+        transfer_fee_bps: &HashMap<eth::TokenAddress, u16>,
         liquidity: &'a [liquidity::Liquidity],
     ) -> Self {
         Self {
             base_tokens: to_boundary_base_tokens(weth, base_tokens),
-            amms: to_boundary_amms(liquidity),
+            amms: to_boundary_amms(liquidity, transfer_fee_bps),
+            transfer_fees: transfer_fee_bps
+                .iter()
+                .map(|(token, fee)| (token.0, *fee))
+                .collect(),
             liquidity: liquidity
                 .iter()
                 .map(|liquidity| (liquidity.id.clone(), liquidity))
@@ -37,7 +48,7 @@ impl<'a> Solver<'a> {
         &self,
         order: order::NonLiquidity,
         max_hops: usize,
-    ) -> Option<baseline::Route<'a>> {
+    ) -> Result<baseline::Route<'a>, SkipReason> {
         let candidates = self.base_tokens.path_candidates_with_hops(
             order.get().sell.token.0,
             order.get().buy.token.0,
@@ -45,38 +56,80 @@ impl<'a> Solver<'a> {
         );
 
         let order = order.get();
-        let (path, executed_sell_amount) = match order.side {
+        let sell_token = order.sell.token.0;
+        // `input` is the amount the user transfers into the settlement
+        // contract, while `swap_input` is the amount that the settlement
+        // contract has available to swap after the sell token's transfer fee.
+        let (path, input, swap_input) = match order.side {
             order::Side::Buy => {
-                let best = candidates
+                let estimates = candidates
                     .iter()
                     .filter_map(|path| {
                         baseline_solver::estimate_sell_amount(order.buy.amount, path, &self.amms)
                     })
-                    .filter(|estimate| estimate.value <= order.sell.amount)
-                    .min_by_key(|estimate| estimate.value)?;
-                (best.path, best.value)
+                    .collect::<Vec<_>>();
+                if estimates.is_empty() {
+                    return Err(SkipReason::NoLiquidity);
+                }
+                let (best, input) = estimates
+                    .into_iter()
+                    .filter_map(|estimate| {
+                        let input = sent(self.transfer_fee_bps(sell_token), estimate.value)?;
+                        (input <= order.sell.amount).then_some((estimate, input))
+                    })
+                    .min_by_key(|(_, input)| *input)
+                    .ok_or(SkipReason::LimitPrice)?;
+                (best.path, input, best.value)
             }
             order::Side::Sell => {
-                let best = candidates
+                let swap_input = received(self.transfer_fee_bps(sell_token), order.sell.amount)
+                    .ok_or(SkipReason::NoLiquidity)?;
+                let estimates = candidates
                     .iter()
                     .filter_map(|path| {
-                        baseline_solver::estimate_buy_amount(order.sell.amount, path, &self.amms)
+                        baseline_solver::estimate_buy_amount(swap_input, path, &self.amms)
                     })
+                    .collect::<Vec<_>>();
+                if estimates.is_empty() {
+                    return Err(SkipReason::NoLiquidity);
+                }
+                let best = estimates
+                    .into_iter()
                     .filter(|estimate| estimate.value >= order.buy.amount)
-                    .max_by_key(|estimate| estimate.value)?;
-                (best.path, order.sell.amount)
+                    .max_by_key(|estimate| estimate.value)
+                    .ok_or(SkipReason::LimitPrice)?;
+                (best.path, order.sell.amount, swap_input)
             }
         };
 
-        baseline::Route::new(self.traverse_path(&path, order.sell.token.0, executed_sell_amount)?)
+        let (segments, output) = self
+            .traverse_path(&path, sell_token, swap_input)
+            .ok_or(SkipReason::NoLiquidity)?;
+        baseline::Route::new(
+            eth::Asset {
+                token: order.sell.token,
+                amount: input,
+            },
+            segments,
+            output,
+        )
+        .ok_or(SkipReason::NoLiquidity)
+    }
+
+    fn transfer_fee_bps(&self, token: H160) -> u16 {
+        self.transfer_fees.get(&token).copied().unwrap_or_default()
     }
 
+    /// Computes the segments of a path along with the amount of the final
+    /// token that the settlement contract receives. Segments use the gross
+    /// amounts sent to and from the liquidity, while transfer fees are only
+    /// deducted from the amounts received by the settlement contract.
     fn traverse_path(
         &self,
         path: &[&Amm],
         mut sell_token: H160,
         mut sell_amount: U256,
-    ) -> Option<Vec<baseline::Segment<'a>>> {
+    ) -> Option<(Vec<baseline::Segment<'a>>, eth::Asset)> {
         let mut segments = Vec::new();
         for liquidity in path {
             let reference_liquidity = self
@@ -88,7 +141,7 @@ impl<'a> Solver<'a> {
                 .token_pair
                 .other(&sell_token)
                 .expect("Inconsistent path");
-            let buy_amount = liquidity.get_amount_out(buy_token, (sell_amount, sell_token))?;
+            let buy_amount = liquidity.gross_amount_out(buy_token, (sell_amount, sell_token))?;
 
             segments.push(baseline::Segment {
                 liquidity: reference_liquidity,
@@ -103,13 +156,30 @@ impl<'a> Solver<'a> {
             });
 
             sell_token = buy_token;
-            sell_amount = buy_amount;
+            sell_amount = received(liquidity.transfer_fee_bps(buy_token), buy_amount)?;
         }
-        Some(segments)
+        Some((
+            segments,
+            eth::Asset {
+                token: eth::TokenAddress(sell_token),
+                amount: sell_amount,
+            },
+        ))
     }
 }
 
-fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Vec<Amm>> {
+fn to_boundary_amms(
+    liquidity: &[liquidity::Liquidity],
+    transfer_fee_bps: &HashMap<eth::TokenAddress, u16>,
+) -> HashMap<TokenPair, Vec<Amm>> {
+    let transfer_fees = |token_pair: TokenPair| {
+        let (a, b) = token_pair.get();
+        [a, b]
+            .into_iter()
+            .filter_map(|token| Some((token, *transfer_fee_bps.get(&eth::TokenAddress(token))?)))
+            .collect()
+    };
+
     liquidity
         .iter()
         .fold(HashMap::new(), |mut amms, liquidity| {
@@ -124,6 +194,7 @@ fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Ve
                         amms.entry(boundary_pool.tokens).or_default().push(Amm {
                             id: liquidity.id.clone(),
                             token_pair: boundary_pool.tokens,
+                            transfer_fees: transfer_fees(boundary_pool.tokens),
                             pool: Pool::ConstantProduct(boundary_pool),
                         });
                     }
@@ -140,6 +211,7 @@ fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Ve
                             amms.entry(token_pair).or_default().push(Amm {
                                 id: liquidity.id.clone(),
                                 token_pair,
+                                transfer_fees: transfer_fees(token_pair),
                                 pool: Pool::WeightedProduct(boundary_pool.clone()),
                             });
                         }
@@ -155,9 +227,50 @@ fn to_boundary_amms(liquidity: &[liquidity::Liquidity]) -> HashMap<TokenPair, Ve
 struct Amm {
     id: liquidity::Id,
     token_pair: TokenPair,
+    /// Transfer fees in basis points for the pair's fee-on-transfer tokens.
+    transfer_fees: HashMap<H160, u16>,
     pool: Pool,
 }
 
+impl Amm {
+    fn transfer_fee_bps(&self, token: H160) -> u16 {
+        self.transfer_fees.get(&token).copied().unwrap_or_default()
+    }
+
+    /// Returns the amount the pool sends when `input` is transferred to it.
+    /// The pool only receives the input amount net of its transfer fee.
+    fn gross_amount_out(&self, out_token: H160, input: (U256, H160)) -> Option<U256> {
+        let (amount_in, in_token) = input;
+        let input = (
+            received(self.transfer_fee_bps(in_token), amount_in)?,
+            in_token,
+        );
+        match &self.pool {
+            Pool::ConstantProduct(pool) => pool.get_amount_out(out_token, input),
+            Pool::WeightedProduct(pool) => pool.get_amount_out(out_token, input),
+        }
+    }
+}
+
+const MAX_BPS: u64 = 10_000;
+
+/// Returns the amount received when transferring `amount` of a token with
+/// the specified transfer fee.
+fn received(fee_bps: u16, amount: U256) -> Option<U256> {
+    let net = U256::from(MAX_BPS).checked_sub(fee_bps.into())?;
+    Some(amount.checked_mul(net)? / MAX_BPS)
+}
+
+/// Returns the amount that needs to be transferred of a token with the
+/// specified transfer fee so that at least `amount` is received.
+fn sent(fee_bps: u16, amount: U256) -> Option<U256> {
+    let net = U256::from(MAX_BPS).checked_sub(fee_bps.into())?;
+    amount
+        .checked_mul(MAX_BPS.into())?
+        .checked_add(net.checked_sub(1.into())?)?
+        .checked_div(net)
+}
+
 enum Pool {
     ConstantProduct(boundary::liquidity::constant_product::Pool),
     WeightedProduct(boundary::liquidity::weighted_product::Pool),
@@ -165,17 +278,25 @@ enum Pool {
 
 impl BaselineSolvable for Amm {
     fn get_amount_out(&self, out_token: H160, input: (U256, H160)) -> Option<U256> {
-        match &self.pool {
-            Pool::ConstantProduct(pool) => pool.get_amount_out(out_token, input),
-            Pool::WeightedProduct(pool) => pool.get_amount_out(out_token, input),
-        }
+        // Only the amount net of the transfer fee is actually received.
+        let amount_out = self.gross_amount_out(out_token, input)?;
+        received(self.transfer_fee_bps(out_token), amount_out)
     }
 
     fn get_amount_in(&self, in_token: H160, out: (U256, H160)) -> Option<U256> {
-        match &self.pool {
+        // The pool needs to send enough so that the requested amount is
+        // received after the transfer fee, and needs to receive enough of the
+        // input token after its transfer fee.
+        let (amount_out, out_token) = out;
+        let out = (
+            sent(self.transfer_fee_bps(out_token), amount_out)?,
+            out_token,
+        );
+        let amount_in = match &self.pool {
             Pool::ConstantProduct(pool) => pool.get_amount_in(in_token, out),
             Pool::WeightedProduct(pool) => pool.get_amount_in(in_token, out),
-        }
+        }?;
+        sent(self.transfer_fee_bps(in_token), amount_in)
     }
 
     fn gas_cost(&self) -> usize {
@@ -5,7 +5,7 @@ use {
 };
 
 /// The auction that the solvers need to find solutions to.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Auction {
     pub id: Option<Id>,
     pub tokens: HashMap<eth::TokenAddress, Token>,
@@ -15,11 +15,44 @@ pub struct Auction {
     pub deadline: chrono::DateTime<chrono::Utc>,
 }
 
+impl Auction {
+    /// Retains at most `max` orders with the highest estimated value, i.e. the
+    /// value of their sell amount in the native token. Orders whose sell token
+    /// has no reference price are considered to have no value. Returns the
+    /// UIDs of the dropped orders.
+    pub fn retain_most_valuable_orders(&mut self, max: usize) -> Vec<order::Uid> {
+        if self.orders.len() <= max {
+            return Vec::new();
+        }
+
+        let value = |order: &order::Order| {
+            self.tokens
+                .get(&order.sell.token)
+                .and_then(|token| token.reference_price)
+                .and_then(|price| price.0 .0.checked_mul(order.sell.amount))
+                .unwrap_or_default()
+        };
+        let mut orders = std::mem::take(&mut self.orders)
+            .into_iter()
+            .map(|order| (value(&order), order))
+            .collect::<Vec<_>>();
+        orders.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let dropped = orders
+            .split_off(max)
+            .into_iter()
+            .map(|(_, order)| order.uid)
+            .collect();
+        self.orders = orders.into_iter().map(|(_, order)| order).collect();
+        dropped
+    }
+}
+
 /// The ID of an auction.
 #[derive(Clone, Debug)]
 pub struct Id(pub String);
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Token {
     pub decimals: Option<u8>,
     pub symbol: Option<String>,
@@ -7,8 +7,8 @@ use {
         domain::{eth, order, solution},
         util::fmt,
     },
-    ethereum_types::U256,
-    std::fmt::{Debug, Formatter},
+    ethereum_types::{U256, U512},
+    std::fmt::{Debug, Display, Formatter},
 };
 
 pub mod slippage;
@@ -95,14 +95,18 @@ impl Swap {
     }
 
     /// Constructs a single order `solution::Solution` for this swap. Returns
-    /// `None` if the swap is not valid for the specified order.
-    pub fn into_solution(self, order: order::Order) -> Option<solution::Solution> {
-        if !self.matches_order(&order) || !self.respects_price(&order) {
-            return None;
+    /// the reason for rejecting the swap if it is not valid for the specified
+    /// order.
+    pub fn into_solution(self, order: order::Order) -> Result<solution::Solution, Rejection> {
+        if !self.matches_order(&order) {
+            return Err(Rejection::Mismatch);
+        }
+        if !self.respects_price(&order) {
+            return Err(self.limit_price_rejection(&order));
         }
 
         let allowance = self.allowance();
-        Some(solution::Solution {
+        Ok(solution::Solution {
             prices: solution::ClearingPrices::new([
                 (self.input.token, self.output.amount),
                 (self.output.token, self.input.amount),
@@ -140,11 +144,72 @@ impl Swap {
 
     fn respects_price(&self, order: &order::Order) -> bool {
         // Note the use of checked multiplication - this is consistent with the
-        // on-chain limit price check.
+        // on-chain limit price check. The limit price itself is inclusive, so
+        // swaps executing exactly at the limit price are accepted.
         let sell = order.sell.amount.checked_mul(self.output.amount);
         let buy = order.buy.amount.checked_mul(self.input.amount);
         matches!((sell, buy), (Some(sell), Some(buy)) if sell >= buy)
     }
+
+    /// Describes how the swap violates the order's limit price. For sell
+    /// orders this is the minimum output for the swap's input, and for buy
+    /// orders the maximum input for the swap's output.
+    fn limit_price_rejection(&self, order: &order::Order) -> Rejection {
+        let (quoted, limit) = match order.side {
+            order::Side::Sell => {
+                let numer = order.buy.amount.full_mul(self.input.amount);
+                let denom = U512::from(order.sell.amount);
+                (
+                    self.output.amount,
+                    (numer + denom).checked_sub(U512::one()).map(|n| n / denom),
+                )
+            }
+            order::Side::Buy => (
+                self.input.amount,
+                Some(order.sell.amount.full_mul(self.output.amount) / order.buy.amount),
+            ),
+        };
+        Rejection::LimitPrice {
+            side: order.side,
+            quoted,
+            limit: limit
+                .and_then(|limit| U256::try_from(limit).ok())
+                .unwrap_or_else(U256::max_value),
+        }
+    }
+}
+
+/// The reason why a swap can't be used for an order.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Rejection {
+    /// The swap does not trade the order's tokens or amount.
+    Mismatch,
+    /// The swap does not satisfy the order's limit price. For sell orders
+    /// `quoted` is the swap output and `limit` the minimum output, for buy
+    /// orders `quoted` is the swap input and `limit` the maximum input.
+    LimitPrice {
+        side: order::Side,
+        quoted: U256,
+        limit: U256,
+    },
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Mismatch => write!(f, "swap does not match order"),
+            Self::LimitPrice {
+                side: order::Side::Sell,
+                quoted,
+                limit,
+            } => write!(f, "swap output {quoted} below limit {limit}"),
+            Self::LimitPrice {
+                side: order::Side::Buy,
+                quoted,
+                limit,
+            } => write!(f, "swap input {quoted} above limit {limit}"),
+        }
+    }
 }
 
 /// A swap allowance.
@@ -170,3 +235,81 @@ impl Amount {
         self.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {super::*, ethereum_types::H160};
+
+    #[test]
+    fn limit_price_rejection() {
+        let sell_token = eth::TokenAddress(H160([1; 20]));
+        let buy_token = eth::TokenAddress(H160([2; 20]));
+        let order = |side| order::Order {
+            uid: order::Uid([0; 56]),
+            sell: eth::Asset {
+                token: sell_token,
+                amount: 100.into(),
+            },
+            buy: eth::Asset {
+                token: buy_token,
+                amount: 200.into(),
+            },
+            fee: order::Fee(0.into()),
+            side,
+            class: order::Class::Market,
+            partially_fillable: false,
+            reward: order::Reward(0.),
+            receiver: None,
+            valid_to: None,
+        };
+        let swap = |input: u64, output: u64| Swap {
+            call: Call {
+                to: eth::ContractAddress(H160([3; 20])),
+                calldata: Vec::new(),
+            },
+            input: eth::Asset {
+                token: sell_token,
+                amount: input.into(),
+            },
+            output: eth::Asset {
+                token: buy_token,
+                amount: output.into(),
+            },
+            allowance: Allowance {
+                spender: eth::ContractAddress(H160([3; 20])),
+                amount: Amount::new(input.into()),
+            },
+        };
+
+        let rejection = swap(100, 199)
+            .into_solution(order(order::Side::Sell))
+            .err()
+            .unwrap();
+        assert_eq!(
+            rejection,
+            Rejection::LimitPrice {
+                side: order::Side::Sell,
+                quoted: 199.into(),
+                limit: 200.into(),
+            }
+        );
+        assert_eq!(rejection.to_string(), "swap output 199 below limit 200");
+
+        let rejection = swap(101, 200)
+            .into_solution(order(order::Side::Buy))
+            .err()
+            .unwrap();
+        assert_eq!(rejection.to_string(), "swap input 101 above limit 100");
+
+        assert!(swap(100, 200)
+            .into_solution(order(order::Side::Sell))
+            .is_ok());
+        assert_eq!(
+            swap(100, 199)
+                .into_solution(order(order::Side::Buy))
+                .err()
+                .unwrap(),
+            Rejection::Mismatch,
+        );
+    }
+}
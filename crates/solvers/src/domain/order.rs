@@ -16,6 +16,11 @@ pub struct Order {
     pub class: Class,
     pub partially_fillable: bool,
     pub reward: Reward,
+    /// The receiver of the bought tokens, or [`None`] if it is the owner.
+    pub receiver: Option<Address>,
+    /// The Unix timestamp until which the order is valid, or [`None`] if it
+    /// never expires.
+    pub valid_to: Option<u32>,
 }
 
 impl Order {
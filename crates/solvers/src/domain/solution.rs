@@ -12,6 +12,143 @@ pub struct Solution {
     pub interactions: Vec<Interaction>,
 }
 
+impl Solution {
+    /// Returns a summary of the solution.
+    pub fn summary(&self) -> SolutionSummary {
+        SolutionSummary::new(self.trades.iter().filter_map(|trade| match trade {
+            Trade::Fulfillment(trade) => Some(trade.order()),
+            Trade::Jit(_) => None,
+        }))
+    }
+
+    /// Returns the content of the solution, i.e. its clearing prices, trades
+    /// and interactions. Solutions with equal content execute the same trades
+    /// in the same way and are interchangeable.
+    pub fn content(&self) -> Content<'_> {
+        let mut prices = self
+            .prices
+            .0
+            .iter()
+            .map(|(token, price)| (*token, *price))
+            .collect::<Vec<_>>();
+        prices.sort();
+        Content {
+            prices,
+            trades: self
+                .trades
+                .iter()
+                .map(|trade| match trade {
+                    Trade::Fulfillment(trade) => TradeContent::Fulfillment {
+                        order: trade.order.uid,
+                        executed: trade.executed,
+                    },
+                    Trade::Jit(trade) => TradeContent::Jit {
+                        owner: trade.order.owner,
+                        signature: trade.order.signature.to_bytes(),
+                        sell: asset_content(&trade.order.sell),
+                        buy: asset_content(&trade.order.buy),
+                        valid_to: trade.order.valid_to,
+                        app_data: trade.order.app_data.0,
+                        receiver: trade.order.receiver,
+                        executed: trade.executed,
+                    },
+                })
+                .collect(),
+            interactions: self
+                .interactions
+                .iter()
+                .map(|interaction| match interaction {
+                    Interaction::Liquidity(interaction) => InteractionContent::Liquidity {
+                        liquidity: &interaction.liquidity.id,
+                        input: asset_content(&interaction.input),
+                        output: asset_content(&interaction.output),
+                        internalize: interaction.internalize,
+                    },
+                    Interaction::Custom(interaction) => InteractionContent::Custom {
+                        target: interaction.target,
+                        value: interaction.value,
+                        calldata: &interaction.calldata,
+                        internalize: interaction.internalize,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The content of a solution, see [`Solution::content`].
+#[derive(Debug, Eq, Hash, PartialEq)]
+pub struct Content<'a> {
+    prices: Vec<(eth::TokenAddress, U256)>,
+    trades: Vec<TradeContent>,
+    interactions: Vec<InteractionContent<'a>>,
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+enum TradeContent {
+    Fulfillment {
+        order: order::Uid,
+        executed: U256,
+    },
+    Jit {
+        owner: Address,
+        signature: Vec<u8>,
+        sell: (eth::TokenAddress, U256),
+        buy: (eth::TokenAddress, U256),
+        valid_to: u32,
+        app_data: [u8; 32],
+        receiver: Address,
+        executed: U256,
+    },
+}
+
+#[derive(Debug, Eq, Hash, PartialEq)]
+enum InteractionContent<'a> {
+    Liquidity {
+        liquidity: &'a liquidity::Id,
+        input: (eth::TokenAddress, U256),
+        output: (eth::TokenAddress, U256),
+        internalize: bool,
+    },
+    Custom {
+        target: Address,
+        value: eth::Ether,
+        calldata: &'a [u8],
+        internalize: bool,
+    },
+}
+
+fn asset_content(asset: &eth::Asset) -> (eth::TokenAddress, U256) {
+    (asset.token, asset.amount)
+}
+
+/// A lightweight summary of a solution, containing only the orders that it
+/// matches and its score.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolutionSummary {
+    pub orders: Vec<order::Uid>,
+    /// The score of the solution, computed as the total reward of the matched
+    /// orders.
+    pub score: f64,
+}
+
+impl SolutionSummary {
+    /// Creates a new summary for a solution matching the specified orders.
+    pub fn new<'a>(orders: impl IntoIterator<Item = &'a order::Order>) -> Self {
+        orders.into_iter().fold(
+            Self {
+                orders: Vec::new(),
+                score: 0.,
+            },
+            |mut summary, order| {
+                summary.orders.push(order.uid);
+                summary.score += order.reward.0;
+                summary
+            },
+        )
+    }
+}
+
 /// A set of uniform clearing prices. They are represented as a mapping of token
 /// addresses to price in an arbitrarily denominated price.
 #[derive(Default)]
@@ -5,7 +5,8 @@ use crate::{
     domain::{
         auction,
         dex::{self, slippage},
-        solution,
+        order,
+        solver::{Baseline, SkipReason, SolveOutcome},
     },
     infra,
 };
@@ -16,32 +17,91 @@ pub struct Balancer {
 
     /// The slippage configuration to use for the solver.
     pub slippage: slippage::Limits,
+
+    /// Optional baseline solver to route orders over the auction's liquidity
+    /// when the SOR API does not find a swap for them.
+    ///
+    /// This takes precedence over the [`Balancer::naive_fallback`], which is
+    /// only used when neither the SOR API nor the baseline fallback find a
+    /// solution for any of the orders.
+    pub baseline_fallback: Option<Baseline>,
+
+    /// Solver to use for the whole auction when no solutions are found.
+    pub naive_fallback: Option<Box<super::Solver>>,
 }
 
 impl Balancer {
-    pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+    /// Solves the specified auction, returning the solutions along with the
+    /// reasons for skipping the remaining orders. Orders for which the SOR API
+    /// fails are skipped if `skip_failed_orders` is set, otherwise the SOR API
+    /// error is returned.
+    pub async fn solve(
+        &self,
+        auction: auction::Auction,
+        skip_failed_orders: bool,
+    ) -> Result<SolveOutcome, infra::dex::balancer::Error> {
         // TODO: order prioritization, skip liquidity orders, concurrency.
         let prices = slippage::Prices::for_auction(&auction);
 
-        let mut solutions = Vec::new();
-        for order in auction.orders {
-            let query = dex::Order::new(&order);
-
-            let slippage = self.slippage.relative(&query.amount(), &prices);
-            let swap = match self.sor.swap(&query, &slippage, auction.gas_price).await {
-                Ok(value) => value,
-                Err(infra::dex::balancer::Error::NotFound) => continue,
-                Err(err) => {
+        let mut outcome = SolveOutcome::default();
+        for order in &auction.orders {
+            let uid = order.uid;
+            match self
+                .solve_order(order.clone(), &auction, &prices, &mut outcome)
+                .await
+            {
+                Ok(()) => (),
+                Err(err) if skip_failed_orders => {
                     tracing::warn!(?err, "failed to get swap");
-                    continue;
+                    outcome.skipped.push((uid, SkipReason::NoSolution));
                 }
-            };
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Solves a single order, adding its solution or the reason for skipping
+    /// it to the outcome. Returns an error if the SOR API fails.
+    async fn solve_order(
+        &self,
+        order: order::Order,
+        auction: &auction::Auction,
+        prices: &slippage::Prices,
+        outcome: &mut SolveOutcome,
+    ) -> Result<(), infra::dex::balancer::Error> {
+        let query = dex::Order::new(&order);
+        let uid = order.uid;
+
+        let slippage = self.slippage.relative(&query.amount(), prices);
+        let swap = match self.sor.swap(&query, &slippage, auction.gas_price).await {
+            Ok(value) => value,
+            Err(infra::dex::balancer::Error::NotFound) => {
+                match &self.baseline_fallback {
+                    Some(baseline) => match baseline.solve_single(&order, &auction.liquidity) {
+                        Ok(solution) => outcome.solutions.push(solution),
+                        Err(reason) => outcome.skipped.push((uid, reason)),
+                    },
+                    None => outcome.skipped.push((uid, SkipReason::NoLiquidity)),
+                }
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
 
-            if let Some(solution) = swap.into_solution(order) {
-                solutions.push(solution);
+        match swap.into_solution(order) {
+            Ok(solution) => outcome.solutions.push(solution),
+            Err(rejection) => {
+                tracing::debug!(order = ?uid, %rejection, "order rejected");
+                let reason = match rejection {
+                    dex::Rejection::Mismatch => SkipReason::NoSolution,
+                    dex::Rejection::LimitPrice { .. } => SkipReason::LimitPrice,
+                };
+                outcome.skipped.push((uid, reason));
             }
         }
 
-        solutions
+        Ok(())
     }
 }
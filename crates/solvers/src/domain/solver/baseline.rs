@@ -8,9 +8,17 @@
 use {
     crate::{
         boundary,
-        domain::{auction, eth, liquidity, order, solution},
+        domain::{
+            auction,
+            eth,
+            liquidity,
+            order,
+            solution,
+            solver::{SkipReason, SolveOutcome},
+        },
     },
-    std::collections::HashSet,
+    std::collections::{HashMap, HashSet},
+    tokio_util::sync::CancellationToken,
 };
 
 pub struct Baseline {
@@ -27,52 +35,126 @@ pub struct Baseline {
     /// - A value of 2 indicates: `A -> B -> C -> D`
     /// - etc.
     pub max_hops: usize,
+    /// Transfer fees, in basis points, of fee-on-transfer tokens. Amounts of
+    /// these tokens received by the settlement contract or by a liquidity
+    /// pool are reduced by the fee.
+    pub transfer_fee_bps: HashMap<eth::TokenAddress, u16>,
+    /// Maximum number of orders to consider per auction. Larger auctions are
+    /// reduced to their most valuable orders in order to bound solve time.
+    pub max_orders: Option<usize>,
 }
 
 impl Baseline {
-    /// Solves the specified auction, returning a vector of all possible
-    /// solutions.
-    pub fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
-        let boundary_solver =
-            boundary::baseline::Solver::new(&self.weth, &self.base_tokens, &auction.liquidity);
+    /// Solves the specified auction, returning all possible solutions along
+    /// with the reasons for skipping the remaining orders. Stops solving
+    /// further orders once `cancel` is cancelled.
+    pub fn solve(&self, auction: auction::Auction, cancel: &CancellationToken) -> SolveOutcome {
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &self.transfer_fee_bps,
+            &auction.liquidity,
+        );
+
+        let mut outcome = SolveOutcome::default();
+        for order in &auction.orders {
+            if cancel.is_cancelled() {
+                break;
+            }
+            match self.solve_order(&boundary_solver, order) {
+                Ok(solution) => outcome.solutions.push(solution),
+                Err(reason) => outcome.skipped.push((order.uid, reason)),
+            }
+        }
+        outcome
+    }
+
+    /// Solves a single order over the specified liquidity. This allows other
+    /// solvers to fall back to baseline path-finding for individual orders.
+    pub fn solve_single(
+        &self,
+        order: &order::Order,
+        liquidity: &[liquidity::Liquidity],
+    ) -> Result<solution::Solution, SkipReason> {
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &self.transfer_fee_bps,
+            liquidity,
+        );
+        self.solve_order(&boundary_solver, order)
+    }
+
+    fn solve_order(
+        &self,
+        boundary_solver: &boundary::baseline::Solver,
+        order: &order::Order,
+    ) -> Result<solution::Solution, SkipReason> {
+        let route = order::NonLiquidity::new(order)
+            .ok_or(SkipReason::Filtered)
+            .and_then(|order| boundary_solver.route(order, self.max_hops))?;
+
+        Ok(solution::Solution {
+            prices: solution::ClearingPrices::new([
+                (order.sell.token, route.output.amount),
+                (order.buy.token, route.input.amount),
+            ]),
+            trades: vec![solution::Trade::Fulfillment(solution::Fulfillment::fill(
+                order.clone(),
+            ))],
+            interactions: route
+                .segments
+                .iter()
+                .map(|segment| {
+                    solution::Interaction::Liquidity(solution::LiquidityInteraction {
+                        liquidity: segment.liquidity.clone(),
+                        input: segment.input,
+                        output: segment.output,
+                        // TODO does the baseline solver know about this optimization?
+                        internalize: false,
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns a summary of the solutions for the specified auction, without
+    /// building any of the liquidity interactions. Stops previewing further
+    /// orders once `cancel` is cancelled.
+    pub fn preview(
+        &self,
+        auction: &auction::Auction,
+        cancel: &CancellationToken,
+    ) -> Vec<solution::SolutionSummary> {
+        let boundary_solver = boundary::baseline::Solver::new(
+            &self.weth,
+            &self.base_tokens,
+            &self.transfer_fee_bps,
+            &auction.liquidity,
+        );
 
         auction
             .orders
             .iter()
-            .filter_map(|order| {
-                let route =
-                    boundary_solver.route(order::NonLiquidity::new(order)?, self.max_hops)?;
-
-                Some(solution::Solution {
-                    prices: solution::ClearingPrices::new([
-                        (order.sell.token, route.output().amount),
-                        (order.buy.token, route.input().amount),
-                    ]),
-                    trades: vec![solution::Trade::Fulfillment(solution::Fulfillment::fill(
-                        order.clone(),
-                    ))],
-                    interactions: route
-                        .segments
-                        .iter()
-                        .map(|segment| {
-                            solution::Interaction::Liquidity(solution::LiquidityInteraction {
-                                liquidity: segment.liquidity.clone(),
-                                input: segment.input,
-                                output: segment.output,
-                                // TODO does the baseline solver know about this optimization?
-                                internalize: false,
-                            })
-                        })
-                        .collect(),
+            .take_while(|_| !cancel.is_cancelled())
+            .filter(|order| {
+                order::NonLiquidity::new(order).map_or(false, |order| {
+                    boundary_solver.route(order, self.max_hops).is_ok()
                 })
             })
+            .map(|order| solution::SolutionSummary::new([order]))
             .collect()
     }
 }
 
 /// A trading route.
 pub struct Route<'a> {
+    /// The amount transferred into the settlement contract by the user.
+    input: eth::Asset,
     segments: Vec<Segment<'a>>,
+    /// The amount received by the settlement contract from the last segment,
+    /// net of any transfer fees.
+    output: eth::Asset,
 }
 
 /// A segment in a trading route.
@@ -89,21 +171,235 @@ pub struct Segment<'a> {
 }
 
 impl<'a> Route<'a> {
-    pub fn new(segments: Vec<Segment<'a>>) -> Option<Self> {
+    pub fn new(input: eth::Asset, segments: Vec<Segment<'a>>, output: eth::Asset) -> Option<Self> {
         if segments.is_empty() {
             return None;
         }
-        Some(Self { segments })
+        Some(Self {
+            input,
+            segments,
+            output,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::domain::liquidity::constant_product, ethereum_types::H160};
+
+    fn token(byte: u8) -> eth::TokenAddress {
+        eth::TokenAddress(H160([byte; 20]))
     }
 
-    fn input(&self) -> eth::Asset {
-        self.segments[0].input
+    fn order(uid: u8, sell: eth::TokenAddress, buy: eth::TokenAddress) -> order::Order {
+        order::Order {
+            uid: order::Uid([uid; 56]),
+            sell: eth::Asset {
+                token: sell,
+                amount: 1_000_000_000_000_000_000_u128.into(),
+            },
+            buy: eth::Asset {
+                token: buy,
+                amount: 1.into(),
+            },
+            fee: order::Fee(0.into()),
+            side: order::Side::Sell,
+            class: order::Class::Market,
+            partially_fillable: false,
+            reward: order::Reward(1.),
+            receiver: None,
+            valid_to: None,
+        }
     }
 
-    fn output(&self) -> eth::Asset {
-        self.segments
-            .last()
-            .expect("route has at least one segment by construction")
-            .output
+    fn pool(a: eth::TokenAddress, b: eth::TokenAddress) -> liquidity::Liquidity {
+        liquidity::Liquidity {
+            id: liquidity::Id("0".to_owned()),
+            address: H160([0xff; 20]),
+            gas: eth::Gas(110_000.into()),
+            state: liquidity::State::ConstantProduct(constant_product::Pool {
+                reserves: constant_product::Reserves::new(
+                    eth::Asset {
+                        token: a,
+                        amount: 1_000_000_000_000_000_000_000_u128.into(),
+                    },
+                    eth::Asset {
+                        token: b,
+                        amount: 1_000_000_000_000_000_000_000_u128.into(),
+                    },
+                )
+                .unwrap(),
+                fee: eth::Rational::new_raw(3.into(), 1000.into()),
+            }),
+        }
+    }
+
+    #[test]
+    fn transfer_fees_reduce_received_amounts() {
+        let weth = token(1);
+        let fot = token(2);
+        let solve = |order: order::Order, transfer_fee_bps: HashMap<eth::TokenAddress, u16>| {
+            let solver = Baseline {
+                weth: eth::WethAddress(weth.0),
+                base_tokens: Default::default(),
+                max_hops: 0,
+                transfer_fee_bps,
+                max_orders: None,
+            };
+            let auction = auction::Auction {
+                id: None,
+                tokens: Default::default(),
+                orders: vec![order],
+                liquidity: vec![pool(weth, fot)],
+                gas_price: auction::GasPrice(eth::Ether(15_000_000_000_u64.into())),
+                deadline: Default::default(),
+            };
+
+            let mut solutions = solver.solve(auction, &CancellationToken::new()).solutions;
+            assert_eq!(solutions.len(), 1);
+            let solution = solutions.remove(0);
+            let interaction = match &solution.interactions[..] {
+                [solution::Interaction::Liquidity(interaction)] => {
+                    (interaction.input.amount, interaction.output.amount)
+                }
+                _ => panic!("unexpected interactions"),
+            };
+            (interaction, solution.prices.0)
+        };
+        let fee = HashMap::from([(fot, 100)]);
+
+        // Buying a fee-on-transfer token: the pool still sends the gross
+        // amount, but the settlement contract only receives 99% of it, which
+        // is reflected in the clearing prices.
+        let buy_fot = order(1, weth, fot);
+        let ((_, gross), _) = solve(buy_fot.clone(), Default::default());
+        let ((input, output), prices) = solve(buy_fot.clone(), fee.clone());
+        assert_eq!(input, buy_fot.sell.amount);
+        assert_eq!(output, gross);
+        assert_eq!(prices[&weth], gross * 99 / 100);
+        assert_eq!(prices[&fot], buy_fot.sell.amount);
+
+        // Selling a fee-on-transfer token: the settlement contract only
+        // receives 99% of the sold amount, and the pool receives 99% of that.
+        let sell_fot = order(2, fot, weth);
+        let ((_, without_fee), _) = solve(sell_fot.clone(), Default::default());
+        let ((input, output), prices) = solve(sell_fot.clone(), fee);
+        assert_eq!(input, sell_fot.sell.amount * 99 / 100);
+        assert!(output < without_fee);
+        assert_eq!(prices[&weth], sell_fot.sell.amount);
+        assert_eq!(prices[&fot], output);
+    }
+
+    #[test]
+    fn skipped_orders_have_reasons() {
+        let weth = token(1);
+        let (a, b) = (token(2), token(3));
+        let solver = Baseline {
+            weth: eth::WethAddress(weth.0),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: None,
+        };
+        let auction = auction::Auction {
+            id: None,
+            tokens: Default::default(),
+            orders: vec![
+                order(1, weth, a),
+                order(2, weth, b),
+                order::Order {
+                    class: order::Class::Liquidity,
+                    ..order(3, weth, a)
+                },
+                order::Order {
+                    buy: eth::Asset {
+                        token: a,
+                        amount: 2_000_000_000_000_000_000_u128.into(),
+                    },
+                    ..order(4, weth, a)
+                },
+            ],
+            liquidity: vec![pool(weth, a)],
+            gas_price: auction::GasPrice(eth::Ether(15_000_000_000_u64.into())),
+            deadline: Default::default(),
+        };
+
+        let outcome = solver.solve(auction, &CancellationToken::new());
+
+        assert_eq!(outcome.solutions.len(), 1);
+        assert_eq!(
+            outcome.skipped,
+            [
+                (order::Uid([2; 56]), SkipReason::NoLiquidity),
+                (order::Uid([3; 56]), SkipReason::Filtered),
+                (order::Uid([4; 56]), SkipReason::LimitPrice),
+            ],
+        );
+    }
+
+    #[test]
+    fn stops_solving_once_cancelled() {
+        let weth = token(1);
+        let a = token(2);
+        let solver = Baseline {
+            weth: eth::WethAddress(weth.0),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: None,
+        };
+        let auction = auction::Auction {
+            id: None,
+            tokens: Default::default(),
+            orders: vec![order(1, weth, a), order(2, weth, a)],
+            liquidity: vec![pool(weth, a)],
+            gas_price: auction::GasPrice(eth::Ether(15_000_000_000_u64.into())),
+            deadline: Default::default(),
+        };
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        assert!(solver.preview(&auction, &cancel).is_empty());
+        let outcome = solver.solve(auction, &cancel);
+        assert!(outcome.solutions.is_empty());
+        assert!(outcome.skipped.is_empty());
+    }
+
+    #[test]
+    fn preview_matches_solutions() {
+        let weth = token(1);
+        let (a, b) = (token(2), token(3));
+        let solver = Baseline {
+            weth: eth::WethAddress(weth.0),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: None,
+        };
+        let auction = auction::Auction {
+            id: None,
+            tokens: Default::default(),
+            orders: vec![order(1, weth, a), order(2, a, weth), order(3, weth, b)],
+            liquidity: vec![pool(weth, a)],
+            gas_price: auction::GasPrice(eth::Ether(15_000_000_000_u64.into())),
+            deadline: Default::default(),
+        };
+
+        let preview = solver.preview(&auction, &CancellationToken::new());
+        let solutions = solver
+            .solve(auction, &CancellationToken::new())
+            .solutions
+            .iter()
+            .map(solution::Solution::summary)
+            .collect::<Vec<_>>();
+
+        assert_eq!(preview, solutions);
+        assert_eq!(
+            preview
+                .iter()
+                .flat_map(|summary| summary.orders.clone())
+                .collect::<Vec<_>>(),
+            [order::Uid([1; 56]), order::Uid([2; 56])],
+        );
     }
 }
@@ -17,22 +17,37 @@ pub struct Config {
     pub solver_name: String,
     pub chain_id: eth::ChainId,
     pub endpoint: Url,
+    pub max_orders: Option<usize>,
+    /// Whether to fall back to the naive solver when the legacy solver does
+    /// not find any solutions.
+    pub naive_fallback: bool,
 }
 
-pub struct Legacy(boundary::legacy::Legacy);
+pub struct Legacy {
+    inner: boundary::legacy::Legacy,
+    /// Maximum number of orders to forward to the legacy solver per auction.
+    pub max_orders: Option<usize>,
+    /// Solver to use for the whole auction when no solutions are found.
+    pub fallback_solver: Option<Box<super::Solver>>,
+}
 
 impl Legacy {
     pub fn new(config: Config) -> Self {
-        Self(boundary::legacy::Legacy::new(config))
+        Self {
+            max_orders: config.max_orders,
+            fallback_solver: config
+                .naive_fallback
+                .then(|| Box::new(super::Solver::Naive(super::Naive))),
+            inner: boundary::legacy::Legacy::new(config),
+        }
     }
 
-    pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
-        match self.0.solve(auction).await {
-            Ok(solution) => vec![solution],
-            Err(err) => {
-                tracing::warn!(?err, "failed to solve auction");
-                vec![]
-            }
-        }
+    /// Solves the specified auction, returning an error if the legacy solver
+    /// fails.
+    pub async fn solve(
+        &self,
+        auction: auction::Auction,
+    ) -> anyhow::Result<Vec<solution::Solution>> {
+        Ok(vec![self.inner.solve(auction).await?])
     }
 }
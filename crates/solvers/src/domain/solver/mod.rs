@@ -1,4 +1,15 @@
-use crate::domain::{auction, solution};
+use {
+    crate::{
+        domain::{auction, order, solution},
+        infra,
+    },
+    prometheus::{HistogramVec, IntCounterVec},
+    std::{
+        collections::{HashMap, HashSet},
+        time::Instant,
+    },
+    tokio_util::sync::CancellationToken,
+};
 
 pub mod balancer;
 pub mod baseline;
@@ -18,12 +29,738 @@ impl Solver {
     /// Solves a given auction and returns multiple solutions. We allow
     /// returning multiple solutions to later merge multiple non-overlapping
     /// solutions to get one big more gas efficient solution.
-    pub async fn solve(&self, auction: auction::Auction) -> Vec<solution::Solution> {
+    ///
+    /// Upstream failures are logged and result in orders being skipped. Use
+    /// [`Solver::try_solve`] to tell them apart from orders without a match.
+    /// Solving stops early with an empty outcome once `cancel` is cancelled.
+    ///
+    /// If the solver has a fallback solver configured, it is used when no
+    /// solutions are found.
+    pub async fn solve(
+        &self,
+        auction: auction::Auction,
+        cancel: &CancellationToken,
+    ) -> SolveOutcome {
+        let fallback = self
+            .fallback_solver()
+            .map(|fallback| (fallback, auction.clone()));
+        let outcome = self.solve_without_fallback(auction, cancel).await;
+        match fallback {
+            Some((fallback, auction)) if outcome.solutions.is_empty() => {
+                tracing::debug!("no solutions found; using fallback solver");
+                fallback.solve_without_fallback(auction, cancel).await
+            }
+            _ => outcome,
+        }
+    }
+
+    /// Solves a given auction like [`Solver::solve`], but returns an error if
+    /// an upstream solver or API fails.
+    pub async fn try_solve(
+        &self,
+        auction: auction::Auction,
+        cancel: &CancellationToken,
+    ) -> Result<SolveOutcome, SolveError> {
+        let fallback = self
+            .fallback_solver()
+            .map(|fallback| (fallback, auction.clone()));
+        let outcome = self
+            .try_solve_without_fallback(auction, cancel, Errors::Propagate)
+            .await?;
+        match fallback {
+            Some((fallback, auction)) if outcome.solutions.is_empty() => {
+                tracing::debug!("no solutions found; using fallback solver");
+                fallback
+                    .try_solve_without_fallback(auction, cancel, Errors::Propagate)
+                    .await
+            }
+            _ => Ok(outcome),
+        }
+    }
+
+    /// Returns the solver to use when this solver finds no solutions.
+    fn fallback_solver(&self) -> Option<&Solver> {
+        match self {
+            Solver::Legacy(solver) => solver.fallback_solver.as_deref(),
+            Solver::Balancer(solver) => solver.naive_fallback.as_deref(),
+            Solver::Baseline(_) | Solver::Naive(_) => None,
+        }
+    }
+
+    /// Solves a given auction without ever using a fallback solver. Fallback
+    /// solvers are only solved with this method, so that they can't trigger
+    /// further fallbacks and recurse indefinitely.
+    ///
+    /// Upstream failures are logged and result in orders being skipped.
+    async fn solve_without_fallback(
+        &self,
+        auction: auction::Auction,
+        cancel: &CancellationToken,
+    ) -> SolveOutcome {
+        let orders = auction
+            .orders
+            .iter()
+            .map(|order| order.uid)
+            .collect::<Vec<_>>();
+        match self
+            .try_solve_without_fallback(auction, cancel, Errors::Skip)
+            .await
+        {
+            Ok(outcome) => outcome,
+            Err(err) => {
+                tracing::warn!(?err, "failed to solve auction");
+                SolveOutcome::new(&orders, Vec::new())
+            }
+        }
+    }
+
+    /// Solves a given auction like [`Solver::solve_without_fallback`], but
+    /// returns an error if an upstream solver or API fails. With
+    /// [`Errors::Skip`], orders for which an upstream API fails are skipped
+    /// instead where the solver supports it.
+    async fn try_solve_without_fallback(
+        &self,
+        mut auction: auction::Auction,
+        cancel: &CancellationToken,
+        errors: Errors,
+    ) -> Result<SolveOutcome, SolveError> {
+        let start = Instant::now();
+        let dropped = self.limit_orders(&mut auction);
+        let orders = auction
+            .orders
+            .iter()
+            .map(|order| order.uid)
+            .collect::<Vec<_>>();
+        let outcome = cancellable(cancel, async {
+            Ok::<_, SolveError>(match self {
+                Solver::Baseline(solver) => solver.solve(auction, cancel),
+                Solver::Naive(solver) => SolveOutcome::new(&orders, solver.solve(auction)),
+                Solver::Legacy(solver) => SolveOutcome::new(
+                    &orders,
+                    solver.solve(auction).await.map_err(SolveError::Legacy)?,
+                ),
+                Solver::Balancer(solver) => solver.solve(auction, errors == Errors::Skip).await?,
+            })
+        })
+        .await;
+        // Synchronous solvers only check for cancellation between orders, so
+        // check the token again in case it was cancelled while they ran.
+        let mut outcome = match outcome {
+            Some(outcome) if !cancel.is_cancelled() => outcome?,
+            _ => {
+                let outcome = SolveOutcome::default();
+                self.record_metrics(start, &outcome);
+                return Ok(outcome);
+            }
+        };
+        outcome.skip(dropped, SkipReason::OrderLimit);
+        outcome.deduplicate();
+        self.record_metrics(start, &outcome);
+        Ok(outcome)
+    }
+
+    /// Returns the kind of solver, used for labelling metrics.
+    fn kind(&self) -> &'static str {
         match self {
-            Solver::Baseline(solver) => solver.solve(auction),
-            Solver::Naive(solver) => solver.solve(auction),
-            Solver::Legacy(solver) => solver.solve(auction).await,
-            Solver::Balancer(solver) => solver.solve(auction).await,
+            Solver::Baseline(_) => "baseline",
+            Solver::Naive(_) => "naive",
+            Solver::Legacy(_) => "legacy",
+            Solver::Balancer(_) => "balancer",
+        }
+    }
+
+    fn record_metrics(&self, start: Instant, outcome: &SolveOutcome) {
+        let metrics = Metrics::get();
+        metrics
+            .solve_duration_seconds
+            .with_label_values(&[self.kind()])
+            .observe(start.elapsed().as_secs_f64());
+        metrics
+            .solutions
+            .with_label_values(&[self.kind()])
+            .inc_by(outcome.solutions.len() as u64);
+    }
+
+    /// Reduces the auction to the solver's configured maximum number of
+    /// orders, returning the UIDs of the dropped orders.
+    fn limit_orders(&self, auction: &mut auction::Auction) -> Vec<order::Uid> {
+        let max_orders = match self {
+            Solver::Baseline(solver) => solver.max_orders,
+            Solver::Legacy(solver) => solver.max_orders,
+            Solver::Naive(_) | Solver::Balancer(_) => None,
+        };
+        let max_orders = match max_orders {
+            Some(max_orders) => max_orders,
+            None => return Vec::new(),
+        };
+
+        let dropped = auction.retain_most_valuable_orders(max_orders);
+        if !dropped.is_empty() {
+            tracing::info!(
+                dropped = dropped.len(),
+                max_orders,
+                "auction exceeds order limit; dropping least valuable orders"
+            );
+        }
+        dropped
+    }
+
+    /// Solves a given auction and returns a summary of each solution with just
+    /// the matched orders and scores, without building the solution
+    /// interactions. This allows quickly inspecting the solver results.
+    ///
+    /// Returns `None` for the legacy and Balancer solvers, which get full
+    /// solutions from an external API and have no cheaper way of solving.
+    pub fn solve_preview(
+        &self,
+        mut auction: auction::Auction,
+        cancel: &CancellationToken,
+    ) -> Option<Vec<solution::SolutionSummary>> {
+        let preview = match self {
+            Solver::Baseline(solver) => {
+                self.limit_orders(&mut auction);
+                solver.preview(&auction, cancel)
+            }
+            // The naive solver is cheap enough to just solve the auction.
+            Solver::Naive(solver) => solver
+                .solve(auction)
+                .iter()
+                .map(solution::Solution::summary)
+                .collect(),
+            Solver::Legacy(_) | Solver::Balancer(_) => return None,
+        };
+        if cancel.is_cancelled() {
+            return Some(Vec::new());
         }
+        Some(preview)
+    }
+}
+
+/// Runs the future until completion, returning `None` if the cancellation
+/// token gets cancelled first. The future is not polled at all if the token is
+/// already cancelled.
+async fn cancellable<T>(
+    cancel: &CancellationToken,
+    future: impl std::future::Future<Output = T>,
+) -> Option<T> {
+    tokio::select! {
+        biased;
+        _ = cancel.cancelled() => {
+            tracing::debug!("solving cancelled");
+            None
+        }
+        value = future => Some(value),
+    }
+}
+
+#[derive(prometheus_metric_storage::MetricStorage)]
+#[metric(subsystem = "solver_engine")]
+struct Metrics {
+    /// Time spent solving an auction, per solver kind.
+    #[metric(labels("solver"))]
+    solve_duration_seconds: HistogramVec,
+
+    /// Number of solutions produced, per solver kind.
+    #[metric(labels("solver"))]
+    solutions: IntCounterVec,
+}
+
+impl Metrics {
+    fn get() -> &'static Self {
+        Self::instance(global_metrics::get_metric_storage_registry()).unwrap()
+    }
+}
+
+/// The result of solving an auction.
+#[derive(Default)]
+pub struct SolveOutcome {
+    pub solutions: Vec<solution::Solution>,
+    /// The orders that are not part of any solution, along with the reason
+    /// why they were skipped.
+    pub skipped: Vec<(order::Uid, SkipReason)>,
+}
+
+impl SolveOutcome {
+    /// Creates a new outcome for the specified solutions, where all auction
+    /// orders not traded in any of the solutions are skipped with
+    /// [`SkipReason::NoSolution`].
+    pub fn new(orders: &[order::Uid], solutions: Vec<solution::Solution>) -> Self {
+        let traded = solutions
+            .iter()
+            .flat_map(|solution| solution.summary().orders)
+            .collect::<HashSet<_>>();
+        let skipped = orders
+            .iter()
+            .filter(|order| !traded.contains(order))
+            .map(|order| (*order, SkipReason::NoSolution))
+            .collect();
+
+        Self { solutions, skipped }
+    }
+
+    /// Removes solutions with the same clearing prices, trades and
+    /// interactions as another solution. Of such duplicates, the one with the
+    /// highest score is kept, or the first one in case of a tie.
+    fn deduplicate(&mut self) {
+        let mut best = HashMap::<_, (usize, f64)>::new();
+        for (i, solution) in self.solutions.iter().enumerate() {
+            let score = solution.summary().score;
+            best.entry(solution.content())
+                .and_modify(|best| {
+                    if score > best.1 {
+                        *best = (i, score);
+                    }
+                })
+                .or_insert((i, score));
+        }
+        let keep = best.into_values().map(|(i, _)| i).collect::<HashSet<_>>();
+        let mut i = 0;
+        self.solutions.retain(|_| {
+            i += 1;
+            keep.contains(&(i - 1))
+        });
+    }
+
+    /// Marks the specified orders as skipped for the given reason.
+    fn skip(&mut self, orders: Vec<order::Uid>, reason: SkipReason) {
+        self.skipped
+            .extend(orders.into_iter().map(|order| (order, reason)));
+    }
+}
+
+/// How upstream failures for individual orders are handled while solving.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Errors {
+    /// Skip the orders for which an upstream API fails.
+    Skip,
+    /// Fail solving the whole auction.
+    Propagate,
+}
+
+/// An upstream failure while solving an auction.
+#[derive(Debug, thiserror::Error)]
+pub enum SolveError {
+    #[error("balancer SOR API error: {0}")]
+    Balancer(#[from] infra::dex::balancer::Error),
+    #[error("legacy solver error: {0:#}")]
+    Legacy(anyhow::Error),
+}
+
+/// The reason why an order was not included in any solution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SkipReason {
+    /// The order is not supported by the solver, for example because it is a
+    /// liquidity order.
+    Filtered,
+    /// There is no liquidity for trading the order's tokens.
+    NoLiquidity,
+    /// There is liquidity for trading the order's tokens, but not at a price
+    /// that satisfies the order's limit price.
+    LimitPrice,
+    /// The solver did not find a solution for the order.
+    NoSolution,
+    /// The auction has more orders than the solver is configured to consider,
+    /// and the order was not among the most valuable ones.
+    OrderLimit,
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            domain::{dex::slippage, eth, liquidity},
+            tests::mock,
+        },
+        ethereum_types::H160,
+        serde_json::json,
+    };
+
+    fn balancer(sor: std::net::SocketAddr) -> Solver {
+        Solver::Balancer(Balancer {
+            sor: infra::dex::balancer::Sor::new(infra::dex::balancer::Config {
+                endpoint: format!("http://{sor}/sor").parse().unwrap(),
+                vault: eth::ContractAddress(H160([0xba; 20])),
+                settlement: eth::ContractAddress(H160([0x90; 20])),
+                timeout: std::time::Duration::from_secs(1),
+                max_retries: 0,
+                back_off: Default::default(),
+            }),
+            slippage: slippage::Limits::new(Default::default(), None).unwrap(),
+            baseline_fallback: None,
+            naive_fallback: None,
+        })
+    }
+
+    fn auction() -> auction::Auction {
+        auction::Auction {
+            id: None,
+            tokens: Default::default(),
+            orders: vec![order::Order {
+                uid: order::Uid([0x2a; 56]),
+                sell: eth::Asset {
+                    token: eth::TokenAddress(H160([0x11; 20])),
+                    amount: 1_000_000_000_000_000_000_u128.into(),
+                },
+                buy: eth::Asset {
+                    token: eth::TokenAddress(H160([0x22; 20])),
+                    amount: 1_000_000_000_000_000_000_u128.into(),
+                },
+                fee: order::Fee(0.into()),
+                side: order::Side::Sell,
+                class: order::Class::Market,
+                partially_fillable: false,
+                reward: order::Reward(0.),
+                receiver: None,
+                valid_to: None,
+            }],
+            liquidity: Default::default(),
+            gas_price: auction::GasPrice(eth::Ether(15_000_000_000_u64.into())),
+            deadline: Default::default(),
+        }
+    }
+
+    /// A SOR API that responds to the auction's single order with a body that
+    /// isn't a valid quote.
+    async fn broken_sor() -> std::net::SocketAddr {
+        mock::http::setup(vec![mock::http::Expectation::Post {
+            path: mock::http::Path::Any,
+            req: json!({
+                "sellToken": "0x1111111111111111111111111111111111111111",
+                "buyToken": "0x2222222222222222222222222222222222222222",
+                "orderKind": "sell",
+                "amount": "1000000000000000000",
+                "gasPrice": "15000000000",
+            }),
+            res: json!({ "error": "internal error" }),
+        }])
+        .await
+    }
+
+    #[test]
+    fn deduplicates_identical_solutions() {
+        let order = auction().orders.pop().unwrap();
+        let solution = |order: &order::Order, price: u64| solution::Solution {
+            prices: solution::ClearingPrices::new([(order.sell.token, price.into())]),
+            trades: vec![solution::Trade::Fulfillment(solution::Fulfillment::fill(
+                order.clone(),
+            ))],
+            interactions: Vec::new(),
+        };
+        let other = order::Order {
+            uid: order::Uid([0x2b; 56]),
+            ..order.clone()
+        };
+        // Rewards are not part of a solution's content, which makes it
+        // possible to tell apart which of the duplicates was kept.
+        let rewarded = |reward| order::Order {
+            reward: order::Reward(reward),
+            ..order.clone()
+        };
+
+        let mut outcome = SolveOutcome {
+            solutions: vec![
+                solution(&rewarded(1.), 1),
+                solution(&other, 1),
+                solution(&rewarded(2.), 1),
+                solution(&rewarded(3.), 2),
+            ],
+            skipped: Vec::new(),
+        };
+        outcome.deduplicate();
+
+        assert_eq!(
+            outcome
+                .solutions
+                .iter()
+                .map(|solution| {
+                    let summary = solution.summary();
+                    (summary.orders, summary.score)
+                })
+                .collect::<Vec<_>>(),
+            [
+                (vec![other.uid], 0.),
+                (vec![order.uid], 2.),
+                (vec![order.uid], 3.),
+            ],
+        );
+    }
+
+    #[tokio::test]
+    async fn considers_only_most_valuable_orders_over_limit() {
+        let solver = Solver::Baseline(Baseline {
+            weth: eth::WethAddress(H160([0xee; 20])),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: Some(2),
+        });
+
+        let mut auction = auction();
+        let template = auction.orders.pop().unwrap();
+        auction.orders = [1_u64, 3, 2]
+            .into_iter()
+            .map(|i| order::Order {
+                uid: order::Uid([i as u8; 56]),
+                sell: eth::Asset {
+                    token: template.sell.token,
+                    amount: i.into(),
+                },
+                ..template.clone()
+            })
+            .collect();
+        auction.tokens.insert(
+            template.sell.token,
+            auction::Token {
+                decimals: None,
+                symbol: None,
+                reference_price: Some(auction::Price(eth::Ether(1.into()))),
+                available_balance: 0.into(),
+                trusted: false,
+            },
+        );
+
+        let outcome = solver.solve(auction, &CancellationToken::new()).await;
+        assert_eq!(
+            outcome.skipped,
+            [
+                (order::Uid([3; 56]), SkipReason::NoLiquidity),
+                (order::Uid([2; 56]), SkipReason::NoLiquidity),
+                (order::Uid([1; 56]), SkipReason::OrderLimit),
+            ]
+        );
+    }
+
+    #[test]
+    fn previews_only_most_valuable_orders_over_limit() {
+        let solver = Solver::Baseline(Baseline {
+            weth: eth::WethAddress(H160([0xee; 20])),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: Some(1),
+        });
+
+        let mut auction = auction();
+        let template = auction.orders.pop().unwrap();
+        auction.orders = [1_u8, 2]
+            .into_iter()
+            .map(|i| order::Order {
+                uid: order::Uid([i; 56]),
+                sell: eth::Asset {
+                    token: template.sell.token,
+                    amount: (1_000_000_000_000_000_000_u128 * u128::from(i)).into(),
+                },
+                buy: eth::Asset {
+                    token: template.buy.token,
+                    amount: 1.into(),
+                },
+                ..template.clone()
+            })
+            .collect();
+        auction.tokens.insert(
+            template.sell.token,
+            auction::Token {
+                decimals: None,
+                symbol: None,
+                reference_price: Some(auction::Price(eth::Ether(1.into()))),
+                available_balance: 0.into(),
+                trusted: false,
+            },
+        );
+        let reserve = |token: eth::TokenAddress| eth::Asset {
+            token,
+            amount: 1_000_000_000_000_000_000_000_u128.into(),
+        };
+        auction.liquidity.push(liquidity::Liquidity {
+            id: liquidity::Id("0".to_owned()),
+            address: H160([0x01; 20]),
+            gas: eth::Gas(110_000.into()),
+            state: liquidity::State::ConstantProduct(liquidity::constant_product::Pool {
+                reserves: liquidity::constant_product::Reserves::new(
+                    reserve(template.sell.token),
+                    reserve(template.buy.token),
+                )
+                .unwrap(),
+                fee: eth::Rational::new(3.into(), 1000.into()),
+            }),
+        });
+
+        let preview = solver
+            .solve_preview(auction, &CancellationToken::new())
+            .unwrap();
+        assert_eq!(
+            preview
+                .iter()
+                .flat_map(|summary| summary.orders.clone())
+                .collect::<Vec<_>>(),
+            [order::Uid([2; 56])],
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_naive_without_solutions() {
+        let mut auction = auction();
+        let order = auction.orders[0].clone();
+        auction.orders.push(order::Order {
+            uid: order::Uid([0x2b; 56]),
+            sell: order.buy,
+            buy: order.sell,
+            ..order
+        });
+
+        let sor = mock::http::setup(
+            [
+                (
+                    "0x1111111111111111111111111111111111111111",
+                    "0x2222222222222222222222222222222222222222",
+                ),
+                (
+                    "0x2222222222222222222222222222222222222222",
+                    "0x1111111111111111111111111111111111111111",
+                ),
+            ]
+            .into_iter()
+            .map(|(sell, buy)| mock::http::Expectation::Post {
+                path: mock::http::Path::Any,
+                req: json!({
+                    "sellToken": sell,
+                    "buyToken": buy,
+                    "orderKind": "sell",
+                    "amount": "1000000000000000000",
+                    "gasPrice": "15000000000",
+                }),
+                res: json!({ "error": "internal error" }),
+            })
+            .collect(),
+        )
+        .await;
+        let solver = match balancer(sor) {
+            Solver::Balancer(solver) => Solver::Balancer(Balancer {
+                naive_fallback: Some(Box::new(Solver::Naive(Naive))),
+                ..solver
+            }),
+            _ => unreachable!(),
+        };
+
+        let outcome = solver.solve(auction, &CancellationToken::new()).await;
+        assert_eq!(outcome.solutions.len(), 1);
+        assert_eq!(
+            outcome.solutions[0].summary().orders,
+            [order::Uid([0x2a; 56]), order::Uid([0x2b; 56])],
+        );
+    }
+
+    #[tokio::test]
+    async fn counts_baseline_solutions() {
+        let solver = Solver::Baseline(Baseline {
+            weth: eth::WethAddress(H160([0xee; 20])),
+            base_tokens: Default::default(),
+            max_hops: 0,
+            transfer_fee_bps: Default::default(),
+            max_orders: None,
+        });
+
+        let mut auction = auction();
+        auction.orders[0].buy.amount = 1.into();
+        let reserve = |token: eth::TokenAddress| eth::Asset {
+            token,
+            amount: 1_000_000_000_000_000_000_000_u128.into(),
+        };
+        auction.liquidity.push(liquidity::Liquidity {
+            id: liquidity::Id("0".to_owned()),
+            address: H160([0x01; 20]),
+            gas: eth::Gas(110_000.into()),
+            state: liquidity::State::ConstantProduct(liquidity::constant_product::Pool {
+                reserves: liquidity::constant_product::Reserves::new(
+                    reserve(auction.orders[0].sell.token),
+                    reserve(auction.orders[0].buy.token),
+                )
+                .unwrap(),
+                fee: eth::Rational::new(3.into(), 1000.into()),
+            }),
+        });
+
+        let counter = Metrics::get().solutions.with_label_values(&["baseline"]);
+        let before = counter.get();
+        let outcome = solver.solve(auction, &CancellationToken::new()).await;
+
+        assert_eq!(outcome.solutions.len(), 1);
+        assert!(counter.get() > before);
+    }
+
+    #[tokio::test]
+    async fn cancelling_returns_promptly() {
+        let sor = mock::http::setup(vec![mock::http::Expectation::Delayed {
+            delay: std::time::Duration::from_secs(10),
+            expectation: Box::new(mock::http::Expectation::Post {
+                path: mock::http::Path::Any,
+                req: json!({
+                    "sellToken": "0x1111111111111111111111111111111111111111",
+                    "buyToken": "0x2222222222222222222222222222222222222222",
+                    "orderKind": "sell",
+                    "amount": "1000000000000000000",
+                    "gasPrice": "15000000000",
+                }),
+                res: json!({}),
+            }),
+        }])
+        .await;
+        let solver = balancer(sor);
+        let durations = Metrics::get()
+            .solve_duration_seconds
+            .with_label_values(&["balancer"]);
+        let before = durations.get_sample_count();
+
+        let cancel = CancellationToken::new();
+        tokio::spawn({
+            let cancel = cancel.clone();
+            async move {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                cancel.cancel();
+            }
+        });
+        // The SOR API request itself only times out after 1s.
+        let outcome = tokio::time::timeout(
+            std::time::Duration::from_millis(500),
+            solver.solve(auction(), &cancel),
+        )
+        .await
+        .expect("solving was not cancelled");
+
+        assert!(outcome.solutions.is_empty());
+        assert!(durations.get_sample_count() > before);
+    }
+
+    #[test]
+    fn no_preview_for_external_solvers() {
+        // The SOR API is never queried for previews.
+        let solver = balancer(([127, 0, 0, 1], 0).into());
+        assert!(solver
+            .solve_preview(auction(), &CancellationToken::new())
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn try_solve_propagates_balancer_errors() {
+        let solver = balancer(broken_sor().await);
+
+        let result = solver.try_solve(auction(), &CancellationToken::new()).await;
+        assert!(matches!(
+            result,
+            Err(SolveError::Balancer(infra::dex::balancer::Error::Json(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn solve_skips_orders_on_balancer_errors() {
+        let solver = balancer(broken_sor().await);
+
+        let outcome = solver.solve(auction(), &CancellationToken::new()).await;
+        assert!(outcome.solutions.is_empty());
+        assert_eq!(
+            outcome.skipped,
+            [(order::Uid([0x2a; 56]), SkipReason::NoSolution)]
+        );
     }
 }
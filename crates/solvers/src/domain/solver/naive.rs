@@ -3,13 +3,16 @@
 //! The naive solver is a solver that collects all orders over a single token
 //! pair, computing how many leftover tokens can't be matched peer-to-peer, and
 //! matching that excess over a Uniswap V2 pool. This allows for naive
-//! coincidence of wants over a single Uniswap V2 pools.
+//! coincidence of wants over a single Uniswap V2 pools. Token pairs without any
+//! Uniswap V2 pool can still be settled when they consist of a pair of opposing
+//! orders that fully offset each other.
 
 use {
     crate::{
         boundary,
         domain::{auction, liquidity, order, solution},
     },
+    ethereum_types::U256,
     std::collections::HashMap,
 };
 
@@ -22,7 +25,10 @@ impl Naive {
         let groups = group_by_token_pair(&auction);
         groups
             .values()
-            .filter_map(|group| boundary::naive::solve(&group.orders, group.liquidity))
+            .filter_map(|group| match group.amm {
+                Some(amm) => boundary::naive::solve(&group.orders, amm.liquidity),
+                None => match_orders(&group.orders),
+            })
             .collect()
     }
 }
@@ -30,6 +36,11 @@ impl Naive {
 #[derive(Debug)]
 struct Group<'a> {
     orders: Vec<&'a order::Order>,
+    amm: Option<Amm<'a>>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Amm<'a> {
     liquidity: &'a liquidity::Liquidity,
     pool: &'a liquidity::constant_product::Pool,
 }
@@ -39,7 +50,7 @@ type Groups<'a> = HashMap<liquidity::TokenPair, Group<'a>>;
 /// Groups an auction by token pairs, where each group contains all orders over
 /// the token pair as well as the **deepest** constant product pool (i.e. most
 /// liquidity, which translates to a higher `K` value for Uniswap V2 style
-/// constant product pools).
+/// constant product pools), if there is one.
 fn group_by_token_pair(auction: &auction::Auction) -> Groups {
     let mut groups = Groups::new();
 
@@ -49,18 +60,16 @@ fn group_by_token_pair(auction: &auction::Auction) -> Groups {
             _ => continue,
         };
 
+        let amm = Amm { liquidity, pool };
         groups
             .entry(pool.tokens())
-            .and_modify(|group| {
-                if group.pool.k() < pool.k() {
-                    group.liquidity = liquidity;
-                    group.pool = pool;
-                }
+            .and_modify(|group| match group.amm {
+                Some(deepest) if deepest.pool.k() >= pool.k() => {}
+                _ => group.amm = Some(amm),
             })
             .or_insert_with(|| Group {
                 orders: Vec::new(),
-                liquidity,
-                pool,
+                amm: Some(amm),
             });
     }
 
@@ -78,9 +87,68 @@ fn group_by_token_pair(auction: &auction::Auction) -> Groups {
 
         groups
             .entry(tokens)
-            .and_modify(|group| group.orders.push(order));
+            .or_insert_with(|| Group {
+                orders: Vec::new(),
+                amm: None,
+            })
+            .orders
+            .push(order);
     }
 
     groups.retain(|_, group| !group.orders.is_empty());
     groups
 }
+
+/// Matches a pair of opposing orders directly against each other without any
+/// AMM liquidity. This is only possible when the orders fully offset each
+/// other, since there is no liquidity to trade any excess against.
+fn match_orders(orders: &[&order::Order]) -> Option<solution::Solution> {
+    let (a, b) = match orders {
+        [a, b] => (a, b),
+        _ => return None,
+    };
+    if a.sell.token != b.buy.token || a.buy.token != b.sell.token {
+        return None;
+    }
+
+    // The amounts of `a`'s sell and buy tokens that get exchanged between the
+    // two orders. Sell orders fix the amount they sell, and buy orders the
+    // amount they buy. When the amount of a token is not fixed by either order,
+    // split the surplus evenly between them.
+    let (sold, bought) = match (a.side, b.side) {
+        (order::Side::Sell, order::Side::Sell) => (a.sell.amount, b.sell.amount),
+        (order::Side::Buy, order::Side::Buy) => (b.buy.amount, a.buy.amount),
+        (order::Side::Sell, order::Side::Buy) if a.sell.amount == b.buy.amount => {
+            (a.sell.amount, midpoint(a.buy.amount, b.sell.amount)?)
+        }
+        (order::Side::Buy, order::Side::Sell) if a.buy.amount == b.sell.amount => {
+            (midpoint(b.buy.amount, a.sell.amount)?, a.buy.amount)
+        }
+        _ => return None,
+    };
+
+    if !respects_limit_price(a, sold, bought) || !respects_limit_price(b, bought, sold) {
+        return None;
+    }
+
+    Some(solution::Solution {
+        prices: solution::ClearingPrices::new([(a.sell.token, bought), (a.buy.token, sold)]),
+        trades: vec![
+            solution::Trade::Fulfillment(solution::Fulfillment::fill((*a).clone())),
+            solution::Trade::Fulfillment(solution::Fulfillment::fill((*b).clone())),
+        ],
+        interactions: Vec::new(),
+    })
+}
+
+/// Returns the value halfway between `low` and `high`, or `None` if the range
+/// is empty.
+fn midpoint(low: U256, high: U256) -> Option<U256> {
+    (low <= high).then(|| low + (high - low) / 2)
+}
+
+/// Returns whether an order selling `sold` and buying `bought` executes at
+/// least at its limit price.
+fn respects_limit_price(order: &order::Order, sold: U256, bought: U256) -> bool {
+    bought.full_mul(order.sell.amount) >= order.buy.amount.full_mul(sold)
+}
@@ -1,14 +1,15 @@
 use {
     crate::{
-        domain::{dex::slippage, eth},
-        infra::{contracts, dex},
+        domain::{dex::slippage, eth, solver},
+        infra::{config::baseline, contracts, dex},
         util::conv,
     },
     bigdecimal::BigDecimal,
     ethereum_types::H160,
     serde::Deserialize,
     serde_with::serde_as,
-    std::path::Path,
+    shared::rate_limiter::RateLimitingStrategy,
+    std::{path::Path, time::Duration},
     tokio::fs,
 };
 
@@ -36,12 +37,57 @@ struct Config {
     /// The absolute slippage allowed by the solver.
     #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
     absolute_slippage: Option<BigDecimal>,
+
+    /// The timeout in seconds of a single SOR API request.
+    #[serde(default = "default_timeout")]
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    timeout: Duration,
+
+    /// The maximum number of times a SOR API request that failed with a
+    /// transient error (timeouts, connection errors, 5xx and 429 responses) is
+    /// retried.
+    #[serde(default)]
+    max_retries: usize,
+
+    /// Optional strategy for backing off between retries, formatted as
+    /// `<back off growth factor>,<min back off secs>,<max back off secs>`. By
+    /// default, requests are retried immediately.
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    back_off: Option<RateLimitingStrategy>,
+
+    /// Optional baseline path-finding over the auction's liquidity for orders
+    /// the SOR API does not find a swap for. The fallback is only used when
+    /// this section is specified.
+    baseline_fallback: Option<BaselineFallbackConfig>,
+
+    /// Whether to fall back to the naive solver for auctions where no
+    /// solutions are found for any of the orders.
+    #[serde(default)]
+    naive_fallback: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct BaselineFallbackConfig {
+    /// List of base tokens to use when path finding. This defines the tokens
+    /// that can appear as intermediate "hops" within a trading route. Note that
+    /// WETH is always considered as a base token.
+    #[serde(default)]
+    base_tokens: Vec<H160>,
+
+    /// The maximum number of hops to consider when finding a trading path.
+    #[serde(default = "baseline::file::default_max_hops")]
+    max_hops: usize,
 }
 
 fn default_relative_slippage() -> BigDecimal {
     BigDecimal::new(1.into(), 2) // 1%
 }
 
+fn default_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
 /// Load the driver configuration from a TOML file.
 ///
 /// # Panics
@@ -68,6 +114,9 @@ pub async fn load(path: &Path) -> super::BalancerConfig {
                 .settlement
                 .map(eth::ContractAddress)
                 .unwrap_or(contracts.settlement),
+            timeout: config.timeout,
+            max_retries: config.max_retries,
+            back_off: config.back_off.unwrap_or_default(),
         },
         slippage: slippage::Limits::new(
             config.relative_slippage,
@@ -76,5 +125,17 @@ pub async fn load(path: &Path) -> super::BalancerConfig {
             }),
         )
         .expect("invalid slippage limits"),
+        baseline_fallback: config.baseline_fallback.map(|fallback| solver::Baseline {
+            weth: contracts.weth,
+            base_tokens: fallback
+                .base_tokens
+                .into_iter()
+                .map(eth::TokenAddress)
+                .collect(),
+            max_hops: fallback.max_hops,
+            transfer_fee_bps: Default::default(),
+            max_orders: None,
+        }),
+        naive_fallback: config.naive_fallback,
     }
 }
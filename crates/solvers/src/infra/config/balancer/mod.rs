@@ -1,8 +1,13 @@
-use crate::{domain::dex::slippage, infra::dex};
+use crate::{
+    domain::{dex::slippage, solver},
+    infra::dex,
+};
 
 pub mod file;
 
 pub struct BalancerConfig {
     pub sor: dex::balancer::Config,
     pub slippage: slippage::Limits,
+    pub baseline_fallback: Option<solver::Baseline>,
+    pub naive_fallback: bool,
 }
@@ -3,7 +3,7 @@ use {
     ethereum_types::H160,
     serde::Deserialize,
     serde_with::serde_as,
-    std::path::Path,
+    std::{collections::HashMap, path::Path},
     tokio::fs,
 };
 
@@ -27,10 +27,33 @@ struct Config {
     base_tokens: Vec<eth::H160>,
 
     /// The maximum number of hops to consider when finding the optimal trading
-    /// path.
+    /// path. Larger values can find better routes at the cost of longer solve
+    /// times.
+    #[serde(default = "default_max_hops")]
     max_hops: usize,
+
+    /// Transfer fees, in basis points, of known fee-on-transfer tokens.
+    /// Amounts of these tokens received by the settlement contract or by a
+    /// pool are discounted by the fee when path finding.
+    #[serde(default)]
+    transfer_fee_bps: HashMap<eth::H160, u16>,
+
+    /// The maximum number of orders to consider per auction. Auctions with
+    /// more orders only consider the most valuable ones, bounding the time
+    /// spent path finding.
+    #[serde(default)]
+    max_orders: Option<usize>,
 }
 
+/// The default maximum number of hops, matching the one used by the legacy
+/// baseline solver.
+pub(crate) fn default_max_hops() -> usize {
+    2
+}
+
+/// Transfer fees need to be strictly smaller than 100%.
+const MAX_BPS: u16 = 10_000;
+
 /// Load the driver configuration from a TOML file.
 ///
 /// # Panics
@@ -54,6 +77,14 @@ pub async fn load(path: &Path) -> super::BaselineConfig {
         ),
     };
 
+    if let Some((token, bps)) = config
+        .transfer_fee_bps
+        .iter()
+        .find(|(_, bps)| **bps >= MAX_BPS)
+    {
+        panic!("invalid configuration: transfer fee of {bps} bps for token {token:?} is too large")
+    }
+
     super::BaselineConfig {
         weth,
         base_tokens: config
@@ -62,5 +93,11 @@ pub async fn load(path: &Path) -> super::BaselineConfig {
             .map(eth::TokenAddress)
             .collect(),
         max_hops: config.max_hops,
+        transfer_fee_bps: config
+            .transfer_fee_bps
+            .into_iter()
+            .map(|(token, bps)| (eth::TokenAddress(token), bps))
+            .collect(),
+        max_orders: config.max_orders,
     }
 }
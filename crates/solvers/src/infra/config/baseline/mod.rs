@@ -1,4 +1,4 @@
-use crate::domain::eth;
+use {crate::domain::eth, std::collections::HashMap};
 
 pub mod file;
 
@@ -6,4 +6,6 @@ pub struct BaselineConfig {
     pub weth: eth::WethAddress,
     pub base_tokens: Vec<eth::TokenAddress>,
     pub max_hops: usize,
+    pub transfer_fee_bps: HashMap<eth::TokenAddress, u16>,
+    pub max_orders: Option<usize>,
 }
@@ -25,6 +25,16 @@ struct Config {
 
     /// The URL of the endpoint that responds to solve requests.
     pub endpoint: String,
+
+    /// The maximum number of orders to forward to the solver per auction.
+    /// Auctions with more orders only include the most valuable ones.
+    #[serde(default)]
+    max_orders: Option<usize>,
+
+    /// Whether to fall back to the naive solver for auctions the solver does
+    /// not return any solutions for.
+    #[serde(default)]
+    naive_fallback: bool,
 }
 
 /// Load the driver configuration from a TOML file.
@@ -45,5 +55,7 @@ pub async fn load(path: &Path) -> legacy::Config {
         solver_name: config.solver_name,
         chain_id: config.chain_id,
         endpoint: Url::parse(&config.endpoint).unwrap(),
+        max_orders: config.max_orders,
+        naive_fallback: config.naive_fallback,
     }
 }
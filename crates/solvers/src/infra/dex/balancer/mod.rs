@@ -2,7 +2,11 @@ use {
     crate::domain::{auction, dex, eth, order},
     contracts::ethcontract::I256,
     ethereum_types::U256,
-    std::sync::atomic::{self, AtomicU64},
+    shared::rate_limiter::RateLimitingStrategy,
+    std::{
+        sync::atomic::{self, AtomicU64},
+        time::Duration,
+    },
     tracing::Instrument,
 };
 
@@ -15,6 +19,8 @@ pub struct Sor {
     endpoint: reqwest::Url,
     vault: vault::Vault,
     settlement: eth::ContractAddress,
+    max_retries: usize,
+    back_off: RateLimitingStrategy,
 }
 
 pub struct Config {
@@ -26,15 +32,46 @@ pub struct Config {
 
     /// The address of the Settlement contract.
     pub settlement: eth::ContractAddress,
+
+    /// The timeout of a single SOR API request.
+    pub timeout: Duration,
+
+    /// The maximum number of times a failed SOR API request is retried.
+    pub max_retries: usize,
+
+    /// The strategy for backing off between retries.
+    pub back_off: RateLimitingStrategy,
 }
 
 impl Sor {
     pub fn new(config: Config) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: reqwest::Client::builder()
+                .timeout(config.timeout)
+                .build()
+                .unwrap(),
             endpoint: config.endpoint,
             vault: vault::Vault::new(config.vault),
             settlement: config.settlement,
+            max_retries: config.max_retries,
+            back_off: config.back_off,
+        }
+    }
+
+    /// Quotes the specified query, retrying transient failures up to the
+    /// configured number of times.
+    async fn quote_with_retries(&self, query: &dto::Query) -> Result<dto::Quote, Error> {
+        let mut attempt = 0;
+        loop {
+            match self.quote(query).await {
+                Err(err) if attempt < self.max_retries && err.is_transient() => {
+                    let delay = self.back_off.back_off(attempt as u64);
+                    tracing::debug!(?err, attempt, ?delay, "retrying quote");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
     }
 
@@ -70,7 +107,7 @@ impl Sor {
             // of a headache.
             static ID: AtomicU64 = AtomicU64::new(0);
             let id = ID.fetch_add(1, atomic::Ordering::Relaxed);
-            self.quote(&query)
+            self.quote_with_retries(&query)
                 .instrument(tracing::trace_span!("quote", id = %id))
                 .await?
         };
@@ -165,3 +202,20 @@ pub enum Error {
     #[error(transparent)]
     Http(#[from] reqwest::Error),
 }
+
+impl Error {
+    /// Returns whether the error is transient, in which case the request can
+    /// be retried. Quotes are idempotent, so retrying them is always safe.
+    fn is_transient(&self) -> bool {
+        match self {
+            Self::NotFound | Self::Json(_) => false,
+            Self::Http(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().map_or(false, |status| {
+                        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                    })
+            }
+        }
+    }
+}
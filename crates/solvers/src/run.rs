@@ -25,6 +25,8 @@ pub async fn run(
                 weth: baseline.weth,
                 base_tokens: baseline.base_tokens.into_iter().collect(),
                 max_hops: baseline.max_hops,
+                transfer_fee_bps: baseline.transfer_fee_bps,
+                max_orders: baseline.max_orders,
             })
         }
         cli::Command::Naive => Solver::Naive(solver::Naive),
@@ -37,6 +39,10 @@ pub async fn run(
             Solver::Balancer(solver::Balancer {
                 sor: dex::balancer::Sor::new(config.sor),
                 slippage: config.slippage,
+                baseline_fallback: config.baseline_fallback,
+                naive_fallback: config
+                    .naive_fallback
+                    .then(|| Box::new(Solver::Naive(solver::Naive))),
             })
         }
     };
@@ -309,3 +309,99 @@ async fn buy() {
         }),
     );
 }
+
+/// Tests the boundary of the limit price check: a swap returning exactly the
+/// order's buy amount is accepted, while one returning a single wei less is
+/// rejected.
+#[tokio::test]
+async fn sell_at_limit_price() {
+    let quote = |return_amount: &str| mock::http::Expectation::Post {
+        path: mock::http::Path::exact("sor"),
+        req: json!({
+            "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "buyToken": "0xba100000625a3754423978a60c9317c58a424e3d",
+            "orderKind": "sell",
+            "amount": "1000000000000000000",
+            "gasPrice": "15000000000",
+        }),
+        res: json!({
+            "tokenAddresses": [
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "0xba100000625a3754423978a60c9317c58a424e3d"
+            ],
+            "swaps": [
+                {
+                    "poolId": "0x5c6ee304399dbdb9c8ef030ab642b10820db8f56000200000000000000000014",
+                    "assetInIndex": 0,
+                    "assetOutIndex": 1,
+                    "amount": "1000000000000000000",
+                    "userData": "0x",
+                    "returnAmount": return_amount,
+                }
+            ],
+            "swapAmount": "1000000000000000000",
+            "swapAmountForSwaps": "1000000000000000000",
+            "returnAmount": return_amount,
+            "returnAmountFromSwaps": return_amount,
+            "returnAmountConsideringFees": return_amount,
+            "tokenIn": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "tokenOut": "0xba100000625a3754423978a60c9317c58a424e3d",
+            "marketSp": "0.004393607339632106",
+        }),
+    };
+    let api = mock::http::setup(vec![
+        quote("227598784442065388110"),
+        quote("227598784442065388109"),
+    ])
+    .await;
+
+    let engine = tests::SolverEngine::new("balancer", balancer::config(&api)).await;
+
+    let auction = json!({
+        "id": null,
+        "tokens": {},
+        "orders": [
+            {
+                "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a",
+                "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "buyToken": "0xba100000625a3754423978a60c9317c58a424e3D",
+                "sellAmount": "1000000000000000000",
+                "buyAmount": "227598784442065388110",
+                "feeAmount": "1000000000000000",
+                "kind": "sell",
+                "partiallyFillable": false,
+                "class": "market",
+                "reward": 0.
+            }
+        ],
+        "liquidity": [],
+        "effectiveGasPrice": "15000000000",
+        "deadline": "2106-01-01T00:00:00.000Z"
+    });
+
+    let at_limit = engine.solve(auction.clone()).await;
+    assert_eq!(
+        at_limit["trades"],
+        json!([
+            {
+                "kind": "fulfillment",
+                "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                            2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                            2a2a2a2a",
+                "executedAmount": "1000000000000000000"
+            }
+        ]),
+    );
+
+    let below_limit = engine.solve(auction).await;
+    assert_eq!(
+        below_limit,
+        json!({
+            "prices": {},
+            "trades": [],
+            "interactions": [],
+        }),
+    );
+}
@@ -3,6 +3,8 @@ use {crate::tests, std::net::SocketAddr};
 mod market_order;
 mod not_found;
 mod out_of_price;
+mod retries;
+mod slippage;
 
 /// Creates a temporary file containing the config of the given solver.
 pub fn config(solver_addr: &SocketAddr) -> tests::Config {
@@ -12,3 +14,66 @@ endpoint = 'http://{solver_addr}/sor'
         ",
     ))
 }
+
+/// Creates a temporary file containing the config of the given solver with
+/// the specified relative and absolute slippage limits.
+pub fn config_with_slippage(
+    solver_addr: &SocketAddr,
+    relative: &str,
+    absolute: Option<&str>,
+) -> tests::Config {
+    let absolute = absolute
+        .map(|absolute| format!("absolute-slippage = '{absolute}'"))
+        .unwrap_or_default();
+    tests::Config::String(format!(
+        r"
+endpoint = 'http://{solver_addr}/sor'
+relative-slippage = '{relative}'
+{absolute}
+        ",
+    ))
+}
+
+/// Creates a temporary file containing the config of the given solver with
+/// the baseline fallback enabled.
+pub fn config_with_baseline_fallback(solver_addr: &SocketAddr) -> tests::Config {
+    tests::Config::String(format!(
+        r"
+endpoint = 'http://{solver_addr}/sor'
+
+[baseline-fallback]
+max-hops = 1
+        ",
+    ))
+}
+
+/// Creates a temporary file containing the config of the given solver with
+/// the specified SOR API request timeout and retries.
+pub fn config_with_retries(
+    solver_addr: &SocketAddr,
+    timeout: &str,
+    max_retries: usize,
+) -> tests::Config {
+    tests::Config::String(format!(
+        r"
+endpoint = 'http://{solver_addr}/sor'
+timeout = {timeout}
+max-retries = {max_retries}
+        ",
+    ))
+}
+
+/// Returns the Vault `batchSwap` limits of the first interaction of a
+/// solution, one per swap asset. Positive limits are the maximum amounts sent
+/// to the Vault and negative ones the minimum amounts received from it, so
+/// they reflect the slippage that was applied to the swap.
+pub fn swap_limits(solution: &serde_json::Value, assets: usize) -> Vec<i128> {
+    let calldata = solution["interactions"][0]["calldata"].as_str().unwrap();
+    let calldata = hex::decode(calldata.strip_prefix("0x").unwrap()).unwrap();
+    // The limits are the last dynamic parameter of `batchSwap` and, since the
+    // deadline is static, at the very end of the calldata.
+    calldata[calldata.len() - assets * 32..]
+        .chunks(32)
+        .map(|word| i128::from_be_bytes(word[16..].try_into().unwrap()))
+        .collect()
+}
@@ -70,3 +70,107 @@ async fn test() {
         }),
     );
 }
+
+/// Tests that orders for which the SOR API finds no swap are routed over the
+/// auction's liquidity when the baseline fallback is enabled.
+#[tokio::test]
+async fn baseline_fallback() {
+    let api = mock::http::setup(vec![mock::http::Expectation::Post {
+        path: mock::http::Path::Any,
+        req: json!({
+            "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "buyToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+            "orderKind": "sell",
+            "amount": "133700000000000000",
+            "gasPrice": "15000000000",
+        }),
+        res: json!({
+            "tokenAddresses": [],
+            "swaps": [],
+            "swapAmount": "0",
+            "swapAmountForSwaps": "0",
+            "returnAmount": "0",
+            "returnAmountFromSwaps": "0",
+            "returnAmountConsideringFees": "0",
+            "tokenIn": "",
+            "tokenOut": "",
+            "marketSp": "0",
+        }),
+    }])
+    .await;
+
+    let engine =
+        tests::SolverEngine::new("balancer", balancer::config_with_baseline_fallback(&api)).await;
+
+    let solution = engine
+        .solve(json!({
+            "id": null,
+            "tokens": {},
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB",
+                    "sellAmount": "133700000000000000",
+                    "buyAmount": "6000000000000000000000",
+                    "feeAmount": "4200000000000000",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                },
+            ],
+            "liquidity": [
+                {
+                    "kind": "constantproduct",
+                    "tokens": {
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "3828187314911751990"
+                        },
+                        "0xDEf1CA1fb7FBcDC777520aa7f396b4E015F497aB": {
+                            "balance": "179617892578796375604692"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x97b744df0b59d93A866304f97431D8EfAd29a08d",
+                    "gasEstimate": "110000"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "prices": {
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "6043910341261930467761",
+                "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab": "133700000000000000"
+            },
+            "trades": [
+                {
+                    "kind": "fulfillment",
+                    "order": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                                2a2a2a2a",
+                    "executedAmount": "133700000000000000"
+                }
+            ],
+            "interactions": [
+                {
+                    "kind": "liquidity",
+                    "internalize": false,
+                    "id": "0",
+                    "inputToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                    "outputToken": "0xdef1ca1fb7fbcdc777520aa7f396b4e015f497ab",
+                    "inputAmount": "133700000000000000",
+                    "outputAmount": "6043910341261930467761"
+                }
+            ]
+        }),
+    );
+}
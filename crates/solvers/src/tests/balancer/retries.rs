@@ -0,0 +1,123 @@
+//! Tests that the Balancer solver retries SOR API requests that time out.
+
+use {
+    crate::tests::{self, balancer, mock},
+    serde_json::json,
+    std::time::Duration,
+};
+
+fn sor_request() -> serde_json::Value {
+    json!({
+        "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        "buyToken": "0xba100000625a3754423978a60c9317c58a424e3d",
+        "orderKind": "sell",
+        "amount": "1000000000000000000",
+        "gasPrice": "15000000000",
+    })
+}
+
+fn sor_response() -> serde_json::Value {
+    json!({
+        "tokenAddresses": [
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "0xba100000625a3754423978a60c9317c58a424e3d"
+        ],
+        "swaps": [
+            {
+                "poolId": "0x5c6ee304399dbdb9c8ef030ab642b10820db8f56000200000000000000000014",
+                "assetInIndex": 0,
+                "assetOutIndex": 1,
+                "amount": "1000000000000000000",
+                "userData": "0x",
+                "returnAmount": "227598784442065388110"
+            }
+        ],
+        "swapAmount": "1000000000000000000",
+        "swapAmountForSwaps": "1000000000000000000",
+        "returnAmount": "227598784442065388110",
+        "returnAmountFromSwaps": "227598784442065388110",
+        "returnAmountConsideringFees": "227307710853355710706",
+        "tokenIn": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        "tokenOut": "0xba100000625a3754423978a60c9317c58a424e3d",
+        "marketSp": "0.004393607339632106",
+    })
+}
+
+fn slow() -> mock::http::Expectation {
+    mock::http::Expectation::Delayed {
+        delay: Duration::from_secs(1),
+        expectation: Box::new(fast()),
+    }
+}
+
+fn fast() -> mock::http::Expectation {
+    mock::http::Expectation::Post {
+        path: mock::http::Path::exact("sor"),
+        req: sor_request(),
+        res: sor_response(),
+    }
+}
+
+fn auction() -> serde_json::Value {
+    json!({
+        "id": null,
+        "tokens": {},
+        "orders": [
+            {
+                "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                          2a2a2a2a",
+                "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                "buyToken": "0xba100000625a3754423978a60c9317c58a424e3D",
+                "sellAmount": "1000000000000000000",
+                "buyAmount": "200000000000000000000",
+                "feeAmount": "1000000000000000",
+                "kind": "sell",
+                "partiallyFillable": false,
+                "class": "market",
+                "reward": 0.
+            }
+        ],
+        "liquidity": [],
+        "effectiveGasPrice": "15000000000",
+        "deadline": "2106-01-01T00:00:00.000Z"
+    })
+}
+
+#[tokio::test]
+async fn slow_then_fast() {
+    let api = mock::http::setup(vec![slow(), fast()]).await;
+
+    let engine =
+        tests::SolverEngine::new("balancer", balancer::config_with_retries(&api, "0.1", 1)).await;
+
+    let solution = engine.solve(auction()).await;
+
+    assert_eq!(
+        solution["prices"],
+        json!({
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": "227598784442065388110",
+            "0xba100000625a3754423978a60c9317c58a424e3d": "1000000000000000000"
+        }),
+    );
+    assert_eq!(solution["trades"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn retries_exhausted() {
+    let api = mock::http::setup(vec![slow(), slow()]).await;
+
+    let engine =
+        tests::SolverEngine::new("balancer", balancer::config_with_retries(&api, "0.1", 1)).await;
+
+    let solution = engine.solve(auction()).await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "prices": {},
+            "trades": [],
+            "interactions": [],
+        }),
+    );
+}
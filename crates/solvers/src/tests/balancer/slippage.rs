@@ -0,0 +1,117 @@
+//! This test verifies that the Balancer solver applies the configured slippage
+//! limits to swaps. The relative slippage is capped by the absolute slippage,
+//! which is denominated in Ether.
+
+use {
+    crate::tests::{self, balancer, mock},
+    serde_json::json,
+};
+
+/// Solves a 1 WETH to BAL sell order with the specified slippage limits and
+/// returns the `batchSwap` limits of the solution.
+async fn swap_limits(relative: &str, absolute: Option<&str>) -> Vec<i128> {
+    let api = mock::http::setup(vec![mock::http::Expectation::Post {
+        path: mock::http::Path::exact("sor"),
+        req: json!({
+            "sellToken": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "buyToken": "0xba100000625a3754423978a60c9317c58a424e3d",
+            "orderKind": "sell",
+            "amount": "1000000000000000000",
+            "gasPrice": "15000000000",
+        }),
+        res: json!({
+            "tokenAddresses": [
+                "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "0xba100000625a3754423978a60c9317c58a424e3d"
+            ],
+            "swaps": [
+                {
+                    "poolId": "0x5c6ee304399dbdb9c8ef030ab642b10820db8f56000200000000000000000014",
+                    "assetInIndex": 0,
+                    "assetOutIndex": 1,
+                    "amount": "1000000000000000000",
+                    "userData": "0x",
+                    "returnAmount": "227598784442065388110"
+                }
+            ],
+            "swapAmount": "1000000000000000000",
+            "swapAmountForSwaps": "1000000000000000000",
+            "returnAmount": "227598784442065388110",
+            "returnAmountFromSwaps": "227598784442065388110",
+            "returnAmountConsideringFees": "227307710853355710706",
+            "tokenIn": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+            "tokenOut": "0xba100000625a3754423978a60c9317c58a424e3d",
+            "marketSp": "0.004393607339632106",
+        }),
+    }])
+    .await;
+
+    let engine = tests::SolverEngine::new(
+        "balancer",
+        balancer::config_with_slippage(&api, relative, absolute),
+    )
+    .await;
+
+    let solution = engine
+        .solve(json!({
+            "id": null,
+            "tokens": {
+                "0xba100000625a3754423978a60c9317c58a424e3D": {
+                    "decimals": 18,
+                    "symbol": "BAL",
+                    "referencePrice": "4327903683155778",
+                    "availableBalance": "1583034704488033979459",
+                    "trusted": true
+                },
+                "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                    "decimals": 18,
+                    "symbol": "WETH",
+                    "referencePrice": "1000000000000000000",
+                    "availableBalance": "482725140468789680",
+                    "trusted": true
+                },
+            },
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+                    "buyToken": "0xba100000625a3754423978a60c9317c58a424e3D",
+                    "sellAmount": "1000000000000000000",
+                    "buyAmount": "200000000000000000000",
+                    "feeAmount": "1000000000000000",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.
+                }
+            ],
+            "liquidity": [],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await;
+
+    balancer::swap_limits(&solution, 2)
+}
+
+#[tokio::test]
+async fn relative() {
+    // 1% of the 1 WETH swap is below the 0.02 ETH absolute slippage, so the
+    // relative slippage is used.
+    assert_eq!(
+        swap_limits("0.01", Some("0.02")).await,
+        [1_000_000_000_000_000_000, -225_322_796_597_644_734_228],
+    );
+}
+
+#[tokio::test]
+async fn capped_by_absolute() {
+    // 1% of the 1 WETH swap exceeds the 0.005 ETH absolute slippage, so the
+    // slippage is capped to 0.5%.
+    assert_eq!(
+        swap_limits("0.01", Some("0.005")).await,
+        [1_000_000_000_000_000_000, -226_460_790_519_855_061_169],
+    );
+}
@@ -0,0 +1,121 @@
+//! Test case that verifies that the baseline solver only considers trading
+//! paths with up to the configured maximum number of hops.
+
+use {crate::tests, serde_json::json};
+
+/// Solves an auction selling `0x1111..` for `0x2222..`, where the two tokens
+/// are only connected through WETH and `0x3333..`, i.e. over two hops.
+async fn solve(max_hops: usize) -> serde_json::Value {
+    let engine = tests::SolverEngine::new(
+        "baseline",
+        tests::Config::String(format!(
+            r"
+weth = '0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2'
+base-tokens = ['0x3333333333333333333333333333333333333333']
+max-hops = {max_hops}
+            ",
+        )),
+    )
+    .await;
+
+    engine
+        .solve(json!({
+            "id": null,
+            "tokens": {},
+            "orders": [
+                {
+                    "uid": "0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a\
+                              2a2a2a2a",
+                    "sellToken": "0x1111111111111111111111111111111111111111",
+                    "buyToken": "0x2222222222222222222222222222222222222222",
+                    "sellAmount": "1000000000000000000",
+                    "buyAmount": "1",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                }
+            ],
+            "liquidity": [
+                {
+                    "kind": "constantproduct",
+                    "tokens": {
+                        "0x1111111111111111111111111111111111111111": {
+                            "balance": "1000000000000000000000"
+                        },
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "1000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "0",
+                    "address": "0x0000000000000000000000000000000000000001",
+                    "gasEstimate": "110000"
+                },
+                {
+                    "kind": "constantproduct",
+                    "tokens": {
+                        "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": {
+                            "balance": "1000000000000000000000"
+                        },
+                        "0x3333333333333333333333333333333333333333": {
+                            "balance": "1000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "1",
+                    "address": "0x0000000000000000000000000000000000000002",
+                    "gasEstimate": "110000"
+                },
+                {
+                    "kind": "constantproduct",
+                    "tokens": {
+                        "0x3333333333333333333333333333333333333333": {
+                            "balance": "1000000000000000000000"
+                        },
+                        "0x2222222222222222222222222222222222222222": {
+                            "balance": "1000000000000000000000"
+                        }
+                    },
+                    "fee": "0.003",
+                    "id": "2",
+                    "address": "0x0000000000000000000000000000000000000003",
+                    "gasEstimate": "110000"
+                }
+            ],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z"
+        }))
+        .await
+}
+
+#[tokio::test]
+async fn finds_path_within_max_hops() {
+    let solution = solve(2).await;
+
+    assert_eq!(solution["trades"].as_array().unwrap().len(), 1);
+    let interactions = solution["interactions"].as_array().unwrap();
+    assert_eq!(
+        interactions
+            .iter()
+            .map(|interaction| interaction["id"].as_str().unwrap())
+            .collect::<Vec<_>>(),
+        ["0", "1", "2"],
+    );
+}
+
+#[tokio::test]
+async fn ignores_path_exceeding_max_hops() {
+    let solution = solve(1).await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "prices": {},
+            "trades": [],
+            "interactions": [],
+        }),
+    );
+}
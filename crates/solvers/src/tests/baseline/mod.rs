@@ -1,3 +1,4 @@
 //! Baseline solver test cases.
 
 mod direct_swap;
+mod max_hops;
@@ -2,6 +2,7 @@ use std::{
     fmt::{self, Debug, Formatter},
     net::SocketAddr,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 #[derive(Clone)]
@@ -58,10 +59,19 @@ pub enum Expectation {
         req: serde_json::Value,
         res: serde_json::Value,
     },
+    /// Responds to the request according to the wrapped expectation, but only
+    /// after the specified delay.
+    Delayed {
+        delay: Duration,
+        expectation: Box<Expectation>,
+    },
 }
 
-/// Set up an mock external HTTP API.
-pub async fn setup(expectations: Vec<Expectation>) -> SocketAddr {
+/// Set up an mock external HTTP API. The expectations are matched against the
+/// requests in order.
+pub async fn setup(mut expectations: Vec<Expectation>) -> SocketAddr {
+    // Expectations are popped off the back of the list.
+    expectations.reverse();
     let state = Arc::new(Mutex::new(expectations));
     let app = axum::Router::new()
         .route(
@@ -70,7 +80,7 @@ pub async fn setup(expectations: Vec<Expectation>) -> SocketAddr {
                 |axum::extract::State(state),
                  axum::extract::Path(path),
                  axum::extract::RawQuery(query)| async move {
-                    axum::response::Json(get(state, Some(path), query))
+                    respond(get(state, Some(path), query)).await
                 },
             )
             .post(
@@ -78,7 +88,7 @@ pub async fn setup(expectations: Vec<Expectation>) -> SocketAddr {
                  axum::extract::Path(path),
                  axum::extract::RawQuery(query),
                  axum::extract::Json(req)| async move {
-                    axum::response::Json(post(state, Some(path), query, req))
+                    respond(post(state, Some(path), query, req)).await
                 },
             ),
         )
@@ -88,14 +98,14 @@ pub async fn setup(expectations: Vec<Expectation>) -> SocketAddr {
             "/",
             axum::routing::get(
                 |axum::extract::State(state), axum::extract::RawQuery(query)| async move {
-                    axum::response::Json(get(state, None, query))
+                    respond(get(state, None, query)).await
                 },
             )
             .post(
                 |axum::extract::State(state),
                  axum::extract::RawQuery(query),
                  axum::extract::Json(req)| async move {
-                    axum::response::Json(post(state, None, query, req))
+                    respond(post(state, None, query, req)).await
                 },
             ),
         )
@@ -109,8 +119,23 @@ pub async fn setup(expectations: Vec<Expectation>) -> SocketAddr {
 #[derive(Debug, Clone)]
 struct State(Arc<Mutex<Vec<Expectation>>>);
 
-fn get(state: State, path: Option<String>, query: Option<String>) -> serde_json::Value {
-    let expectation = state.0.lock().unwrap().pop();
+async fn respond(
+    (res, delay): (serde_json::Value, Duration),
+) -> axum::response::Json<serde_json::Value> {
+    tokio::time::sleep(delay).await;
+    axum::response::Json(res)
+}
+
+/// Pops the next expectation, unwrapping its response delay if any.
+fn next(state: &State) -> (Option<Expectation>, Duration) {
+    match state.0.lock().unwrap().pop() {
+        Some(Expectation::Delayed { delay, expectation }) => (Some(*expectation), delay),
+        expectation => (expectation, Duration::ZERO),
+    }
+}
+
+fn get(state: State, path: Option<String>, query: Option<String>) -> (serde_json::Value, Duration) {
+    let (expectation, delay) = next(&state);
     let (expected_path, res) = match expectation {
         Some(Expectation::Get { path, res }) => (path, res),
         Some(other) => panic!("expected GET request but got {other:?}"),
@@ -119,7 +144,7 @@ fn get(state: State, path: Option<String>, query: Option<String>) -> serde_json:
 
     let full_path = full_path(path, query);
     assert_eq!(full_path, expected_path, "GET request has unexpected path");
-    res
+    (res, delay)
 }
 
 fn post(
@@ -127,8 +152,8 @@ fn post(
     path: Option<String>,
     query: Option<String>,
     req: serde_json::Value,
-) -> serde_json::Value {
-    let expectation = state.0.lock().unwrap().pop();
+) -> (serde_json::Value, Duration) {
+    let (expectation, delay) = next(&state);
     let (expected_path, expected_req, res) = match expectation {
         Some(Expectation::Post { path, req, res }) => (path, req, res),
         Some(other) => panic!("expected POST request but got {other:?}"),
@@ -138,7 +163,7 @@ fn post(
     let full_path = full_path(path, query);
     assert_eq!(full_path, expected_path, "POST request has unexpected path");
     assert_eq!(req, expected_req, "POST request has unexpected body");
-    res
+    (res, delay)
 }
 
 fn full_path(path: Option<String>, query: Option<String>) -> String {
@@ -13,7 +13,7 @@ use {
 mod balancer;
 mod baseline;
 mod legacy;
-mod mock;
+pub(crate) mod mock;
 mod naive;
 
 /// A solver engine handle for E2E testing.
@@ -5,4 +5,5 @@ mod matches_orders;
 mod reserves_too_small;
 mod rounds_prices_in_favour_of_traders;
 mod swap_less_than_reserves;
+mod without_liquidity;
 mod without_pool;
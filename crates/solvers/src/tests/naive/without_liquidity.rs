@@ -0,0 +1,133 @@
+//! Tests that verify that the naive solver matches opposing orders on token
+//! pairs without any liquidity, as long as they fully offset each other. Any
+//! excess would need to be routed through an AMM, so partially offsetting
+//! orders on such pairs are not matched at all.
+
+use {crate::tests, serde_json::json};
+
+#[tokio::test]
+async fn fully_offsetting_orders() {
+    let engine = tests::SolverEngine::new("naive", tests::Config::None).await;
+
+    let solution = engine
+        .solve(json!({
+            "id": null,
+            "tokens": {},
+            "orders": [
+                {
+                    "uid": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                              0101010101010101010101010101010101010101\
+                              01010101",
+                    "sellToken": "0x000000000000000000000000000000000000000a",
+                    "buyToken": "0x000000000000000000000000000000000000000b",
+                    "sellAmount": "1000000000000000000000",
+                    "buyAmount": "900000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                },
+                {
+                    "uid": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                              0202020202020202020202020202020202020202\
+                              02020202",
+                    "sellToken": "0x000000000000000000000000000000000000000b",
+                    "buyToken": "0x000000000000000000000000000000000000000a",
+                    "sellAmount": "1000000000000000000000",
+                    "buyAmount": "950000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                },
+            ],
+            "liquidity": [],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+        }))
+        .await;
+
+    assert_eq!(
+        solution,
+        json!({
+            "prices": {
+                "0x000000000000000000000000000000000000000a": "1000000000000000000000",
+                "0x000000000000000000000000000000000000000b": "1000000000000000000000",
+            },
+            "trades": [
+                {
+                    "kind": "fulfillment",
+                    "order": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                                0101010101010101010101010101010101010101\
+                                01010101",
+                    "executedAmount": "1000000000000000000000",
+                },
+                {
+                    "kind": "fulfillment",
+                    "order": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                                0202020202020202020202020202020202020202\
+                                02020202",
+                    "executedAmount": "1000000000000000000000",
+                },
+            ],
+            "interactions": [],
+        }),
+    );
+}
+
+#[tokio::test]
+async fn partially_offsetting_orders_are_not_matched() {
+    let engine = tests::SolverEngine::new("naive", tests::Config::None).await;
+
+    let solution = engine
+        .solve(json!({
+            "id": null,
+            "tokens": {},
+            "orders": [
+                {
+                    "uid": "0x0101010101010101010101010101010101010101010101010101010101010101\
+                              0101010101010101010101010101010101010101\
+                              01010101",
+                    "sellToken": "0x000000000000000000000000000000000000000a",
+                    "buyToken": "0x000000000000000000000000000000000000000b",
+                    "sellAmount": "1000000000000000000000",
+                    "buyAmount": "1000000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                },
+                {
+                    "uid": "0x0202020202020202020202020202020202020202020202020202020202020202\
+                              0202020202020202020202020202020202020202\
+                              02020202",
+                    "sellToken": "0x000000000000000000000000000000000000000b",
+                    "buyToken": "0x000000000000000000000000000000000000000a",
+                    "sellAmount": "500000000000000000000",
+                    "buyAmount": "500000000000000000000",
+                    "feeAmount": "0",
+                    "kind": "sell",
+                    "partiallyFillable": false,
+                    "class": "market",
+                    "reward": 0.,
+                },
+            ],
+            "liquidity": [],
+            "effectiveGasPrice": "15000000000",
+            "deadline": "2106-01-01T00:00:00.000Z",
+        }))
+        .await;
+
+    // The excess can't be traded against any AMM, so there is no solution.
+    assert_eq!(
+        solution,
+        json!({
+            "prices": {},
+            "trades": [],
+            "interactions": [],
+        }),
+    );
+}
@@ -0,0 +1,2 @@
+-- Allows listing all orders that share the same app data, newest first.
+CREATE INDEX order_app_data ON orders USING BTREE (app_data, creation_timestamp DESC);